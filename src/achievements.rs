@@ -0,0 +1,126 @@
+// Achievements unlocked by milestones during play
+// Design Decision: Each achievement is a row in a static table with a check over a small
+// progress snapshot, so adding one is a single entry; unlocks are stored by id
+use crate::paths;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// What the checks can look at, sampled every tick
+struct Progress {
+    food: u32,            // Food eaten this game
+    lifetime_food: u64,   // Food eaten in every game, including this one
+    play_time: Duration,  // Game time survived this game
+    board_fill: f64,      // Fraction of the open floor covered by the snake
+}
+
+// One unlockable milestone
+pub struct Achievement {
+    pub id: &'static str,              // Stable key in the unlock file
+    pub name: &'static str,
+    pub description: &'static str,
+    check: fn(&Progress) -> bool,
+}
+
+static ACHIEVEMENTS: [Achievement; 5] = [
+    Achievement {
+        id: "first-bite",
+        name: "First Bite",
+        description: "Eat your first food",
+        check: |progress| progress.food >= 1,
+    },
+    Achievement {
+        id: "hungry",
+        name: "Hungry Hungry Snek",
+        description: "Eat 25 food in one game",
+        check: |progress| progress.food >= 25,
+    },
+    Achievement {
+        id: "centurion",
+        name: "Centurion",
+        description: "Eat 100 food",
+        check: |progress| progress.lifetime_food >= 100,
+    },
+    Achievement {
+        id: "survivor",
+        name: "Survivor",
+        description: "Survive 5 minutes",
+        check: |progress| progress.play_time >= Duration::from_secs(5 * 60),
+    },
+    Achievement {
+        id: "space-filler",
+        name: "Space Filler",
+        description: "Fill 50% of the board",
+        check: |progress| progress.board_fill >= 0.5,
+    },
+];
+
+// Unlock state for the player, plus food eaten in earlier games
+pub struct Achievements {
+    unlocked: Vec<String>,  // Ids of everything earned so far
+    earlier_food: u64,      // Lifetime food before this game
+}
+
+impl Achievements {
+    // Reads the unlocked ids; `earlier_food` comes from the lifetime stats
+    pub fn load(earlier_food: u64) -> Achievements {
+        let unlocked = fs::read_to_string(unlock_path())
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect();
+        Achievements { unlocked, earlier_food }
+    }
+
+    // Unlocks whatever the progress now satisfies, returning the new ones
+    // Design Decision: Saved right away so an unlock survives the terminal being closed mid-game
+    pub fn check(&mut self, food: u32, play_time: Duration, board_fill: f64) -> Vec<&'static Achievement> {
+        let progress = Progress {
+            food,
+            lifetime_food: self.earlier_food + u64::from(food),
+            play_time,
+            board_fill,
+        };
+        let earned: Vec<&'static Achievement> = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| !self.is_unlocked(achievement) && (achievement.check)(&progress))
+            .collect();
+        if !earned.is_empty() {
+            self.unlocked.extend(earned.iter().map(|achievement| achievement.id.to_string()));
+            // A failed write only costs the unlock being shown again next time
+            let _ = self.save();
+        }
+        earned
+    }
+
+    // Whether an achievement has been earned
+    fn is_unlocked(&self, achievement: &Achievement) -> bool {
+        self.has(achievement.id)
+    }
+
+    // Whether the achievement with this id has been earned, for rewards tied to it
+    pub fn has(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == id)
+    }
+
+    // Every achievement with whether it's unlocked, for `snekrs stats`
+    pub fn list(&self) -> impl Iterator<Item = (&'static Achievement, bool)> + '_ {
+        ACHIEVEMENTS.iter().map(|achievement| (achievement, self.is_unlocked(achievement)))
+    }
+
+    // Writes the unlocked ids to disk
+    fn save(&self) -> std::io::Result<()> {
+        let path = unlock_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.unlocked.join("\n") + "\n")
+    }
+}
+
+// Where unlocks are kept
+fn unlock_path() -> PathBuf {
+    paths::data_dir().join("achievements.txt")
+}
@@ -0,0 +1,263 @@
+// Computer-controlled snake steering
+// Design Decision: Bots only see a read-only GameState and answer with a direction, so new
+// strategies plug in without touching the update loop
+use crate::game::{Direction, Position};
+use crate::state::GameState;
+use rand::rngs::StdRng;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use std::collections::VecDeque;
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+// A strategy that steers one snake
+pub trait Bot {
+    // Picks the next direction for the snake at `player` in the state
+    fn next_move(&mut self, state: &GameState, player: usize) -> Direction;
+}
+
+// Selectable bot strength
+#[derive(Clone, Copy)]
+pub enum Difficulty {
+    Easy,    // Wanders randomly, only avoiding instant death
+    Medium,  // Greedy shortest path to the food with a survival check
+    Hard,    // Follows a Hamiltonian cycle, taking safe shortcuts
+}
+
+impl Difficulty {
+    // Parses a CLI name (easy, medium, hard)
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        match name {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    // CLI name, also used in replay files
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    // Builds a fresh bot of this difficulty
+    // Design Decision: Bots that roll dice get their own seed so replays stay deterministic
+    pub fn bot(self, seed: u64) -> Box<dyn Bot> {
+        match self {
+            Difficulty::Easy => Box::new(RandomBot { rng: StdRng::seed_from_u64(seed) }),
+            Difficulty::Medium => Box::new(GreedyBot),
+            Difficulty::Hard => Box::new(CycleBot),
+        }
+    }
+}
+
+// Easy: picks any move that doesn't crash this tick
+struct RandomBot {
+    rng: StdRng,
+}
+
+impl Bot for RandomBot {
+    fn next_move(&mut self, state: &GameState, player: usize) -> Direction {
+        let current = state.snake(player).direction;
+        let moves = safe_moves(state, player);
+
+        // Mostly keep going straight so the wandering doesn't look jittery
+        if moves.iter().any(|(direction, _)| *direction == current) && self.rng.gen_bool(0.7) {
+            return current;
+        }
+        moves.choose(&mut self.rng).map(|(direction, _)| *direction).unwrap_or(current)
+    }
+}
+
+// Medium: breadth-first search to the food, falling back to the move that keeps the
+// most open space when the food is unreachable, so the bot rarely traps itself
+struct GreedyBot;
+
+impl Bot for GreedyBot {
+    fn next_move(&mut self, state: &GameState, player: usize) -> Direction {
+        let snake = state.snake(player);
+        let moves = safe_moves(state, player);
+
+        // Survival check: only chase food through moves that leave room for the whole body
+        let roomy: Vec<(Direction, Position)> = moves
+            .iter()
+            .copied()
+            .filter(|(_, pos)| open_area(state, *pos) > snake.body.len())
+            .collect();
+        if let Some(direction) = path_to_food(state, snake.head(), &roomy) {
+            return direction;
+        }
+
+        // No route to the food: stay alive by heading for the roomiest neighbour
+        moves
+            .iter()
+            .max_by_key(|(_, pos)| open_area(state, *pos))
+            .map(|(direction, _)| *direction)
+            .unwrap_or(snake.direction)
+    }
+}
+
+// Hard: walks a Hamiltonian cycle over the board, which can never self-trap, and cuts
+// across it towards the food whenever the shortcut can't overtake its own tail
+// Design Decision: Boards with interior walls have no simple cycle, so it plays greedy there
+struct CycleBot;
+
+// Position of every interior cell along the cycle
+struct Cycle {
+    width: u16,
+    order: Vec<usize>,  // Cycle index per board cell (usize::MAX for the border)
+    len: usize,
+}
+
+impl Cycle {
+    // Builds a cycle for an empty board, if its dimensions allow one
+    // Design Decision: Snake through the rows and return up a reserved first column;
+    // this needs an even number of rows (or columns, by swapping axes), and no portals to
+    // jump it off the cycle
+    fn build(state: &GameState) -> Option<Cycle> {
+        let (width, height) = (state.width(), state.height());
+        let (inner_w, inner_h) = (width as usize - 2, height as usize - 2);
+        let interior = (1..height - 1).flat_map(|y| (1..width - 1).map(move |x| Position { x, y }));
+        if inner_w < 2 || inner_h < 2 || interior.clone().any(|pos| state.is_wall(&pos) || state.through_portal(pos) != pos) {
+            return None;
+        }
+
+        // Walk in (column, row) terms, transposing when only the width is even
+        let transpose = inner_h % 2 == 1;
+        let (cols, rows) = if transpose { (inner_h, inner_w) } else { (inner_w, inner_h) };
+        if rows % 2 == 1 {
+            return None;
+        }
+        let mut path: Vec<(usize, usize)> = vec![(0, 0)];
+        for row in 0..rows {
+            if row % 2 == 0 {
+                path.extend((1..cols).map(|col| (col, row)));
+            } else {
+                path.extend((1..cols).rev().map(|col| (col, row)));
+            }
+        }
+        path.extend((1..rows).rev().map(|row| (0, row)));
+
+        let mut order = vec![usize::MAX; width as usize * height as usize];
+        for (step, &(col, row)) in path.iter().enumerate() {
+            let (x, y) = if transpose { (row, col) } else { (col, row) };
+            order[(y + 1) * width as usize + (x + 1)] = step;
+        }
+        Some(Cycle { width, order, len: path.len() })
+    }
+
+    // Cycle index of a cell
+    fn index(&self, pos: Position) -> usize {
+        self.order[pos.y as usize * self.width as usize + pos.x as usize]
+    }
+
+    // Steps needed to go from one cell to another along the cycle
+    fn distance(&self, from: Position, to: Position) -> usize {
+        (self.index(to) + self.len - self.index(from)) % self.len
+    }
+}
+
+impl Bot for CycleBot {
+    fn next_move(&mut self, state: &GameState, player: usize) -> Direction {
+        // Rebuilt every move: it's cheap, and campaign stages can swap the board underneath us
+        let Some(cycle) = Cycle::build(state) else {
+            return GreedyBot.next_move(state, player);
+        };
+
+        let snake = state.snake(player);
+        let (head, tail) = (snake.head(), *snake.body.back().unwrap());
+
+        // Shortcuts must stay well behind the tail, and stop once the body fills half the board
+        let room = if snake.body.len() == 1 { cycle.len } else { cycle.distance(head, tail) };
+        let shortcuts_allowed = snake.body.len() < cycle.len / 2;
+        let best = safe_moves(state, player)
+            .into_iter()
+            .filter(|(_, pos)| {
+                let ahead = cycle.distance(head, *pos);
+                ahead == 1 || (shortcuts_allowed && ahead + 3 < room)
+            })
+            // With only poison out, plain cycle-following is the safest move
+            .min_by_key(|(_, pos)| match state.edible_food() {
+                Some(food) => cycle.distance(*pos, food),
+                None => cycle.distance(head, *pos),
+            });
+
+        match best {
+            Some((direction, _)) => direction,
+            None => GreedyBot.next_move(state, player),
+        }
+    }
+}
+
+// Moves that don't crash this tick, with the cell each one leads to
+fn safe_moves(state: &GameState, player: usize) -> Vec<(Direction, Position)> {
+    let snake = state.snake(player);
+    DIRECTIONS
+        .iter()
+        .filter(|&&direction| direction != snake.direction.opposite())
+        .filter_map(|&direction| step(state, snake.head(), direction).map(|pos| (direction, pos)))
+        .filter(|(_, pos)| !state.is_blocked(pos))
+        .collect()
+}
+
+// Cell a head reaches moving in a direction, portals included (None when it would leave the
+// board)
+fn step(state: &GameState, pos: Position, direction: Direction) -> Option<Position> {
+    let next = pos.step(direction).on_board(state.width(), state.height())?;
+    Some(state.through_portal(next))
+}
+
+// First move of the shortest path from the head to the food, if one exists
+fn path_to_food(state: &GameState, head: Position, moves: &[(Direction, Position)]) -> Option<Direction> {
+    let width = state.width() as usize;
+    let index = |pos: Position| pos.y as usize * width + pos.x as usize;
+    let mut first_move: Vec<Option<Direction>> = vec![None; width * state.height() as usize];
+    let mut queue = VecDeque::new();
+
+    first_move[index(head)] = Some(Direction::Up);  // Marks the head as visited
+    for &(direction, pos) in moves {
+        first_move[index(pos)] = Some(direction);
+        queue.push_back(pos);
+    }
+
+    let food = state.edible_food()?;
+    while let Some(pos) = queue.pop_front() {
+        if pos == food {
+            return first_move[index(pos)];
+        }
+        for direction in DIRECTIONS {
+            let Some(next) = step(state, pos, direction) else { continue };
+            if state.is_blocked(&next) || first_move[index(next)].is_some() {
+                continue;
+            }
+            first_move[index(next)] = first_move[index(pos)];
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+// Number of free cells reachable from a position (flood fill)
+fn open_area(state: &GameState, start: Position) -> usize {
+    let width = state.width() as usize;
+    let index = |pos: Position| pos.y as usize * width + pos.x as usize;
+    let mut seen = vec![false; width * state.height() as usize];
+    let mut stack = vec![start];
+    seen[index(start)] = true;
+    let mut count = 0;
+
+    while let Some(pos) = stack.pop() {
+        count += 1;
+        for direction in DIRECTIONS {
+            let Some(next) = step(state, pos, direction) else { continue };
+            if !state.is_blocked(&next) && !seen[index(next)] {
+                seen[index(next)] = true;
+                stack.push(next);
+            }
+        }
+    }
+    count
+}
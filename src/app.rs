@@ -0,0 +1,569 @@
+// The interactive session: title menu, games, and the screens between them
+// Design Decision: One state machine owns the terminal for the whole session, so players can
+// start game after game from the title screen without relaunching
+use crate::achievements::Achievements;
+use crate::ai::Difficulty;
+use crate::cast::Cast;
+use crate::challenge::Challenge;
+use crate::cli::{Cli, Command};
+use crate::daily::Daily;
+use crate::events::GameEvent;
+use crate::config::GameConfig;
+use crate::config_file::Config;
+use crate::game::{Board, Direction, Game};
+use crate::level::Level;
+#[cfg(feature = "lua")]
+use crate::lua::LuaMod;
+use crate::menu::{self, Menu};
+use crate::mode::Mode;
+#[cfg(feature = "rodio")]
+use crate::music::Music;
+use crate::play::Play;
+use crate::rating::{Outcome, Ratings};
+use crate::replay::Replay;
+use crate::scores::HighScores;
+use crate::skin::Skin;
+use crate::sound::Volume;
+use crate::stats::Stats;
+use crate::stream::StateStream;
+use crate::theme::Theme;
+use crate::{chat, ghost, input, lang, leaderboard, log, paths, play, replay, runs, sound, tutorial};
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{Clear, ClearType},
+};
+use std::cell::RefCell;
+use std::fs;
+use std::io::{stdout, Write};
+use std::rc::Rc;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+// Time each frame of the crash animation stays up
+const DEATH_FRAME: Duration = Duration::from_millis(70);
+
+// Columns the global leaderboard gives a player's name and a board's settings
+const LEADERBOARD_NAME: usize = 16;
+const LEADERBOARD_BOARD: usize = 40;
+
+// Where the session is
+enum Screen {
+    Menu,                 // Title screen
+    Settings,             // Options for the next game
+    HighScores,           // Local high score table
+    Playing(Box<Play>),
+    Paused(Box<Play>),    // Frozen mid-game until the pause key is pressed again
+    Dying(Box<Play>, usize),  // Crash animation, at this frame, before the results
+    GameOver(Box<Play>),  // Finished game, shown with its results
+    Exit,
+}
+
+// Who plays alongside player one
+#[derive(Clone, Copy, PartialEq)]
+enum Opponent {
+    Nobody,
+    Local,     // Second snake on the same keyboard (or the remote player when hosting)
+    Computer,  // AI snake
+}
+
+// What the next game will be, from the command line and the Settings screen
+struct Setup {
+    board: Board,
+    level: Option<Level>,  // Level given with --level, so cycling boards can come back to it
+    opponent: Opponent,
+    difficulty: Difficulty,
+    ghost: bool,           // Race the personal best in solo games
+    seed: Option<u64>,     // Fixed seed from --seed (None = a fresh one per game)
+    settings: GameConfig,
+    daily: Option<String>, // Date of the daily challenge being played (None = ordinary games)
+    rival: Option<u32>,    // Score to beat when playing a challenge code
+    skin: Skin,            // Pattern on player one's body, kept for the next launch
+    volume: Volume,        // Sound effects and music, off with --no-sound
+}
+
+impl Setup {
+    // Labels for the Settings screen, one per option in cycle order
+    fn labels(&self) -> Vec<String> {
+        let text = lang::text();
+        let opponent = match self.opponent {
+            _ if self.settings.mode == Mode::Coop => text.second_player,
+            Opponent::Nobody => text.nobody,
+            Opponent::Local => text.second_player,
+            Opponent::Computer => text.computer,
+        };
+        vec![
+            format!("{}: {}", text.difficulty, self.settings.difficulty.name()),
+            format!("{}: {}", text.mode, self.settings.mode.name()),
+            format!("{}: {}", text.board, self.board.name()),
+            format!("{}: {opponent}", text.opponent),
+            format!("{}: {}", text.computer, self.difficulty.name()),
+            format!("{}: {}", text.ghost, if self.ghost { text.on } else { text.off }),
+            format!("{}: {}", text.skin, self.skin.name()),
+            format!("{}: {}", text.volume, self.volume.label()),
+        ]
+    }
+
+    // Moves one option on to its next value
+    // Design Decision: The campaign is single-player, so choosing it drops the opponent and
+    // opponents are skipped while it's selected; co-op always has the second player, so they're
+    // skipped then too
+    fn cycle(&mut self, option: usize) {
+        // Anything but the ghost, the skin and the volume changes the game, so it's no longer
+        // the daily challenge or the shared run
+        if !matches!(option, 5..=7) && (self.daily.take().is_some() | self.rival.take().is_some()) {
+            self.seed = None;
+        }
+        match option {
+            0 => self.settings.difficulty = self.settings.difficulty.next(),
+            1 => self.settings.mode = self.settings.mode.next(),
+            2 => {
+                self.board = match (&self.board, &self.level) {
+                    (Board::Classic, _) => Board::Maze,
+                    (Board::Maze, _) => Board::Campaign,
+                    (Board::Campaign, Some(level)) => Board::Custom(level.clone()),
+                    _ => Board::Classic,
+                };
+                if matches!(self.board, Board::Campaign) {
+                    self.opponent = Opponent::Nobody;
+                }
+            }
+            3 if !matches!(self.board, Board::Campaign) && self.settings.mode != Mode::Coop => {
+                self.opponent = match self.opponent {
+                    Opponent::Nobody => Opponent::Local,
+                    Opponent::Local => Opponent::Computer,
+                    Opponent::Computer => Opponent::Nobody,
+                };
+            }
+            4 => {
+                self.difficulty = match self.difficulty {
+                    Difficulty::Easy => Difficulty::Medium,
+                    Difficulty::Medium => Difficulty::Hard,
+                    Difficulty::Hard => Difficulty::Easy,
+                };
+            }
+            5 => self.ghost = !self.ghost,
+            // Only skins the player has unlocked come up; a failed save just forgets the choice
+            6 => {
+                self.skin = self.skin.next_unlocked(&Achievements::load(0));
+                let _ = self.skin.save();
+            }
+            7 => self.volume = self.volume.next(),
+            _ => {}
+        }
+    }
+}
+
+// Everything that outlives a single game
+pub(crate) struct Session<'a> {
+    cli: &'a Cli,
+    config: &'a Config,
+    setup: Setup,
+    stats: Rc<RefCell<Stats>>,                  // Lifetime stats, updated as every game ends
+    ratings: Ratings,                           // Versus ratings, updated after every versus match
+    gamepad: Option<Receiver<Direction>>,       // Lent to whichever game is running
+    state_stream: Option<StateStream>,          // Lent to whichever game is running, like the gamepad
+    farewell: Option<String>,                   // Printed once the terminal is restored
+    #[cfg(feature = "rodio")]
+    music: Music,                               // Plays for the whole session, at the setup's volume
+}
+
+impl<'a> Session<'a> {
+    // Starts a session with the next game set up from the command line, opening the state
+    // stream if one was asked for
+    pub(crate) fn new(cli: &'a Cli, config: &'a Config, settings: GameConfig, board: Board) -> std::io::Result<Self> {
+        let opponent = if cli.ai_opponent {
+            Opponent::Computer
+        } else if cli.players > 1 {
+            Opponent::Local
+        } else {
+            Opponent::Nobody
+        };
+        let level = match &board {
+            Board::Custom(level) => Some(level.clone()),
+            _ => None,
+        };
+        let mut setup = Setup {
+            board,
+            level,
+            opponent,
+            difficulty: cli.difficulty,
+            ghost: cli.ghost && config.ghost,
+            seed: cli.seed,
+            settings,
+            daily: None,
+            rival: None,
+            skin: config.theme.skin,
+            volume: if cli.sound { config.volume } else { Volume::OFF },
+        };
+        if matches!(cli.command, Command::Daily) {
+            let daily = Daily::today();
+            (setup.board, setup.seed, setup.settings) = (Board::Classic, Some(daily.seed), daily.settings);
+            setup.daily = Some(daily.date);
+        }
+        if let Command::Challenge(challenge) = &cli.command {
+            (setup.board, setup.seed, setup.settings) = (challenge.board.clone(), Some(challenge.seed), challenge.settings.clone());
+            setup.rival = Some(challenge.score);
+        }
+        let state_stream = cli.emit_state.as_deref().map(StateStream::create).transpose()?;
+        #[cfg(feature = "rodio")]
+        let music = Music::start(setup.volume);
+        Ok(Session {
+            cli,
+            config,
+            setup,
+            stats: Rc::new(RefCell::new(Stats::load())),
+            ratings: Ratings::load(),
+            #[cfg(target_os = "linux")]
+            gamepad: crate::gamepad::connect(),
+            #[cfg(not(target_os = "linux"))]
+            gamepad: None,
+            state_stream,
+            farewell: None,
+            #[cfg(feature = "rodio")]
+            music,
+        })
+    }
+
+    // Builds the next game from the current setup
+    pub(crate) fn new_game(&mut self) -> Play {
+        let setup = &self.setup;
+        let partnered = setup.settings.mode.players().is_some();
+        let players = setup.settings.mode.players().unwrap_or(if setup.opponent == Opponent::Nobody { 1 } else { 2 });
+        let seed = setup.seed.unwrap_or_else(rand::random);
+        let mut game = Play::new(Game::new(setup.board.clone(), players, seed, setup.settings.clone()));
+        // Co-op partners both play from this keyboard, whatever the opponent setting says
+        if setup.opponent == Opponent::Computer && !partnered {
+            game.hand_to_ai(1, setup.difficulty);
+        }
+        if let Some(date) = &setup.daily {
+            game.set_daily(date.clone());
+        }
+        // Daily challenges and challenge codes are played by the same rules for everyone, so they
+        // go unscripted; the script loaded fine at launch, so one that fails now was broken since
+        #[cfg(feature = "lua")]
+        let script = self.config.script.as_deref().filter(|_| setup.daily.is_none() && setup.rival.is_none());
+        #[cfg(feature = "lua")]
+        if let Some(script) = script.and_then(|path| LuaMod::load(path).ok()) {
+            game.add_mod(Box::new(script));
+        }
+        // Solo games race a ghost of the best run on the same board and seed
+        if setup.ghost && players == 1 {
+            if let Some(ghost) = ghost::load(game.board(), game.seed(), game.settings()) {
+                game.set_ghost(ghost);
+            }
+        }
+        self.prepare(&mut game);
+        game
+    }
+
+    // Applies the player's preferences to a game about to be played, new or resumed
+    fn prepare(&mut self, game: &mut Play) {
+        let theme = Theme { skin: self.setup.skin, ..self.config.theme.clone() };
+        game.set_preferences(self.config.keys.clone(), theme);
+        game.set_slow_motion(self.config.slow_motion);
+        if let Some(gamepad) = self.gamepad.take() {
+            game.set_gamepad(gamepad);
+        }
+        if let Some(stream) = self.state_stream.take() {
+            game.set_state_stream(stream);
+        }
+        if self.cli.cast.is_some() {
+            game.set_cast(Cast::new(&format!("snekrs: {}", game.board_settings())));
+        }
+        game.track_achievements(Achievements::load(self.stats.borrow().food()));
+        game.subscribe(Stats::follow(&self.stats));
+        if !self.setup.volume.is_muted() {
+            sound::attach(game);
+        }
+        if log::enabled() {
+            log::record("game", &[("settings", &game.board_settings()), ("tick", &game.ticks())]);
+            // Every tick is already logged on its own
+            game.subscribe(|event| {
+                if !matches!(event, GameEvent::Ticked { .. }) {
+                    log::record("event", &[("event", &format!("{event:?}"))]);
+                }
+            });
+        }
+    }
+
+    // Runs the title menu and every game started from it until the player quits
+    pub(crate) fn run(mut self, resumed: Option<Game>) -> std::io::Result<()> {
+        let game_screen = play::enter_game_screen()?;
+        let mut screen = match resumed {
+            Some(game) => {
+                let mut game = Play::new(game);
+                self.prepare(&mut game);
+                start(game)?
+            }
+            // The daily challenge and shared runs start straight away, skipping the title screen
+            None if self.setup.daily.is_some() || self.setup.rival.is_some() => start(self.new_game())?,
+            None => Screen::Menu,
+        };
+        loop {
+            screen = match screen {
+                Screen::Menu => self.title_screen()?,
+                Screen::Settings => self.settings_screen()?,
+                Screen::HighScores => high_scores_screen(self.config)?,
+                Screen::Playing(game) => self.play(game)?,
+                Screen::Paused(mut game) => {
+                    game.poll_input(Duration::from_millis(50))?;
+                    if game.is_paused() && !game.is_over() {
+                        Screen::Paused(game)
+                    } else {
+                        Screen::Playing(game)
+                    }
+                }
+                Screen::Dying(game, frame) => self.dying(game, frame)?,
+                Screen::GameOver(game) => self.game_over(game)?,
+                Screen::Exit => break,
+            };
+        }
+        game_screen.leave()?;
+        if let Some(farewell) = self.farewell {
+            println!("{farewell}");
+        }
+        Ok(())
+    }
+
+    // The title screen's menu
+    fn title_screen(&mut self) -> std::io::Result<Screen> {
+        execute!(
+            stdout(),
+            Clear(ClearType::All),
+            MoveTo(2, 1),
+            SetAttribute(Attribute::Bold),
+            Print("S N E K R S"),
+            SetAttribute(Attribute::Reset),
+        )?;
+        let text = lang::text();
+        let items = [text.new_game, text.settings, text.high_scores_item, text.tutorial, text.quit];
+        let choice = Menu::new(&items, 2, 3).choose()?;
+        match choice {
+            Some(0) => start(self.new_game()),
+            Some(1) => Ok(Screen::Settings),
+            Some(2) => Ok(Screen::HighScores),
+            Some(3) => {
+                tutorial::run(self.config)?;
+                Ok(Screen::Menu)
+            }
+            _ => Ok(Screen::Exit),
+        }
+    }
+
+    // One step of a running game: input, then a tick when one is due
+    fn play(&mut self, mut game: Box<Play>) -> std::io::Result<Screen> {
+        // Design Decision: 50ms poll rate for responsive controls
+        game.poll_input(Duration::from_millis(50))?;
+        if game.is_paused() && !game.is_over() {
+            return Ok(Screen::Paused(game));
+        }
+        if game.tick_if_due()? || game.animation_due() {
+            game.draw()?;
+        }
+        if !game.is_over() {
+            return Ok(Screen::Playing(game));
+        }
+
+        // Quitting mid-round saves the game for later instead of ending it, except in hardcore
+        // where quitting ends the run like a crash
+        if game.was_quit() && game.settings().mode != Mode::Hardcore {
+            game.replay().save(&replay::suspended_path())?;
+            self.farewell.get_or_insert_with(String::new).push_str(lang::text().game_saved);
+            return Ok(Screen::Exit);
+        }
+        Ok(Screen::Dying(game, 0))
+    }
+
+    // One frame of the crash animation; any key skips straight to the results
+    fn dying(&mut self, mut game: Box<Play>, frame: usize) -> std::io::Result<Screen> {
+        if !game.draw_death(frame)? || input::next(DEATH_FRAME)?.is_some_and(|event| menu::is_dismissal(&event)) {
+            return Ok(Screen::GameOver(game));
+        }
+        Ok(Screen::Dying(game, frame + 1))
+    }
+
+    // Records a finished game and shows its results, offering to go straight into another
+    fn game_over(&mut self, mut game: Box<Play>) -> std::io::Result<Screen> {
+        let notes = self.finish(&game);
+        let row = game.draw_game_over(&notes)?;
+        self.gamepad = game.take_gamepad();
+        self.state_stream = game.take_state_stream();
+        let text = lang::text();
+        execute!(stdout(), MoveTo(2, row + 1), Print(text.restart_prompt))?;
+
+        // Drop keys pressed while playing so the results don't flash by
+        input::discard()?;
+        let items = [text.restart, text.title_screen, text.quit, text.export_board];
+        loop {
+            let choice = Menu::new(&items, 2, row + 3).with_shortcuts(&['r', 't', 'q', 'e']).choose()?;
+            match choice {
+                Some(0) => return start(self.new_game()),
+                Some(2) => return Ok(Screen::Exit),
+                Some(3) => {
+                    let note = self.export_board(&game);
+                    execute!(stdout(), MoveTo(2, row + 4 + items.len() as u16), Print(note), Clear(ClearType::UntilNewLine))?;
+                }
+                _ => return Ok(Screen::Menu),
+            }
+        }
+    }
+
+    // Writes the finished board as text where --export says (or prints it once the terminal
+    // is back), returning a note saying where it went
+    fn export_board(&mut self, game: &Game) -> String {
+        let board = game.board_text();
+        let text = lang::text();
+        let path = self.cli.export.clone().unwrap_or_else(|| paths::data_dir().join("board.txt"));
+        if path.as_os_str() == "-" {
+            let farewell = self.farewell.get_or_insert_with(String::new);
+            farewell.push_str(&board);
+            return text.board_on_exit.to_string();
+        }
+        let written = path.parent().filter(|parent| !parent.as_os_str().is_empty()).map_or(Ok(()), fs::create_dir_all);
+        match written.and_then(|_| fs::write(&path, board)) {
+            Ok(()) => (text.board_exported)(&path.display().to_string()),
+            Err(err) => (text.board_not_exported)(&path.display().to_string(), &err.to_string()),
+        }
+    }
+
+    // Lets the player change the next game's options, one entry per option
+    fn settings_screen(&mut self) -> std::io::Result<Screen> {
+        let mut selected = 0;
+        loop {
+            let mut labels = self.setup.labels();
+            labels.push(lang::text().back.to_string());
+            let items: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+            execute!(stdout(), Clear(ClearType::All), MoveTo(2, 1), Print(lang::text().settings_title))?;
+            match Menu::new(&items, 2, 3).with_selected(selected).choose()? {
+                Some(option) if option < items.len() - 1 => {
+                    self.setup.cycle(option);
+                    #[cfg(feature = "rodio")]
+                    self.music.set_volume(self.setup.volume);
+                    selected = option;
+                }
+                _ => return Ok(Screen::Menu),
+            }
+        }
+    }
+
+    // Records a finished game everywhere it's kept, returning notes for the game-over screen
+    pub(crate) fn finish(&mut self, game: &Play) -> Vec<String> {
+        let text = lang::text();
+        let mut notes = vec![(text.seed_note)(game.seed())];
+
+        if let Err(err) = self.stats.borrow().save() {
+            notes.push((text.stats_not_saved)(&err.to_string()));
+        }
+
+        // Hardcore runs all go in the run history, however they ended
+        if game.settings().mode == Mode::Hardcore {
+            let score = game.state().snake(0).score;
+            match runs::log(game.seed(), game.play_time(), score, &game.board_settings()) {
+                Ok(count) => notes.push((text.hardcore_logged)(count, &runs::history_path().display().to_string())),
+                Err(err) => notes.push((text.run_not_logged)(&err.to_string())),
+            }
+        }
+
+        // Keep the replay of every finished game
+        let replay = game.replay();
+        if let Some(opponent) = self.opponent_profile(game, &replay) {
+            // Leaving a match early concedes it
+            let outcome = match game.winner() {
+                _ if game.was_quit() => Outcome::Loss,
+                Some(0) => Outcome::Win,
+                Some(_) => Outcome::Loss,
+                None => Outcome::Draw,
+            };
+            let player = leaderboard::player_name();
+            let change = self.ratings.record(&player, &opponent, outcome);
+            notes.push((text.rating_note)(self.ratings.rating(&player), change, &opponent));
+            if let Err(err) = self.ratings.save() {
+                notes.push((text.ratings_not_saved)(&err.to_string()));
+            }
+        }
+        if let Some(challenge) = Challenge::from_replay(&replay) {
+            notes.push((text.challenge_note)(&challenge.code()));
+        }
+        if let Some(target) = self.setup.rival {
+            notes.push((text.challenge_result)(replay.score, target));
+        }
+        match ghost::record_if_best(&replay) {
+            Ok(true) => notes.push(text.new_best.to_string()),
+            Ok(false) => {}
+            Err(err) => notes.push((text.best_not_saved)(&err.to_string())),
+        }
+        let path = self.cli.record.clone().unwrap_or_else(replay::default_path);
+        match replay.save(&path) {
+            Ok(()) => notes.push((text.replay_saved)(&path.display().to_string())),
+            Err(err) => notes.push((text.replay_not_saved)(&path.display().to_string(), &err.to_string())),
+        }
+        if let (Some(path), Some(cast)) = (&self.cli.cast, game.cast()) {
+            match cast.save(path) {
+                Ok(()) => notes.push((text.cast_saved)(&path.display().to_string())),
+                Err(err) => notes.push((text.cast_not_saved)(&path.display().to_string(), &err.to_string())),
+            }
+        }
+
+        // Online leaderboard, only for solo games (not practice runs) and only when opted in
+        let ranked = replay.players == 1 && replay.bots.is_empty() && replay.settings.mode != Mode::Practice;
+        if let Some(url) = self.cli.leaderboard.as_ref().filter(|_| ranked) {
+            let name = leaderboard::player_name();
+            match leaderboard::submit(url, &name, replay.score, replay.seed, &game.board_settings()) {
+                Ok(()) => notes.push((text.score_submitted)(&name)),
+                Err(err) => notes.push((text.score_not_submitted)(&err.to_string())),
+            }
+            match leaderboard::fetch(url) {
+                Ok(entries) => {
+                    notes.push(String::new());
+                    notes.push(text.global_leaderboard.to_string());
+                    // Whatever the server sends is cleaned and cut to size, so it can't carry escape
+                    // sequences or push the table off the screen
+                    let fit = |text: &str, width: usize| chat::clean(text).chars().take(width).collect::<String>();
+                    for (index, entry) in entries.iter().enumerate() {
+                        let (name, board) = (fit(&entry.name, LEADERBOARD_NAME), fit(&entry.board, LEADERBOARD_BOARD));
+                        notes.push(format!("{:>2}. {:>4}  {name:<LEADERBOARD_NAME$}  {board}", index + 1, entry.score));
+                    }
+                }
+                Err(err) => notes.push((text.leaderboard_unavailable)(&err.to_string())),
+            }
+        }
+        notes
+    }
+
+    // The rating profile player one just played against, or None when the game wasn't a
+    // versus match between player one and someone else
+    fn opponent_profile(&self, game: &Game, replay: &Replay) -> Option<String> {
+        let mode = game.settings().mode;
+        if replay.players != 2 || matches!(mode, Mode::Coop | Mode::Practice) || replay.bots.iter().any(|(player, _)| *player == 0) {
+            return None;
+        }
+        Some(match replay.bots.first() {
+            Some((_, difficulty)) => format!("AI ({})", difficulty.name()),
+            None if matches!(self.cli.command, Command::Host) => "network guest".to_string(),
+            None => "player 2".to_string(),
+        })
+    }
+}
+
+// Draws a game's first frame under the countdown and hands it to the Playing screen
+fn start(mut game: Play) -> std::io::Result<Screen> {
+    game.start_countdown();
+    game.draw()?;
+    Ok(Screen::Playing(Box::new(game)))
+}
+
+// Shows the local high score table until a key is pressed
+fn high_scores_screen(config: &Config) -> std::io::Result<Screen> {
+    let mut stdout = stdout();
+    execute!(stdout, Clear(ClearType::All))?;
+    let scores = HighScores::load();
+    let row = play::draw_high_scores(&mut stdout, &scores, None, config.theme.accent(), 1)?;
+    let row = play::draw_daily_results(&mut stdout, &scores, row + 1)?;
+    execute!(stdout, MoveTo(2, row + 1), Print(lang::text().back_prompt))?;
+    stdout.flush()?;
+    while !menu::is_dismissal(&input::read()?) {}
+    Ok(Screen::Menu)
+}
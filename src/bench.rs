@@ -0,0 +1,125 @@
+// Benchmark harness: simulates games flat out and reports how fast the engine runs
+// Design Decision: The snake is steered by a fixed greedy script (the safe move closest to the
+// food, no search) instead of one of the bots, so the numbers measure the update loop and
+// frame building rather than the bot's path-finding. Games are seeded 0, 1, 2, ... so two
+// builds benchmarked with the same flags play exactly the same games
+use crate::config::GameConfig;
+use crate::frame::Frame;
+use crate::game::{Board, Direction, Game};
+use crate::play::Play;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+// Ticks simulated when no count is given
+pub(crate) const DEFAULT_TICKS: u64 = 1_000_000;
+
+// Heap allocations and bytes requested since the program started
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+// The system allocator, counting every allocation on the way through
+// Design Decision: Installed for every run, not just benchmarks; two relaxed atomic adds per
+// allocation cost nothing measurable next to the allocation itself
+pub(crate) struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+// Time and allocations spent in one part of each tick
+#[derive(Default)]
+struct Tally {
+    time: Duration,
+    allocations: u64,
+    bytes: u64,
+}
+
+impl Tally {
+    // Runs `work`, adding its time and allocations to the tally
+    fn measure<T>(&mut self, work: impl FnOnce() -> T) -> T {
+        let (allocations, bytes) = (ALLOCATIONS.load(Ordering::Relaxed), ALLOCATED_BYTES.load(Ordering::Relaxed));
+        let start = Instant::now();
+        let result = work();
+        self.time += start.elapsed();
+        self.allocations += ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+        self.bytes += ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes;
+        result
+    }
+
+    // One line of the report
+    fn report(&self, name: &str, ticks: u64) -> String {
+        let per_second = ticks as f64 / self.time.as_secs_f64().max(f64::EPSILON);
+        format!(
+            "  {name:<7}{per_second:>12.0} ticks/s  {:>8.2} allocations  {:>9.1} bytes per tick",
+            self.allocations as f64 / ticks as f64,
+            self.bytes as f64 / ticks as f64
+        )
+    }
+}
+
+// Simulates `ticks` ticks over as many games as it takes and prints the results
+pub(crate) fn run(ticks: u64, board: Board, settings: GameConfig, first_seed: u64) {
+    let (mut update, mut frames) = (Tally::default(), Tally::default());
+    let mut seed = first_seed;
+    let mut games = 0;
+    let mut done = 0;
+    let started = Instant::now();
+    while done < ticks {
+        let mut game = Play::new(Game::new(board.clone(), 1, seed, settings.clone()));
+        let mut previous: Option<Frame> = None;
+        games += 1;
+        seed += 1;
+        while !game.is_over() && done < ticks {
+            update.measure(|| {
+                steer(&mut game);
+                game.tick_quietly();
+            });
+            // What drawing costs short of the terminal: the new frame and the cells it changed
+            previous = Some(frames.measure(|| {
+                let frame = game.frame(false);
+                if let Some(previous) = &previous {
+                    std::hint::black_box(frame.changed_since(previous));
+                }
+                frame
+            }));
+            done += 1;
+        }
+    }
+    println!("Simulated {done} ticks over {games} games in {:.2}s", started.elapsed().as_secs_f64());
+    println!("{}", update.report("update", done));
+    println!("{}", frames.report("frame", done));
+}
+
+// Points the snake at the safe move that gets closest to the food, keeping its heading on ties
+fn steer(game: &mut Game) {
+    let state = game.state();
+    let snake = state.snake(0);
+    let (head, heading) = (snake.head(), snake.direction);
+    let target = state.edible_food().unwrap_or(head);
+    let best = [heading, Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+        .into_iter()
+        .filter(|&direction| direction != heading.opposite())
+        .filter_map(|direction| {
+            let pos = head.step(direction).on_board(state.width(), state.height())?;
+            (!state.is_blocked(&pos)).then_some((direction, pos.x.abs_diff(target.x) + pos.y.abs_diff(target.y)))
+        })
+        .min_by_key(|&(_, distance)| distance);
+    if let Some((direction, _)) = best {
+        game.steer(0, direction);
+    }
+}
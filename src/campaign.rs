@@ -0,0 +1,103 @@
+// Campaign mode: a fixed sequence of stages unlocked by reaching score targets
+// Design Decision: Stage boards reuse the level file format, embedded at compile time
+use crate::game::{HEIGHT, WIDTH};
+use crate::level::Level;
+use crate::maze;
+use rand::Rng;
+use std::time::Duration;
+
+// How a stage's board is produced
+enum Layout {
+    Grid(&'static str),  // Embedded level grid
+    Maze,                // Freshly generated maze every time the stage is entered
+}
+
+// A single campaign stage
+pub struct Stage {
+    pub name: &'static str,
+    pub target_score: Option<u32>,  // Score needed to advance (None = final, endless stage)
+    pub tick_interval: Duration,    // Time between snake moves on this stage
+    layout: Layout,
+}
+
+impl Stage {
+    // Builds the board for this stage
+    pub fn level(&self, rng: &mut impl Rng) -> Level {
+        match self.layout {
+            Layout::Grid(grid) => Level::parse(grid).expect("built-in campaign level is valid"),
+            Layout::Maze => maze::generate(WIDTH, HEIGHT, rng),
+        }
+    }
+}
+
+// Campaign stages in play order
+// Design Decision: Targets are cumulative so the score keeps counting across the whole run
+const STAGES: [Stage; 5] = [
+    Stage {
+        name: "Warm-up",
+        target_score: Some(5),
+        tick_interval: Duration::from_millis(120),
+        layout: Layout::Grid(include_str!("../levels/campaign/01-warmup.txt")),
+    },
+    Stage {
+        name: "Pillars",
+        target_score: Some(15),
+        tick_interval: Duration::from_millis(105),
+        layout: Layout::Grid(include_str!("../levels/campaign/02-pillars.txt")),
+    },
+    Stage {
+        name: "Arena",
+        target_score: Some(30),
+        tick_interval: Duration::from_millis(90),
+        layout: Layout::Grid(include_str!("../levels/campaign/03-arena.txt")),
+    },
+    Stage {
+        name: "Crossroads",
+        target_score: Some(50),
+        tick_interval: Duration::from_millis(80),
+        layout: Layout::Grid(include_str!("../levels/campaign/04-crossroads.txt")),
+    },
+    Stage {
+        name: "The Labyrinth",
+        target_score: None,
+        tick_interval: Duration::from_millis(70),
+        layout: Layout::Maze,
+    },
+];
+
+// Tracks progress through the campaign
+#[derive(Clone, Default)]
+pub struct Campaign {
+    current: usize,
+}
+
+impl Campaign {
+    pub fn new() -> Self {
+        Campaign { current: 0 }
+    }
+
+    // The stage currently being played
+    pub fn stage(&self) -> &Stage {
+        &STAGES[self.current]
+    }
+
+    // 1-based stage number for display
+    pub fn stage_number(&self) -> usize {
+        self.current + 1
+    }
+
+    // Whether the given score clears the current stage
+    pub fn is_cleared(&self, score: u32) -> bool {
+        self.stage().target_score.is_some_and(|target| score >= target)
+    }
+
+    // Moves on to the next stage, returning it (None once the final stage is reached)
+    pub fn advance(&mut self) -> Option<&Stage> {
+        if self.current + 1 < STAGES.len() {
+            self.current += 1;
+            Some(self.stage())
+        } else {
+            None
+        }
+    }
+}
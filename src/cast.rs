@@ -0,0 +1,85 @@
+// Asciinema recordings of games, in the asciicast v2 format
+// Design Decision: A cast is built from the frames the game draws rather than from the bytes it
+// sends to the terminal, so it plays back the same whatever terminal, theme or renderer it was
+// recorded with; each frame only writes the rows that changed, which keeps long runs small
+//
+// File format (https://docs.asciinema.org/manual/asciicast/v2/): a JSON header line, then one
+//   [<seconds since the start>, "o", "<terminal output>"]
+// line per frame
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// A recording in progress
+pub struct Cast {
+    title: String,
+    started: Instant,              // When recording started
+    timestamp: u64,                // Wall-clock start, in seconds since the Unix epoch
+    rows: Vec<String>,             // Screen as last recorded, one string per row
+    width: u16,                    // Widest frame so far, in columns
+    height: usize,                 // Tallest frame so far, in rows
+    events: Vec<(f64, String)>,    // Output per frame, with its time
+}
+
+impl Cast {
+    // An empty recording, titled for asciinema's player
+    pub fn new(title: &str) -> Cast {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        Cast { title: title.to_string(), started: Instant::now(), timestamp, rows: Vec::new(), width: 0, height: 0, events: Vec::new() }
+    }
+
+    // Records a frame: the screen's rows from the top, the widest `width` columns across
+    // (rows may hold SGR escapes, so their length says nothing about their width)
+    pub fn record(&mut self, rows: Vec<String>, width: u16) {
+        let mut output = if self.events.is_empty() { "\u{1b}[?25l\u{1b}[2J".to_string() } else { String::new() };
+        for (index, row) in rows.iter().enumerate() {
+            if self.rows.get(index) != Some(row) {
+                output.push_str(&format!("\u{1b}[{};1H{row}\u{1b}[0m\u{1b}[K", index + 1));
+            }
+        }
+        // Rows a taller frame left behind are blanked
+        for index in rows.len()..self.rows.len() {
+            output.push_str(&format!("\u{1b}[{};1H\u{1b}[K", index + 1));
+        }
+        self.width = self.width.max(width);
+        self.height = self.height.max(rows.len());
+        self.rows = rows;
+        if !output.is_empty() {
+            self.events.push((self.started.elapsed().as_secs_f64(), output));
+        }
+    }
+
+    // Writes the recording as an asciicast v2 file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut text = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}, \"title\": {}}}\n",
+            self.width.max(1),
+            self.height.max(1),
+            self.timestamp,
+            json_string(&self.title)
+        );
+        for (time, output) in &self.events {
+            text.push_str(&format!("[{time:.3}, \"o\", {}]\n", json_string(output)));
+        }
+        fs::write(path, text)
+    }
+}
+
+// A string as a JSON string literal
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            ch if ch.is_control() => quoted.push_str(&format!("\\u{:04x}", u32::from(ch))),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,327 @@
+// Challenge codes: a finished run's board, seed, rules and score in a short string to share
+// Design Decision: The code carries exactly what Game::new needs (plus the score to beat), bit
+// packed and written in Crockford's base32 so it survives being read out or typed by hand:
+// no padding, case-insensitive, I/L read as 1 and O as 0, dashes ignored. A checksum catches
+// typos before they turn into a different game. Level files don't fit, so games on custom
+// boards, with more than one snake or with pace settings past the limits below have no code
+use crate::config::{GameConfig, MAX_SIZE, MIN_SIZE};
+use crate::difficulty::Difficulty;
+use crate::game::Board;
+use crate::mode::Mode;
+use crate::mods::ModKind;
+use crate::replay::Replay;
+use std::time::Duration;
+
+// Format version, bumped if the layout ever changes
+const VERSION: u64 = 2;
+
+// Crockford's base32 alphabet
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+// Symbols in a code: 28 carry the packed fields, the last 2 the checksum
+const SYMBOLS: usize = 30;
+const CHECK_SYMBOLS: usize = 2;
+
+// Symbols per dash-separated group when a code is shown
+const GROUP: usize = 5;
+
+// Field widths in bits, in packing order
+const VERSION_BITS: u32 = 3;
+const SEED_BITS: u32 = 64;
+const BOARD_BITS: u32 = 2;
+const SIZE_BITS: u32 = 9;
+const TICK_BITS: u32 = 11;
+const PRESET_BITS: u32 = 2;
+const MODE_BITS: u32 = 4;
+const MODS_BITS: u32 = 4;
+const SCORE_BITS: u32 = 20;
+
+// Mode field width in version 1 codes, before co-op mode outgrew it; those codes still read
+const V1_MODE_BITS: u32 = 3;
+
+// One shareable run
+#[derive(Clone)]
+pub struct Challenge {
+    pub seed: u64,
+    pub board: Board,          // Classic, maze or campaign
+    pub settings: GameConfig,  // Built-in mods come back in their canonical order
+    pub score: u32,            // What the sharer scored (capped at about a million)
+}
+
+impl Challenge {
+    // The challenge of a solo game on a built-in board, if it fits in a code
+    pub fn from_replay(replay: &Replay) -> Option<Challenge> {
+        let fits = |value: u64, bits: u32| value < 1 << bits;
+        let settings = &replay.settings;
+        let shareable = replay.players == 1
+            && replay.bots.is_empty()
+            && !matches!(replay.board, Board::Custom(_))
+            && fits(settings.width.into(), SIZE_BITS)
+            && fits(settings.height.into(), SIZE_BITS)
+            && fits(settings.tick_interval.as_millis() as u64, TICK_BITS)
+            && fits(settings.min_tick_interval.as_millis() as u64, TICK_BITS)
+            && fits(steps_from(Mode::Standard, settings.mode, Mode::next), MODE_BITS);
+        shareable.then(|| Challenge {
+            seed: replay.seed,
+            board: replay.board.clone(),
+            settings: settings.clone(),
+            score: replay.score.min((1 << SCORE_BITS) - 1),
+        })
+    }
+
+    // The code, in dash-separated groups (e.g. 4ZK1M-...)
+    pub fn code(&self) -> String {
+        let settings = &self.settings;
+        let board = match self.board {
+            Board::Maze => 1,
+            Board::Campaign => 2,
+            Board::Classic | Board::Custom(_) => 0,
+        };
+        let mods = ModKind::ALL
+            .iter()
+            .enumerate()
+            .filter(|(_, kind)| settings.mods.contains(kind))
+            .fold(0, |mask, (bit, _)| mask | 1 << bit);
+        let mut bits = Bits::default();
+        bits.push(VERSION, VERSION_BITS);
+        bits.push(self.seed, SEED_BITS);
+        bits.push(board, BOARD_BITS);
+        bits.push(settings.width.into(), SIZE_BITS);
+        bits.push(settings.height.into(), SIZE_BITS);
+        bits.push(settings.tick_interval.as_millis() as u64, TICK_BITS);
+        bits.push(settings.min_tick_interval.as_millis() as u64, TICK_BITS);
+        bits.push(steps_from(Difficulty::Easy, settings.difficulty, Difficulty::next), PRESET_BITS);
+        bits.push(steps_from(Mode::Standard, settings.mode, Mode::next), MODE_BITS);
+        bits.push(mods, MODS_BITS);
+        bits.push(self.score.into(), SCORE_BITS);
+
+        let mut symbols = bits.symbols(SYMBOLS - CHECK_SYMBOLS);
+        let check = checksum(&symbols);
+        symbols.extend([(check >> 5) as u8, (check & 31) as u8]);
+        let text: Vec<char> = symbols.iter().map(|&symbol| ALPHABET[symbol as usize] as char).collect();
+        text.chunks(GROUP).map(|group| group.iter().collect::<String>()).collect::<Vec<_>>().join("-")
+    }
+
+    // Reads a code back, or None if it was mistyped or isn't a challenge code at all
+    pub fn parse(code: &str) -> Option<Challenge> {
+        let symbols: Vec<u8> = code
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .map(|c| match c.to_ascii_uppercase() {
+                'I' | 'L' => Some(1),
+                'O' => Some(0),
+                c => ALPHABET.iter().position(|&symbol| symbol as char == c).map(|index| index as u8),
+            })
+            .collect::<Option<_>>()?;
+        if symbols.len() != SYMBOLS {
+            return None;
+        }
+        let (payload, check) = symbols.split_at(SYMBOLS - CHECK_SYMBOLS);
+        if checksum(payload) != u32::from(check[0]) << 5 | u32::from(check[1]) {
+            return None;
+        }
+
+        let mut bits = Bits::from_symbols(payload);
+        let mode_bits = match bits.take(VERSION_BITS) {
+            VERSION => MODE_BITS,
+            1 => V1_MODE_BITS,
+            _ => return None,
+        };
+        let seed = bits.take(SEED_BITS);
+        let board = match bits.take(BOARD_BITS) {
+            0 => Board::Classic,
+            1 => Board::Maze,
+            2 => Board::Campaign,
+            _ => return None,
+        };
+        let (width, height) = (bits.take(SIZE_BITS) as u16, bits.take(SIZE_BITS) as u16);
+        let (tick, min_tick) = (bits.take(TICK_BITS), bits.take(TICK_BITS));
+        let difficulty = nth_from(Difficulty::Easy, bits.take(PRESET_BITS), Difficulty::next);
+        let mode_index = bits.take(mode_bits);
+        let mode = nth_from(Mode::Standard, mode_index, Mode::next);
+        let mask = bits.take(MODS_BITS);
+        let score = bits.take(SCORE_BITS) as u32;
+        let sizes = MIN_SIZE..=MAX_SIZE;
+        if !sizes.contains(&width) || !sizes.contains(&height) || tick == 0 || min_tick == 0 {
+            return None;
+        }
+        // An index past the last mode wraps around to another one, so no code holds it
+        if steps_from(Mode::Standard, mode, Mode::next) != mode_index {
+            return None;
+        }
+        let settings = GameConfig {
+            width,
+            height,
+            tick_interval: Duration::from_millis(tick),
+            min_tick_interval: Duration::from_millis(min_tick),
+            difficulty,
+            mode,
+            mods: ModKind::ALL.iter().enumerate().filter(|(bit, _)| mask & 1 << bit != 0).map(|(_, &kind)| kind).collect(),
+        };
+        Some(Challenge { seed, board, settings, score })
+    }
+}
+
+// How many `next` steps lead from `first` to `value`
+fn steps_from<T: Copy + PartialEq>(first: T, value: T, next: fn(T) -> T) -> u64 {
+    let mut steps = 0;
+    let mut current = first;
+    while current != value {
+        current = next(current);
+        steps += 1;
+    }
+    steps
+}
+
+// The value `steps` calls of `next` away from `first`
+fn nth_from<T: Copy>(first: T, steps: u64, next: fn(T) -> T) -> T {
+    (0..steps).fold(first, |value, _| next(value))
+}
+
+// Position-weighted sum of the symbols, so swapped pairs are caught as well as typos
+fn checksum(symbols: &[u8]) -> u32 {
+    symbols.iter().fold(7, |sum, &symbol| (sum * 33 + u32::from(symbol)) % 1021)
+}
+
+// A stream of bits, most significant first within each field
+#[derive(Default)]
+struct Bits {
+    bits: Vec<bool>,
+    read: usize,  // Bits already taken
+}
+
+impl Bits {
+    // The bits of base32 symbols, five each
+    fn from_symbols(symbols: &[u8]) -> Bits {
+        let mut bits = Bits::default();
+        for &symbol in symbols {
+            bits.push(symbol.into(), 5);
+        }
+        bits
+    }
+
+    // Appends the low `width` bits of a value
+    fn push(&mut self, value: u64, width: u32) {
+        self.bits.extend((0..width).rev().map(|bit| value >> bit & 1 == 1));
+    }
+
+    // Reads the next `width` bits as a number
+    fn take(&mut self, width: u32) -> u64 {
+        let field = &self.bits[self.read..self.read + width as usize];
+        self.read += width as usize;
+        field.iter().fold(0, |value, &bit| value << 1 | u64::from(bit))
+    }
+
+    // The stream as `count` five-bit symbols, padded with zero bits
+    fn symbols(mut self, count: usize) -> Vec<u8> {
+        self.bits.resize(count * 5, false);
+        self.bits.chunks(5).map(|chunk| chunk.iter().fold(0, |symbol, &bit| symbol << 1 | u8::from(bit))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A challenge on the default settings, changed by `tweak`
+    fn challenge(tweak: impl FnOnce(&mut Challenge)) -> Challenge {
+        let mut challenge = Challenge { seed: 0x0123_4567_89ab_cdef, board: Board::Classic, settings: GameConfig::default(), score: 42 };
+        tweak(&mut challenge);
+        challenge
+    }
+
+    // Whether two challenges would start the same game with the same score to beat
+    fn same(one: &Challenge, two: &Challenge) -> bool {
+        let (a, b) = (&one.settings, &two.settings);
+        one.seed == two.seed
+            && std::mem::discriminant(&one.board) == std::mem::discriminant(&two.board)
+            && one.score == two.score
+            && (a.width, a.height, a.tick_interval, a.min_tick_interval) == (b.width, b.height, b.tick_interval, b.min_tick_interval)
+            && a.difficulty == b.difficulty
+            && a.mode == b.mode
+            && a.mods == b.mods
+    }
+
+    #[test]
+    fn the_payload_fits_in_the_code() {
+        let used = VERSION_BITS + SEED_BITS + BOARD_BITS + 2 * SIZE_BITS + 2 * TICK_BITS + PRESET_BITS + MODE_BITS + MODS_BITS + SCORE_BITS;
+        assert!(used as usize <= (SYMBOLS - CHECK_SYMBOLS) * 5);
+    }
+
+    #[test]
+    fn a_code_reads_back_as_the_same_challenge() {
+        let original = challenge(|challenge| {
+            challenge.board = Board::Maze;
+            challenge.settings.width = 61;
+            challenge.settings.height = 23;
+            challenge.settings.tick_interval = Duration::from_millis(180);
+            challenge.settings.difficulty = Difficulty::Hard;
+            challenge.settings.mods = vec![ModKind::NoPoison, ModKind::Hunger];
+        });
+        let code = original.code();
+        assert_eq!(code.len(), SYMBOLS + SYMBOLS / GROUP - 1);
+        assert!(same(&original, &Challenge::parse(&code).expect("code should parse")));
+    }
+
+    #[test]
+    fn every_mode_survives_the_round_trip() {
+        let mut mode = Mode::Standard;
+        loop {
+            let original = challenge(|challenge| challenge.settings.mode = mode);
+            let parsed = Challenge::parse(&original.code()).expect("code should parse");
+            assert!(same(&original, &parsed));
+            mode = mode.next();
+            if mode == Mode::Standard {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn codes_read_however_they_are_typed() {
+        let original = challenge(|_| {});
+        let code = original.code();
+        let retyped = code.replace('-', " ").to_lowercase().replace('1', "l").replace('0', "O");
+        assert!(same(&original, &Challenge::parse(&retyped).expect("code should parse")));
+    }
+
+    #[test]
+    fn version_one_codes_still_read() {
+        let mut bits = Bits::default();
+        bits.push(1, VERSION_BITS);
+        bits.push(99, SEED_BITS);
+        bits.push(0, BOARD_BITS);
+        bits.push(40, SIZE_BITS);
+        bits.push(20, SIZE_BITS);
+        bits.push(150, TICK_BITS);
+        bits.push(60, TICK_BITS);
+        bits.push(1, PRESET_BITS);
+        bits.push(3, V1_MODE_BITS);
+        bits.push(0, MODS_BITS);
+        bits.push(7, SCORE_BITS);
+        let mut symbols = bits.symbols(SYMBOLS - CHECK_SYMBOLS);
+        let check = checksum(&symbols);
+        symbols.extend([(check >> 5) as u8, (check & 31) as u8]);
+        let code: String = symbols.iter().map(|&symbol| ALPHABET[symbol as usize] as char).collect();
+
+        let parsed = Challenge::parse(&code).expect("version 1 code should parse");
+        assert_eq!((parsed.seed, parsed.score), (99, 7));
+        assert!(parsed.settings.mode == Mode::Survival);
+    }
+
+    #[test]
+    fn typos_and_garbage_are_rejected() {
+        let code = challenge(|_| {}).code();
+        // Every single-symbol typo changes the checksum
+        for (index, symbol) in code.char_indices().filter(|(_, symbol)| *symbol != '-') {
+            let other = if symbol == 'Z' { 'Y' } else { 'Z' };
+            let typo = format!("{}{other}{}", &code[..index], &code[index + 1..]);
+            assert!(Challenge::parse(&typo).is_none(), "{typo} should be rejected");
+        }
+        assert!(Challenge::parse(&code[..code.len() - 1]).is_none());
+        assert!(Challenge::parse(&format!("{code}0")).is_none());
+        assert!(Challenge::parse("").is_none());
+        assert!(Challenge::parse("hello, world").is_none());
+        assert!(Challenge::parse(&"U".repeat(SYMBOLS)).is_none());
+    }
+}
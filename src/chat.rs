@@ -0,0 +1,111 @@
+// Chat in network games: the latest few lines under the board, and the message being typed
+// Design Decision: Both ends keep the same small struct and draw it the same way, so the host
+// (whose keys go through Game) and a joining player (whose keys go through the client loop)
+// see one chat; what goes over the wire is up to the network code
+use crate::lang;
+use crate::render::Layout;
+use crossterm::{
+    cursor::MoveTo,
+    event::KeyCode,
+    queue,
+    style::Print,
+    terminal::{self, Clear, ClearType},
+};
+use std::collections::VecDeque;
+use std::io::Write;
+
+// Longest message, in characters, so a line fits under most boards
+pub const MAX_MESSAGE: usize = 80;
+
+// Chat lines kept on screen
+const SHOWN_LINES: usize = 3;
+
+// Terminal rows the chat takes under the board: its lines plus the typing line
+pub const CHAT_ROWS: u16 = SHOWN_LINES as u16 + 1;
+
+// The chat as one end sees it
+pub struct Chat {
+    lines: VecDeque<String>,  // Newest last, at most SHOWN_LINES
+    draft: Option<String>,    // Message being typed (None = not typing)
+    key: Option<String>,      // Label of the key that starts a message (None = watching, can't type)
+}
+
+// A message as it may be shown: one line, no control characters, at most MAX_MESSAGE long
+// Design Decision: Everything shown or passed on goes through here, so nobody can send
+// terminal escape sequences to the other screens
+pub fn clean(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).take(MAX_MESSAGE).collect::<String>().trim().to_string()
+}
+
+impl Chat {
+    // An empty chat, started with the given key
+    pub fn new(key: String) -> Self {
+        Chat { lines: VecDeque::new(), draft: None, key: Some(key) }
+    }
+
+    // Stops offering to type, for spectators
+    pub fn set_read_only(&mut self) {
+        self.key = None;
+        self.draft = None;
+    }
+
+    // Adds a line of the form "<who>: <message>", dropping the oldest once the chat is full
+    pub fn add(&mut self, who: &str, message: &str) {
+        if self.lines.len() == SHOWN_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(format!("{}: {}", clean(who), clean(message)));
+    }
+
+    // Whether a message is being typed, so keys go to it instead of the game
+    pub fn is_typing(&self) -> bool {
+        self.draft.is_some()
+    }
+
+    // Starts typing a message, unless only watching
+    pub fn start_typing(&mut self) {
+        if self.key.is_some() {
+            self.draft = Some(String::new());
+        }
+    }
+
+    // Applies a key while typing; Enter returns the message to send, Esc drops it
+    pub fn type_key(&mut self, code: KeyCode) -> Option<String> {
+        let draft = self.draft.as_mut()?;
+        match code {
+            KeyCode::Enter => {
+                let message = clean(&self.draft.take()?);
+                return (!message.is_empty()).then_some(message);
+            }
+            KeyCode::Esc => self.draft = None,
+            KeyCode::Backspace => {
+                draft.pop();
+            }
+            KeyCode::Char(c) if !c.is_control() && draft.chars().count() < MAX_MESSAGE => draft.push(c),
+            _ => {}
+        }
+        None
+    }
+
+    // Queues the chat lines and the typing line from `row` under the board down, leaving out
+    // any that would fall off the bottom of the terminal
+    pub fn draw(&self, out: &mut impl Write, layout: Layout, row: u16) -> std::io::Result<()> {
+        let bottom = terminal::size().map_or(u16::MAX, |(_, rows)| rows);
+        let padding = SHOWN_LINES - self.lines.len();
+        let prompt = match (&self.draft, &self.key) {
+            (Some(draft), _) => (lang::text().chat_draft)(draft),
+            (None, Some(key)) => (lang::text().chat_key)(key),
+            (None, None) => String::new(),
+        };
+        let rows = std::iter::repeat_n(String::new(), padding).chain(self.lines.iter().cloned()).chain([prompt]);
+        for (offset, text) in rows.enumerate() {
+            let position = layout.at(0, row + offset as u16);
+            let MoveTo(_, y) = position;
+            if y >= bottom {
+                break;
+            }
+            queue!(out, position, Print(text), Clear(ClearType::UntilNewLine))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,410 @@
+// Command-line parsing
+// Design Decision: clap derives the parser and --help from the structs below, which are then
+// folded into one Cli struct, so the rest of the binary never deals with how flags are spelled
+use crate::ai::Difficulty;
+use crate::bench;
+use crate::challenge::Challenge;
+use crate::config::{self, GameConfig};
+use crate::difficulty;
+use crate::keymap::Controls;
+use crate::mode::Mode;
+use crate::net;
+use crate::render::Renderer;
+use crate::theme::Scheme;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const UDP_HELP: &str = "Play over UDP: your own turns show instantly and the host corrects them, so play stays \
+smooth at 100ms+ round trips (both ends need it; no spectators or reconnecting)";
+
+// The command line as clap reads it
+// Design Decision: Options are global, so they can come before or after the subcommand as
+// they always could; only the ones that make sense for a single subcommand belong to it
+#[derive(Parser)]
+#[command(name = "snekrs", version, about = "A fun and quirky terminal-based snake game")]
+struct Arguments {
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+    #[command(flatten)]
+    board: BoardOptions,
+    #[command(flatten)]
+    players: PlayerOptions,
+    #[command(flatten)]
+    game: GameOptions,
+}
+
+#[derive(Subcommand)]
+enum Subcommands {
+    #[command(about = "Host a network game: the first to join plays, anyone after them watches")]
+    Host {
+        #[arg(long, default_value_t = net::DEFAULT_PORT, help = "Port to host on")]
+        port: u16,
+        #[arg(long, help = UDP_HELP)]
+        udp: bool,
+    },
+    #[command(about = "Join a network game")]
+    Join {
+        #[arg(help = "The host's address, e.g. 192.168.1.5 (port 7878 unless given)")]
+        address: String,
+        #[arg(long, help = UDP_HELP)]
+        udp: bool,
+    },
+    #[command(about = "Pick a game to join from those hosted on the local network")]
+    Lobby,
+    #[command(about = "Watch a replay")]
+    Replay {
+        #[arg(help = "Replay file (default: the last game)")]
+        file: Option<PathBuf>,
+        #[arg(long, help = "Turn the replay into an animated GIF in the theme's colors instead of playing it, \
+timed by --speed (needs a build with `--features gif`)")]
+        gif: Option<PathBuf>,
+    },
+    #[command(about = "Play today's daily challenge")]
+    Daily,
+    #[command(about = "Play the run a challenge code describes")]
+    Challenge {
+        #[arg(value_parser = challenge_code, help = "A code as shown after a solo game")]
+        code: Challenge,
+    },
+    #[command(about = "Print lifetime stats, ratings and achievements")]
+    Stats,
+    #[command(about = "Edit a level file, creating it if need be")]
+    Edit {
+        #[arg(help = "The level file")]
+        file: PathBuf,
+    },
+    #[command(about = "Walk through the basics on practice boards")]
+    Tutorial,
+    #[command(about = "Play in a window of its own, cells drawn as squares (needs a build with `--features window`)")]
+    Window,
+    #[command(about = "Measure how fast the engine simulates; board and game options pick what it plays")]
+    Bench {
+        #[arg(long, default_value_t = bench::DEFAULT_TICKS, value_parser = clap::value_parser!(u64).range(1..), help = "Ticks to simulate")]
+        ticks: u64,
+    },
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Board")]
+struct BoardOptions {
+    #[arg(long, global = true, value_name = "FILE", help = "Play on a board loaded from a level file")]
+    level: Option<PathBuf>,
+    #[arg(long, global = true, value_name = "FILE", help = "Play on a board drawn as ASCII art: '#' (or any mark) is a wall, \
+'.' or a blank is floor and 'S' the spawn (with `edit`, start the level from it)")]
+    art: Option<PathBuf>,
+    #[arg(long, global = true, help = "Play on a freshly generated maze")]
+    maze: bool,
+    #[arg(long, global = true, help = "Play through the built-in stages")]
+    campaign: bool,
+    #[arg(long, global = true, value_name = "N", value_parser = board_size, help = "Width of classic and maze boards (10-500, default: fill the terminal)")]
+    width: Option<u16>,
+    #[arg(long, global = true, value_name = "N", value_parser = board_size, help = "Height of classic and maze boards (10-500, default: fill the terminal)")]
+    height: Option<u16>,
+    #[arg(long, global = true, help = "Reproduce the board and food of an earlier run (the first game's, for `bench`)")]
+    seed: Option<u64>,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Players")]
+struct PlayerOptions {
+    #[arg(long, global = true, help = "Add a second snake steered with WASD")]
+    two_player: bool,
+    #[arg(long, global = true, help = "Add a computer-controlled opponent")]
+    ai: bool,
+    #[arg(long, global = true, value_name = "LEVEL", default_value = "medium", value_parser = one_of(Difficulty::parse, "easy, medium, hard"),
+          help = "How well the computer plays: easy, medium or hard")]
+    bot: Difficulty,
+    #[arg(long, global = true, help = "Let the computer play on its own until a key is pressed")]
+    demo: bool,
+    #[arg(long, global = true, help = "No terminal at all: the computer plays every snake (or a replay's inputs do) \
+as fast as it can, then the final score is printed")]
+    headless: bool,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Game")]
+struct GameOptions {
+    #[arg(long, global = true, value_name = "NAME", value_parser = one_of(difficulty::Difficulty::parse, "easy, normal, hard, insane"),
+          help = "easy, normal (default), hard or insane: pace, speed-up and points")]
+    difficulty: Option<difficulty::Difficulty>,
+    #[arg(long, global = true, value_name = "NAME",
+          value_parser = one_of(Mode::parse, "standard, fleeing, time-attack, survival, zen, hardcore, royale, practice, coop"),
+          help = "standard (default), fleeing (the food runs from you), time-attack (score all you can in 2 minutes), \
+survival (the walls close in every 30 seconds), zen (no crashes, the border wraps around), \
+hardcore (top speed, no saving, every run logged), royale (a damaging zone closes in; best with two snakes), \
+practice (press u to rewind a couple of seconds, crashes too) or coop (two snakes on one keyboard share a score)")]
+    mode: Option<Mode>,
+    #[arg(long, global = true, value_name = "SCHEME", value_parser = one_of(Controls::parse, "arrows, wasd, vim, all"),
+          help = "Extra movement keys: arrows (default), wasd, vim (hjkl) or all")]
+    controls: Option<Controls>,
+    #[arg(long, global = true, value_name = "X", default_value_t = 1.0, value_parser = speed, allow_negative_numbers = true,
+          help = "Pace multiplier (also the playback speed for `replay`)")]
+    speed: f64,
+    #[arg(long, global = true, help = "Don't race the ghost of your personal best")]
+    no_ghost: bool,
+    #[arg(long, global = true, help = "No sound effects (the terminal bell on eating, power-ups and crashes)")]
+    no_sound: bool,
+    #[arg(long, global = true, value_name = "NAME", value_parser = one_of(Scheme::parse, "classic, neon, solarized, mono, high-contrast"),
+          help = "classic (default), neon, solarized, mono (no color, ASCII only) or high-contrast \
+(bold, nothing blinks, two columns per cell)")]
+    theme: Option<Scheme>,
+    #[arg(long, global = true, help = "Emoji sneks and food, two columns per cell")]
+    emoji: bool,
+    #[arg(long, global = true, value_name = "NAME", value_parser = one_of(Renderer::parse, "text, half-block, braille"),
+          help = "text (default), half-block (two square cells per character) or braille \
+(experimental: 2x4 cells per character, for huge boards)")]
+    renderer: Option<Renderer>,
+    #[arg(long, global = true, help = "Plain glyphs only (also set by NO_COLOR or TERM=dumb)")]
+    no_color: bool,
+    #[arg(long, global = true, value_name = "FILE", help = "Where to save this game's replay")]
+    record: Option<PathBuf>,
+    #[arg(long, global = true, value_name = "FILE", help = "Record every frame as an asciinema cast (asciicast v2), to upload to \
+asciinema.org or play with `asciinema play` (replays too)")]
+    cast: Option<PathBuf>,
+    #[arg(long, global = true, value_name = "FILE", help = "Where 'Export board' on the results screen writes the final board as text \
+(default: board.txt in the data folder; - prints it on exit)")]
+    export: Option<PathBuf>,
+    #[arg(long, global = true, value_name = "FILE", help = "Write the board, snakes, food and score as one JSON line per tick \
+to a file or named pipe, for outside tools")]
+    emit_state: Option<PathBuf>,
+    #[arg(long, global = true, value_name = "URL", help = "Submit solo scores to an online leaderboard (http:// only; also set by SNEKRS_LEADERBOARD)")]
+    leaderboard: Option<String>,
+    #[arg(long, global = true, value_name = "FILE", help = "Log every key, tick and game event with timestamps, for tracking down \
+dropped keys or stutters (also set by SNEKRS_LOG)")]
+    log: Option<PathBuf>,
+}
+
+// What to do on launch
+pub(crate) enum Command {
+    Play,              // A local game
+    Host,              // Host a network game
+    Join(String),      // Join a network game at this address
+    Lobby,             // Pick a network game to join from those hosted on the local network
+    Replay(Option<PathBuf>),  // Watch a replay (None = the last game)
+    Daily,             // Play today's daily challenge
+    Challenge(Challenge),  // Play the run a challenge code describes
+    Stats,             // Print lifetime statistics
+    Edit(PathBuf),     // Edit a level file, creating it if need be
+    Tutorial,          // Walk through the basics on practice boards
+    Window,            // A local game in a window instead of the terminal
+    Demo,              // Attract mode
+    Bench(u64),        // Simulate this many ticks and report the engine's speed
+}
+
+// Everything given on the command line
+pub(crate) struct Cli {
+    pub(crate) command: Command,
+    pub(crate) level: Option<PathBuf>,     // Level file to play on
+    pub(crate) art: Option<PathBuf>,       // ASCII art to build the board from
+    pub(crate) maze: bool,
+    pub(crate) campaign: bool,
+    pub(crate) players: usize,             // Snakes in the game, including remote and AI ones
+    pub(crate) ai_opponent: bool,
+    pub(crate) difficulty: Difficulty,
+    pub(crate) port: u16,
+    pub(crate) udp: bool,                  // Network games go over UDP with prediction instead of TCP
+    pub(crate) record: Option<PathBuf>,
+    pub(crate) cast: Option<PathBuf>,      // Where to save an asciinema recording of the game
+    pub(crate) gif: Option<PathBuf>,       // Where to write a replay as an animated GIF
+    pub(crate) export: Option<PathBuf>,    // Where exported boards go ("-" = stdout on exit)
+    pub(crate) speed: f64,
+    pub(crate) preset: Option<difficulty::Difficulty>,  // Difficulty preset (--bot sets the computer's)
+    pub(crate) mode: Option<Mode>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) ghost: bool,
+    pub(crate) sound: bool,
+    pub(crate) color: bool,                // False forces the no-color fallback
+    pub(crate) theme: Option<Scheme>,      // Replaces the configured theme
+    pub(crate) emoji: bool,                // Emoji glyphs on top of the theme
+    pub(crate) renderer: Option<Renderer>,
+    pub(crate) leaderboard: Option<String>,
+    pub(crate) width: Option<u16>,
+    pub(crate) height: Option<u16>,
+    pub(crate) controls: Option<Controls>, // Alias keys on top of the configured ones
+    pub(crate) headless: bool,             // Simulate without drawing, printing only the score
+    pub(crate) emit_state: Option<PathBuf>,  // Where to stream a JSON snapshot after every tick
+    pub(crate) log: Option<PathBuf>,       // Debug log of input, ticks and game events
+    pub(crate) bare: bool,                 // Launched with no arguments at all
+}
+
+impl Cli {
+    // Parses the process arguments, exiting with a usage error on bad input
+    pub(crate) fn parse() -> Cli {
+        let bare = std::env::args_os().len() == 1;
+        let Arguments { command, board, players, game } = Arguments::parse();
+        let (mut port, mut udp, mut gif) = (net::DEFAULT_PORT, false, None);
+        let command = match command {
+            _ if players.demo => Command::Demo,
+            None => Command::Play,
+            Some(Subcommands::Host { port: host_port, udp: over_udp }) => {
+                (port, udp) = (host_port, over_udp);
+                Command::Host
+            }
+            Some(Subcommands::Join { address, udp: over_udp }) => {
+                udp = over_udp;
+                Command::Join(address)
+            }
+            Some(Subcommands::Lobby) => Command::Lobby,
+            Some(Subcommands::Replay { file, gif: gif_path }) => {
+                gif = gif_path;
+                Command::Replay(file)
+            }
+            Some(Subcommands::Daily) => Command::Daily,
+            Some(Subcommands::Challenge { code }) => Command::Challenge(code),
+            Some(Subcommands::Stats) => Command::Stats,
+            Some(Subcommands::Edit { file }) => Command::Edit(file),
+            Some(Subcommands::Tutorial) => Command::Tutorial,
+            Some(Subcommands::Window) => Command::Window,
+            Some(Subcommands::Bench { ticks }) => Command::Bench(ticks),
+        };
+        let mut cli = Cli {
+            command,
+            level: board.level,
+            art: board.art,
+            maze: board.maze,
+            campaign: board.campaign,
+            players: if players.two_player { 2 } else { 1 },
+            ai_opponent: players.ai,
+            difficulty: players.bot,
+            port,
+            udp,
+            record: game.record,
+            cast: game.cast,
+            gif,
+            export: game.export,
+            speed: game.speed,
+            preset: game.difficulty,
+            mode: game.mode,
+            seed: board.seed,
+            ghost: !game.no_ghost,
+            sound: !game.no_sound,
+            color: !game.no_color,
+            theme: game.theme,
+            emoji: game.emoji,
+            renderer: game.renderer,
+            leaderboard: game.leaderboard.or_else(|| std::env::var("SNEKRS_LEADERBOARD").ok().filter(|url| !url.is_empty())),
+            width: board.width,
+            height: board.height,
+            controls: game.controls,
+            headless: players.headless,
+            emit_state: game.emit_state,
+            log: game.log.or_else(|| std::env::var_os("SNEKRS_LOG").filter(|path| !path.is_empty()).map(PathBuf::from)),
+            bare,
+        };
+        cli.validate();
+        cli
+    }
+
+    // Rejects flag combinations that can't work together and fills in implied players
+    fn validate(&mut self) {
+        // Network games always have a remote second player
+        let hosting = matches!(self.command, Command::Host);
+        if self.ai_opponent && (hosting || self.players > 1) {
+            usage_error("--ai takes the second snake, so it can't be combined with another player");
+        }
+        if hosting || self.ai_opponent {
+            self.players = 2;
+        }
+        let results_screen = matches!(self.command, Command::Play | Command::Daily | Command::Challenge(_)) && !self.headless;
+        if self.export.is_some() && !results_screen {
+            usage_error("--export only works for local games, whose results screen offers the export");
+        }
+        let drawn = matches!(self.command, Command::Play | Command::Host | Command::Daily | Command::Challenge(_) | Command::Replay(_));
+        if self.cast.is_some() && (self.headless || self.gif.is_some() || !drawn) {
+            usage_error("--cast records what a game draws, so it only works for games and replays on screen");
+        }
+        if self.gif.is_some() && (self.headless || !matches!(self.command, Command::Replay(_))) {
+            usage_error("--gif turns a replay into a GIF, so it can't be combined with --headless or --demo");
+        }
+        if self.gif.is_some() && !cfg!(feature = "gif") {
+            usage_error("this snekrs was built without GIF support; rebuild it with `cargo install snekrs --features gif`");
+        }
+        if matches!(self.command, Command::Window) && !cfg!(feature = "window") {
+            usage_error("this snekrs was built without the window frontend; rebuild it with `cargo install snekrs --features window`");
+        }
+        if self.level.is_some() && self.art.is_some() {
+            usage_error("--level and --art both pick the board, so only one can be given");
+        }
+        if self.campaign && self.players > 1 {
+            usage_error("--campaign is single-player only");
+        }
+        if self.headless && !matches!(self.command, Command::Play | Command::Replay(_)) {
+            usage_error("--headless only works for local games and replays");
+        }
+        // Everyone plays the same daily challenge or shared run, so nothing that shapes the game can change
+        let reshaped = self.seed.is_some()
+            || self.level.is_some()
+            || self.art.is_some()
+            || self.maze
+            || self.campaign
+            || self.players > 1
+            || self.preset.is_some()
+            || self.mode.is_some()
+            || self.width.is_some()
+            || self.height.is_some()
+            || self.speed != 1.0;
+        let fixed = match self.command {
+            Command::Daily => Some("daily"),
+            Command::Challenge(_) => Some("challenge"),
+            Command::Tutorial => Some("tutorial"),
+            _ => None,
+        };
+        if let Some(command) = fixed.filter(|_| reshaped) {
+            usage_error(&format!("{command} picks its own board, seed, mods and pace, so it only takes display and input options"));
+        }
+        let streamable = match self.command {
+            Command::Play | Command::Host | Command::Daily | Command::Challenge(_) => true,
+            Command::Replay(_) => self.headless,
+            _ => false,
+        };
+        if self.emit_state.is_some() && !streamable {
+            usage_error("--emit-state only works for local and hosted games, and headless replays");
+        }
+    }
+
+    // Whether this run draws to the terminal at all (headless games, benchmarks, GIF exports
+    // and windowed games don't)
+    pub(crate) fn uses_terminal(&self) -> bool {
+        !self.headless && !matches!(self.command, Command::Bench(_) | Command::Window) && self.gif.is_none()
+    }
+
+    // The config file's game settings with the command-line overrides applied
+    pub(crate) fn game_config(&self, defaults: GameConfig) -> GameConfig {
+        GameConfig {
+            width: self.width.unwrap_or(defaults.width),
+            height: self.height.unwrap_or(defaults.height),
+            tick_interval: defaults.tick_interval.div_f64(self.speed).max(Duration::from_millis(1)),
+            difficulty: self.preset.unwrap_or(defaults.difficulty),
+            mode: self.mode.unwrap_or(defaults.mode),
+            ..defaults
+        }
+    }
+}
+
+// Prints an argument error the way clap prints its own, and exits with a usage status code
+fn usage_error(message: &str) -> ! {
+    Arguments::command().error(ErrorKind::ArgumentConflict, message).exit()
+}
+
+// A value parser for one of the game's named options, listing the choices when it's none
+// of them
+fn one_of<T: 'static>(parse: fn(&str) -> Option<T>, choices: &'static str) -> impl Fn(&str) -> Result<T, String> + Clone + Send + Sync + 'static {
+    move |value| parse(value).ok_or(format!("must be one of: {choices}"))
+}
+
+// Parses and range-checks a board dimension
+fn board_size(value: &str) -> Result<u16, String> {
+    config::board_size(value.parse().map_err(|_| "must be a whole number".to_string())?)
+}
+
+// Parses a pace multiplier
+fn speed(value: &str) -> Result<f64, String> {
+    value.parse().ok().filter(|speed: &f64| *speed > 0.0 && speed.is_finite()).ok_or("must be a positive number".to_string())
+}
+
+// Parses a challenge code
+fn challenge_code(code: &str) -> Result<Challenge, String> {
+    Challenge::parse(code).ok_or("that isn't a valid challenge code (check it for typos)".to_string())
+}
@@ -0,0 +1,46 @@
+// Settings that shape the simulation: board size, pace, difficulty, mode and mods
+// Design Decision: Kept apart from the player's config file (keys, colors, sound), which the
+// binary reads, so replays, challenge codes and embedding crates only deal with the rules
+use crate::difficulty::Difficulty;
+use crate::game::{HEIGHT, MIN_TICK_INTERVAL, TICK_INTERVAL, WIDTH};
+use crate::mode::Mode;
+use crate::mods::ModKind;
+use std::time::Duration;
+
+// Board limits accepted from the config, matching what level files allow
+pub const MIN_SIZE: u16 = 10;
+pub const MAX_SIZE: u16 = 500;
+
+// Settings that shape the simulation, so replays record them
+#[derive(Clone)]
+pub struct GameConfig {
+    pub width: u16,               // Board width including border (classic and maze boards)
+    pub height: u16,              // Board height including border
+    pub tick_interval: Duration,  // Time between snake moves, before the difficulty's pace
+    pub min_tick_interval: Duration,  // Fastest the pace gets as the score rises
+    pub difficulty: Difficulty,
+    pub mode: Mode,
+    pub mods: Vec<ModKind>,  // Built-in mods stacked on the rules, applied in order
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            width: WIDTH,
+            height: HEIGHT,
+            tick_interval: TICK_INTERVAL,
+            min_tick_interval: MIN_TICK_INTERVAL,
+            difficulty: Difficulty::Normal,
+            mode: Mode::Standard,
+            mods: Vec::new(),
+        }
+    }
+}
+
+// Validates a board dimension
+pub fn board_size(value: i64) -> Result<u16, String> {
+    u16::try_from(value)
+        .ok()
+        .filter(|size| (MIN_SIZE..=MAX_SIZE).contains(size))
+        .ok_or(format!("board size must be between {MIN_SIZE} and {MAX_SIZE}"))
+}
@@ -0,0 +1,342 @@
+// User configuration loaded from config.toml
+// Design Decision: Read with the toml crate into one struct per section, so anything valid
+// TOML (multi-line arrays, 'literal' strings, escapes) works as users expect
+//
+// Example ~/.config/snekrs/config.toml:
+//   [board]
+//   width = 60
+//   height = 24
+//   fit = false
+//
+//   [game]
+//   tick_ms = 80
+//   min_tick_ms = 50
+//   difficulty = "hard"
+//   mode = "fleeing"
+//   ghost = false
+//   volume = 50
+//   slow_motion = false
+//   language = "de"
+//   mods = ["no-poison", "hunger"]
+//   script = "rules.lua"
+//
+//   [keys]
+//   controls = "vim"
+//   steering = "relative"
+//   up = ["k", "Up"]
+//   p2_left = "j"
+//   quit = "Esc"
+//   faster = "]"
+//
+//   [theme]
+//   name = "solarized"
+//   head = "yellow"
+//   snake = "dark_yellow"
+//   food = "red"
+//   walls_bg = "dark_grey"
+//   emoji = true
+//   bold = true
+//   steady = true
+//   renderer = "half-block"
+use crate::config::{board_size, GameConfig, MAX_SIZE, MIN_SIZE};
+use crate::difficulty::Difficulty;
+use crate::keymap::{self, Action, Controls, Keymap, Steering};
+use crate::lang::Language;
+use crate::mode::Mode;
+#[cfg(feature = "lua")]
+use crate::lua::LuaMod;
+use crate::mods::ModKind;
+use crate::paths;
+use crate::render::Renderer;
+use crate::sound::Volume;
+use crate::theme::{Glyphs, Scheme, Theme};
+use crossterm::style::Color;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Everything read from the config file
+#[derive(Clone)]
+pub struct Config {
+    pub game: GameConfig,
+    pub keys: Keymap,
+    pub theme: Theme,
+    pub ghost: bool,  // Race the personal best ghost in solo games
+    pub volume: Volume,  // Sound effects and music (sound = false is the same as volume = 0)
+    pub slow_motion: bool,  // Slow down for a moment after a close call at speed
+    pub fit: bool,    // Size the board to the terminal unless a size is given
+    pub language: Option<Language>,  // None = the locale's language (LANG and friends)
+    #[cfg(feature = "lua")]
+    pub script: Option<PathBuf>,  // Lua mod stacked on every new game
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { game: GameConfig::default(), keys: Keymap::default(), theme: Theme::default(), ghost: true, volume: Volume::FULL, slow_motion: true, fit: true, language: None, #[cfg(feature = "lua")] script: None }
+    }
+}
+
+// The file as written: one table per section, every setting optional
+// Design Decision: Unknown sections and keys are rejected rather than ignored, so a typo in a
+// setting's name is reported instead of silently doing nothing
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct File {
+    board: BoardSection,
+    game: GameSection,
+    keys: KeysSection,
+    theme: ThemeSection,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct BoardSection {
+    width: Option<i64>,
+    height: Option<i64>,
+    fit: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct GameSection {
+    tick_ms: Option<i64>,
+    min_tick_ms: Option<i64>,
+    difficulty: Option<String>,
+    mode: Option<String>,
+    ghost: Option<bool>,
+    sound: Option<bool>,
+    volume: Option<i64>,
+    slow_motion: Option<bool>,
+    language: Option<String>,
+    mods: Option<Vec<String>>,
+    script: Option<String>,
+}
+
+// Key bindings: the two scheme settings, then any number of `action = keys` lines
+// Design Decision: Bindings are collected as a map and checked against the actions, since
+// serde can't deny unknown fields next to a flattened map
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct KeysSection {
+    controls: Option<String>,
+    steering: Option<String>,
+    #[serde(flatten)]
+    bindings: BTreeMap<String, Keys>,
+}
+
+// One key or a list of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeSection {
+    name: Option<String>,
+    color: Option<bool>,
+    renderer: Option<String>,
+    bold: Option<bool>,
+    steady: Option<bool>,
+    emoji: Option<bool>,
+    wide: Option<bool>,
+    head: Option<String>,
+    head2: Option<String>,
+    snake: Option<String>,
+    snake2: Option<String>,
+    food: Option<String>,
+    golden: Option<String>,
+    poison: Option<String>,
+    powerup: Option<String>,
+    walls: Option<String>,
+    walls_bg: Option<String>,
+    ghost: Option<String>,
+    portal: Option<String>,
+    zone: Option<String>,
+}
+
+impl Config {
+    // Loads the user's config file, falling back to defaults when there is none
+    pub fn load() -> std::io::Result<Config> {
+        let path = config_path();
+        match fs::read_to_string(&path) {
+            Ok(text) => Config::parse(&text).map_err(|err| invalid(&format!("{}: {err}", path.display()))),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Applies every setting in the text on top of the defaults, rejecting unknown keys and
+    // out-of-range values
+    fn parse(text: &str) -> Result<Config, String> {
+        let file: File = toml::from_str(text).map_err(|err| err.to_string())?;
+        let mut config = Config::default();
+        config.apply_board(file.board)?;
+        config.apply_game(file.game).map_err(|err| format!("[game] {err}"))?;
+        config.apply_keys(file.keys).map_err(|err| format!("[keys] {err}"))?;
+        config.apply_theme(file.theme).map_err(|err| format!("[theme] {err}"))?;
+        Ok(config)
+    }
+
+    fn apply_board(&mut self, board: BoardSection) -> Result<(), String> {
+        if let Some(width) = board.width {
+            self.game.width = board_size(width).map_err(|err| format!("[board] width: {err}"))?;
+            self.fit = false;
+        }
+        if let Some(height) = board.height {
+            self.game.height = board_size(height).map_err(|err| format!("[board] height: {err}"))?;
+            self.fit = false;
+        }
+        if let Some(fit) = board.fit {
+            self.fit = fit;
+        }
+        Ok(())
+    }
+
+    fn apply_game(&mut self, game: GameSection) -> Result<(), String> {
+        let pace = |millis: i64, key: &str| match millis {
+            10..=2000 => Ok(Duration::from_millis(millis as u64)),
+            _ => Err(format!("{key} must be between 10 and 2000")),
+        };
+        if let Some(millis) = game.tick_ms {
+            self.game.tick_interval = pace(millis, "tick_ms")?;
+        }
+        if let Some(millis) = game.min_tick_ms {
+            self.game.min_tick_interval = pace(millis, "min_tick_ms")?;
+        }
+        if let Some(name) = game.difficulty {
+            self.game.difficulty = Difficulty::parse(&name).ok_or(format!("unknown difficulty {name:?}"))?;
+        }
+        if let Some(name) = game.mode {
+            self.game.mode = Mode::parse(&name).ok_or(format!("unknown mode {name:?}"))?;
+        }
+        if let Some(name) = game.language {
+            self.language = Some(Language::parse(&name).ok_or(format!("unknown language {name:?}"))?);
+        }
+        if let Some(names) = game.mods {
+            self.game.mods = names
+                .iter()
+                .map(|name| ModKind::parse(name).ok_or(format!("unknown mod {name:?}")))
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(volume) = game.volume {
+            self.volume = Volume::percent(volume).ok_or("volume must be between 0 and 100")?;
+        }
+        if game.sound == Some(false) {
+            self.volume = Volume::OFF;
+        }
+        // Design Decision: The script is run once here so a broken one is reported at launch,
+        // then loaded afresh for every game so each starts with the script's own state reset
+        #[cfg(feature = "lua")]
+        if let Some(path) = game.script {
+            let path = paths::config_dir().join(path);
+            LuaMod::load(&path).map_err(|err| format!("script: {err}"))?;
+            self.script = Some(path);
+        }
+        #[cfg(not(feature = "lua"))]
+        if game.script.is_some() {
+            return Err("script needs a build with `--features lua`".to_string());
+        }
+        self.ghost = game.ghost.unwrap_or(self.ghost);
+        self.slow_motion = game.slow_motion.unwrap_or(self.slow_motion);
+        Ok(())
+    }
+
+    // The schemes go first, so bindings for single actions win over them
+    fn apply_keys(&mut self, keys: KeysSection) -> Result<(), String> {
+        if let Some(name) = keys.controls {
+            self.keys.add_aliases(Controls::parse(&name).ok_or(format!("unknown controls {name:?}"))?);
+        }
+        if let Some(name) = keys.steering {
+            self.keys.set_steering(Steering::parse(&name).ok_or(format!("unknown steering {name:?}"))?);
+        }
+        for (action, keys) in keys.bindings {
+            match keys {
+                Keys::One(key) => self.bind_keys(&action, &[key])?,
+                Keys::Many(keys) => self.bind_keys(&action, &keys)?,
+            }
+        }
+        Ok(())
+    }
+
+    // A named theme replaces the whole theme, so it goes first and everything else overrides it
+    fn apply_theme(&mut self, theme: ThemeSection) -> Result<(), String> {
+        if let Some(name) = theme.name {
+            self.theme = Scheme::parse(&name).ok_or(format!("unknown theme {name:?}"))?.theme();
+        }
+        if let Some(emoji) = theme.emoji {
+            self.theme.glyphs = if emoji { Glyphs::emoji() } else { Glyphs::default() };
+        }
+        if let Some(name) = theme.renderer {
+            self.theme.renderer = Renderer::parse(&name).ok_or(format!("unknown renderer {name:?}"))?;
+        }
+        self.theme.color = theme.color.unwrap_or(self.theme.color);
+        self.theme.bold = theme.bold.unwrap_or(self.theme.bold);
+        self.theme.steady = theme.steady.unwrap_or(self.theme.steady);
+        self.theme.glyphs.wide = theme.wide.unwrap_or(self.theme.glyphs.wide);
+
+        let color = |name: Option<String>| -> Result<Option<Color>, String> {
+            name.map(|name| Color::try_from(name.as_str()).map_err(|_| format!("unknown color {name:?}"))).transpose()
+        };
+        let [head, head2] = &mut self.theme.heads;
+        let [snake, snake2] = &mut self.theme.snakes;
+        let elements = [
+            (theme.head, head),
+            (theme.head2, head2),
+            (theme.snake, snake),
+            (theme.snake2, snake2),
+            (theme.golden, &mut self.theme.golden),
+            (theme.poison, &mut self.theme.poison),
+            (theme.powerup, &mut self.theme.powerup),
+            (theme.ghost, &mut self.theme.ghost),
+            (theme.portal, &mut self.theme.portal),
+            (theme.zone, &mut self.theme.zone),
+        ];
+        for (name, slot) in elements {
+            if let Some(color) = color(name)? {
+                *slot = color;
+            }
+        }
+        // These three can also be left uncolored by a theme, which a color here overrides
+        for (name, slot) in [(theme.food, &mut self.theme.food), (theme.walls, &mut self.theme.walls), (theme.walls_bg, &mut self.theme.walls_bg)] {
+            if let Some(color) = color(name)? {
+                *slot = Some(color);
+            }
+        }
+        Ok(())
+    }
+
+    // Rebinds an action to the named keys
+    fn bind_keys(&mut self, action: &str, names: &[String]) -> Result<(), String> {
+        let action = Action::parse(action).ok_or(format!("unknown action {action:?}"))?;
+        let keys = names
+            .iter()
+            .map(|name| keymap::parse_key(name).ok_or(format!("unknown key {name:?}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.keys.bind(action, &keys);
+        Ok(())
+    }
+}
+
+// Where the config file lives
+pub fn config_path() -> PathBuf {
+    paths::config_dir().join("config.toml")
+}
+
+// Board size that fills the terminal, leaving room for the status and controls lines and
+// `extra_rows` more (None when the terminal size can't be read, e.g. output isn't a terminal)
+pub fn fitted_board(theme: &Theme, extra_rows: u16) -> Option<(u16, u16)> {
+    let (columns, rows) = crossterm::terminal::size().ok().filter(|&(columns, rows)| columns > 0 && rows > 0)?;
+    let (width, height) = theme.renderer.board_size(columns, rows.saturating_sub(2 + extra_rows), theme);
+    Some((width.clamp(MIN_SIZE, MAX_SIZE), height.clamp(MIN_SIZE, MAX_SIZE)))
+}
+
+// Builds an InvalidData error for a malformed config file
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
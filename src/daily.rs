@@ -0,0 +1,55 @@
+// The daily challenge: one board, food sequence and set of mods per calendar day
+// Design Decision: Everything is derived from the UTC date alone, so every player gets the
+// same game without any server; the board size and pace are the built-in defaults rather
+// than the player's config, since either would change where the food lands
+use crate::config::GameConfig;
+use crate::mods::ModKind;
+use crate::scores;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Mixed into the day number so daily seeds don't line up with small hand-picked ones
+const SALT: u64 = 0x736e_656b_7273_6461;
+
+// Most mods stacked on one day's challenge
+const MAX_MODS: usize = 2;
+
+// One day's challenge
+pub struct Daily {
+    pub date: String,  // YYYY-MM-DD (UTC), shown with the results
+    pub seed: u64,
+    pub settings: GameConfig,
+}
+
+impl Daily {
+    // Today's challenge (UTC)
+    pub fn today() -> Daily {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        Daily::for_day(seconds / 86_400)
+    }
+
+    // The challenge for a day, counted from 1970-01-01
+    pub fn for_day(day: u64) -> Daily {
+        let seed = mix(day ^ SALT);
+        // The mods come from their own stream so they don't shift the board's random choices
+        let mut rng = StdRng::seed_from_u64(mix(seed));
+        let mut mods = ModKind::ALL.to_vec();
+        mods.shuffle(&mut rng);
+        mods.truncate(rng.gen_range(0..=MAX_MODS));
+        Daily {
+            date: scores::date(day as i64),
+            seed,
+            settings: GameConfig { mods, ..GameConfig::default() },
+        }
+    }
+}
+
+// SplitMix64's finalizer, spreading consecutive days across the whole seed range
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
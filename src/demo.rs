@@ -0,0 +1,48 @@
+// Attract / demo mode: the computer plays endless rounds on its own
+// Design Decision: Reuses the AI opponent's steering so the binary doubles as a screensaver
+use crate::ai::Difficulty;
+use crate::config::GameConfig;
+use crate::config_file::Config;
+use crate::game::{Board, Game};
+use crate::play::Play;
+use crate::{input, menu, play};
+use std::time::{Duration, Instant};
+
+// Pause between rounds so the final position stays visible for a moment
+const ROUND_BREAK: Duration = Duration::from_millis(1500);
+
+// Runs demo rounds until any key is pressed or the screen is clicked
+pub(crate) fn run(difficulty: Difficulty, settings: GameConfig, config: &Config) -> std::io::Result<()> {
+    let game_screen = play::enter_game_screen()?;
+
+    'rounds: loop {
+        let mut game = Play::new(Game::new(Board::Classic, 1, rand::random(), settings.clone()));
+        game.hand_to_ai(0, difficulty);
+        game.set_preferences(config.keys.clone(), config.theme.clone());
+        game.draw()?;
+
+        while !game.is_over() {
+            if key_pressed(Duration::from_millis(50))? {
+                break 'rounds;
+            }
+            if game.tick_if_due()? || game.animation_due() {
+                game.draw()?;
+            }
+        }
+
+        let round_ended = Instant::now();
+        while round_ended.elapsed() < ROUND_BREAK {
+            if key_pressed(Duration::from_millis(50))? {
+                break 'rounds;
+            }
+        }
+    }
+
+    game_screen.leave()?;
+    Ok(())
+}
+
+// Waits up to `timeout` for a key press or click
+fn key_pressed(timeout: Duration) -> std::io::Result<bool> {
+    Ok(input::next(timeout)?.is_some_and(|event| menu::is_dismissal(&event)))
+}
@@ -0,0 +1,80 @@
+// Difficulty presets for the player's own game
+// Design Decision: Pace, speed ramp and food value move together under one name, so a
+// harder game is faster, speeds up sooner and pays out more for the extra risk
+use std::time::Duration;
+
+// How demanding a game is
+#[derive(Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,    // Slower, barely speeds up
+    Normal,  // The configured pace, speeding up gently
+    Hard,    // Faster, speeds up sooner, double points
+    Insane,  // Much faster, speeds up sharply, triple points
+}
+
+impl Difficulty {
+    // Parses a CLI or config name (easy, normal, hard, insane)
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        match name {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            "insane" => Some(Difficulty::Insane),
+            _ => None,
+        }
+    }
+
+    // CLI name, also used in replay files
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Insane => "insane",
+        }
+    }
+
+    // The next preset, wrapping around, for the Settings screen
+    pub fn next(self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Insane,
+            Difficulty::Insane => Difficulty::Easy,
+        }
+    }
+
+    // Starting tick interval for a board normally played at `base`
+    pub fn tick_interval(self, base: Duration) -> Duration {
+        let pace = match self {
+            Difficulty::Easy => 1.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.7,
+            Difficulty::Insane => 0.45,
+        };
+        base.mul_f64(pace).max(Duration::from_millis(1))
+    }
+
+    // Tick interval after `eaten` more food has been eaten at `interval`, never going below `floor`
+    // Design Decision: Each food shaves a fixed fraction off, so the pace tightens steadily the
+    // longer a run survives; an interval already under the floor (e.g. from --speed) is left
+    // alone rather than slowed down
+    pub fn speed_up(self, interval: Duration, eaten: u32, floor: Duration) -> Duration {
+        let ramp: f64 = match self {
+            Difficulty::Easy => 0.005,
+            Difficulty::Normal => 0.01,
+            Difficulty::Hard => 0.02,
+            Difficulty::Insane => 0.03,
+        };
+        interval.mul_f64((1.0 - ramp).powi(eaten as i32)).max(floor.min(interval))
+    }
+
+    // Points scored for each food
+    pub fn food_value(self) -> u32 {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => 1,
+            Difficulty::Hard => 2,
+            Difficulty::Insane => 3,
+        }
+    }
+}
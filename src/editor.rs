@@ -0,0 +1,319 @@
+// Level editor: `snekrs edit <file>` shows a level file's grid to change with the keys or the
+// mouse, saves it back in the same format, and can drop straight into a game on it
+// Design Decision: The editor works on the file's own characters rather than on a Level, so a
+// half-finished board (a portal still missing its other end, say) can be kept on screen; the
+// grid only has to parse as a Level when it's saved or played, and Level::parse says what's wrong
+use crate::config::GameConfig;
+use crate::config_file::Config;
+use crate::game::{Board, Game, Position};
+use crate::level::Level;
+use crate::play::Play;
+use crate::{input, play};
+use crossterm::{
+    cursor::MoveTo,
+    event::{Event, KeyCode, KeyModifiers, MouseButton, MouseEventKind},
+    execute, queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{Clear, ClearType},
+};
+use std::fs;
+use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Screen position of the board's top-left corner
+const LEFT: u16 = 2;
+const TOP: u16 = 2;
+
+// What Space or a click puts down
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    Wall,
+    Portal,
+    Spawn,
+    Food,
+    Erase,
+}
+
+impl Tool {
+    const ALL: [Tool; 5] = [Tool::Wall, Tool::Portal, Tool::Spawn, Tool::Food, Tool::Erase];
+
+    // Tool picked by a key, if any
+    fn parse(key: char) -> Option<Tool> {
+        Tool::ALL.into_iter().find(|tool| tool.key() == key)
+    }
+
+    // Label in the tool bar, with its key in brackets
+    fn name(self) -> &'static str {
+        match self {
+            Tool::Wall => "[w]all",
+            Tool::Portal => "[p]ortal",
+            Tool::Spawn => "[s]pawn",
+            Tool::Food => "[f]ood spot",
+            Tool::Erase => "e[x]ase",
+        }
+    }
+
+    // Key that picks the tool
+    fn key(self) -> char {
+        match self {
+            Tool::Wall => 'w',
+            Tool::Portal => 'p',
+            Tool::Spawn => 's',
+            Tool::Food => 'f',
+            Tool::Erase => 'x',
+        }
+    }
+
+    // The next tool along the bar, for Tab
+    fn next(self) -> Tool {
+        match self {
+            Tool::Wall => Tool::Portal,
+            Tool::Portal => Tool::Spawn,
+            Tool::Spawn => Tool::Food,
+            Tool::Food => Tool::Erase,
+            Tool::Erase => Tool::Wall,
+        }
+    }
+}
+
+// A level being edited
+struct Editor {
+    path: PathBuf,
+    width: u16,
+    height: u16,
+    cells: Vec<char>,   // Level file characters, row by row, border included
+    cursor: Position,   // Cell Space places on, always inside the border
+    tool: Tool,
+    status: String,     // Last thing that happened, shown under the help
+    changed: bool,      // Edited since the last save
+    leaving: bool,      // Asked to quit with unsaved changes, so asking again quits
+}
+
+impl Editor {
+    // Opens a level file, or starts a new level from ASCII art or as an empty board of the
+    // given size
+    fn open(path: &Path, art: Option<&Path>, width: u16, height: u16) -> std::io::Result<Editor> {
+        let (grid, status) = if let Some(art) = art {
+            (Level::load_art(art)?.to_grid(), format!("Imported {}: Ctrl+S saves it as a level to {}", art.display(), path.display()))
+        } else if path.exists() {
+            (Level::load(path)?.to_grid(), format!("Editing {}", path.display()))
+        } else {
+            let empty = Level::parse(&blank_grid(width, height))?;
+            (empty.to_grid(), format!("New {width}x{height} level: Ctrl+S saves it to {}", path.display()))
+        };
+        let rows: Vec<&str> = grid.lines().collect();
+        let (width, height) = (rows[0].chars().count() as u16, rows.len() as u16);
+        Ok(Editor {
+            path: path.to_path_buf(),
+            width,
+            height,
+            cells: rows.iter().flat_map(|row| row.chars()).collect(),
+            cursor: Position { x: width / 2, y: height / 2 },
+            tool: Tool::Wall,
+            status,
+            changed: false,
+            leaving: false,
+        })
+    }
+
+    // The grid in the level file format
+    fn text(&self) -> String {
+        self.cells.chunks(usize::from(self.width)).map(|row| row.iter().collect::<String>() + "\n").collect()
+    }
+
+    // The character at a cell
+    fn cell(&self, x: u16, y: u16) -> char {
+        self.cells[usize::from(y) * usize::from(self.width) + usize::from(x)]
+    }
+
+    // Changes a cell, noting the edit
+    fn set(&mut self, x: u16, y: u16, tile: char) {
+        let index = usize::from(y) * usize::from(self.width) + usize::from(x);
+        if self.cells[index] != tile {
+            self.cells[index] = tile;
+            self.changed = true;
+        }
+    }
+
+    // Moves the cursor, keeping it off the border
+    fn move_cursor(&mut self, dx: i32, dy: i32) {
+        self.cursor.x = (i32::from(self.cursor.x) + dx).clamp(1, i32::from(self.width) - 2) as u16;
+        self.cursor.y = (i32::from(self.cursor.y) + dy).clamp(1, i32::from(self.height) - 2) as u16;
+    }
+
+    // Puts the tool down under the cursor
+    // Design Decision: There's one spawn, so placing it moves it; portals go down in pairs, each
+    // new end joining a pair that's still missing one before a new pair is started
+    fn place(&mut self, tool: Tool) {
+        let Position { x, y } = self.cursor;
+        let tile = match tool {
+            Tool::Wall => '#',
+            Tool::Food => '*',
+            Tool::Erase => ' ',
+            Tool::Spawn => {
+                if let Some(old) = self.cells.iter().position(|&tile| tile == 'S') {
+                    self.cells[old] = ' ';
+                }
+                'S'
+            }
+            Tool::Portal if self.cell(x, y).is_ascii_digit() => return,
+            Tool::Portal => {
+                let ends = |digit: char| self.cells.iter().filter(|&&tile| tile == digit).count();
+                let digits = '1'..='9';
+                let Some(digit) = digits.clone().find(|&digit| ends(digit) == 1).or_else(|| digits.clone().find(|&digit| ends(digit) == 0)) else {
+                    self.status = "All nine portal pairs are placed; erase one to move it".to_string();
+                    return;
+                };
+                digit
+            }
+        };
+        self.set(x, y, tile);
+        self.leaving = false;
+    }
+
+    // Writes the grid back to its file, once it parses as a level
+    fn save(&mut self) {
+        let text = self.text();
+        self.status = match Level::parse(&text).and_then(|_| fs::write(&self.path, text)) {
+            Ok(()) => {
+                self.changed = false;
+                format!("Saved to {}", self.path.display())
+            }
+            Err(err) => format!("Not saved: {err}"),
+        };
+    }
+
+    // Plays a game on the board as it stands, coming back to the editor when it ends
+    fn test_play(&mut self, settings: &GameConfig, config: &Config) -> std::io::Result<()> {
+        let level = match Level::parse(&self.text()) {
+            Ok(level) => level,
+            Err(err) => {
+                self.status = format!("Can't play this level yet: {err}");
+                return Ok(());
+            }
+        };
+        let mut game = Play::new(Game::new(Board::Custom(level), 1, rand::random(), settings.clone()));
+        game.set_preferences(config.keys.clone(), config.theme.clone());
+        game.set_hint(Some("Test play: quit to go back to the editor".to_string()));
+        game.start_countdown();
+        game.draw()?;
+        while !game.is_over() {
+            game.poll_input(Duration::from_millis(50))?;
+            if game.tick_if_due()? || game.animation_due() {
+                game.draw()?;
+            }
+        }
+        self.status = if game.was_quit() { "Back from test play".to_string() } else { game.final_message() };
+        input::discard()?;
+        execute!(stdout(), Clear(ClearType::All))
+    }
+
+    // Draws the board with the cursor on it, the tool bar, the keys and the status line
+    fn draw(&self) -> std::io::Result<()> {
+        let mut out = stdout();
+        let unsaved = if self.changed { " (unsaved)" } else { "" };
+        queue!(out, MoveTo(LEFT, 0), Print(format!("Level editor: {}{unsaved}", self.path.display())), Clear(ClearType::UntilNewLine))?;
+        for y in 0..self.height {
+            queue!(out, MoveTo(LEFT, TOP + y))?;
+            for x in 0..self.width {
+                let tile = self.cell(x, y);
+                if self.cursor == (Position { x, y }) {
+                    queue!(out, SetAttribute(Attribute::Reverse), Print(tile), SetAttribute(Attribute::Reset))?;
+                } else {
+                    queue!(out, Print(tile))?;
+                }
+            }
+        }
+        let below = TOP + self.height + 1;
+        queue!(out, MoveTo(LEFT, below), Print("Tool:"))?;
+        for tool in Tool::ALL {
+            let marker = if tool == self.tool { Attribute::Reverse } else { Attribute::Reset };
+            queue!(out, Print(" "), SetAttribute(marker), Print(tool.name()), SetAttribute(Attribute::Reset))?;
+        }
+        queue!(
+            out,
+            Clear(ClearType::UntilNewLine),
+            MoveTo(LEFT, below + 1),
+            Print("Arrows move, Space places, Tab changes tool, click or drag to paint (right button erases)"),
+            Clear(ClearType::UntilNewLine),
+            MoveTo(LEFT, below + 2),
+            Print("Ctrl+S saves, t test-plays, q quits"),
+            Clear(ClearType::UntilNewLine),
+            MoveTo(LEFT, below + 4),
+            Print(&self.status),
+            Clear(ClearType::UntilNewLine),
+        )?;
+        out.flush()
+    }
+}
+
+// An empty board of the given size: floor inside a wall border
+fn blank_grid(width: u16, height: u16) -> String {
+    (0..height)
+        .map(|y| {
+            let row: String = (0..width)
+                .map(|x| if x == 0 || y == 0 || x == width - 1 || y == height - 1 { '#' } else { ' ' })
+                .collect();
+            row + "\n"
+        })
+        .collect()
+}
+
+// Edits a level file until the player quits, starting from ASCII art when some is given, or
+// from a board the size of the game settings' when the file doesn't exist yet
+pub(crate) fn run(path: &Path, art: Option<&Path>, settings: GameConfig, config: &Config) -> std::io::Result<()> {
+    let mut editor = Editor::open(path, art, settings.width, settings.height)?;
+    let game_screen = play::enter_game_screen()?;
+    execute!(stdout(), Clear(ClearType::All))?;
+    loop {
+        editor.draw()?;
+        match input::read()? {
+            Event::Key(key) if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') => editor.save(),
+            Event::Key(key) => match key.code {
+                KeyCode::Up => editor.move_cursor(0, -1),
+                KeyCode::Down => editor.move_cursor(0, 1),
+                KeyCode::Left => editor.move_cursor(-1, 0),
+                KeyCode::Right => editor.move_cursor(1, 0),
+                KeyCode::Char(' ') | KeyCode::Enter => editor.place(editor.tool),
+                KeyCode::Tab => editor.tool = editor.tool.next(),
+                KeyCode::Char('t') => editor.test_play(&settings, config)?,
+                KeyCode::Char('q') | KeyCode::Esc if editor.changed && !editor.leaving => {
+                    editor.status = "Unsaved changes: Ctrl+S saves them, q again quits without saving".to_string();
+                    editor.leaving = true;
+                }
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char(c) => {
+                    if let Some(tool) = Tool::parse(c.to_ascii_lowercase()) {
+                        editor.tool = tool;
+                    }
+                }
+                _ => {}
+            },
+            // Clicking inside the border moves the cursor there and paints with the tool
+            Event::Mouse(mouse) => {
+                let (Some(x), Some(y)) = (mouse.column.checked_sub(LEFT), mouse.row.checked_sub(TOP)) else {
+                    continue;
+                };
+                if !(1..editor.width - 1).contains(&x) || !(1..editor.height - 1).contains(&y) {
+                    continue;
+                }
+                let (tool, dragged) = match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => (editor.tool, false),
+                    MouseEventKind::Drag(MouseButton::Left) => (editor.tool, true),
+                    MouseEventKind::Down(MouseButton::Right) => (Tool::Erase, false),
+                    MouseEventKind::Drag(MouseButton::Right) => (Tool::Erase, true),
+                    _ => continue,
+                };
+                editor.cursor = Position { x, y };
+                // Dragging paints walls, food spots and floor; portals and the spawn go down a click at a time
+                if !dragged || matches!(tool, Tool::Wall | Tool::Food | Tool::Erase) {
+                    editor.place(tool);
+                }
+            }
+            Event::Resize(..) => execute!(stdout(), Clear(ClearType::All))?,
+            _ => {}
+        }
+    }
+    game_screen.leave()
+}
@@ -0,0 +1,105 @@
+// Timed effects from collected power-ups
+// Design Decision: Effects count down in game time (the tick interval of every tick run)
+// rather than wall-clock time, so pauses don't eat into them and replays expire them on
+// exactly the same tick
+use crate::powerup::PowerUp;
+use std::time::Duration;
+
+// How long an effect lasts once collected (None = applied on the spot by GameState)
+fn duration(power: PowerUp) -> Option<Duration> {
+    match power {
+        PowerUp::Boost => Some(Duration::from_secs(5)),
+        PowerUp::Slow => Some(Duration::from_secs(5)),
+        PowerUp::Shrink => None,
+        PowerUp::Phase => Some(Duration::from_secs(5)),
+        PowerUp::Double | PowerUp::Triple => Some(Duration::from_secs(8)),
+        PowerUp::Magnet => Some(Duration::from_secs(6)),
+    }
+}
+
+// One running effect
+#[derive(Clone)]
+struct Effect {
+    player: usize,       // Who collected it
+    power: PowerUp,
+    remaining: Duration, // Game time left
+}
+
+// Every effect currently running
+#[derive(Clone, Default)]
+pub struct Effects {
+    active: Vec<Effect>,
+}
+
+impl Effects {
+    // Starts an effect for a player
+    // Design Decision: Collecting one that's already running refreshes it instead of stacking
+    pub fn add(&mut self, player: usize, power: PowerUp) {
+        let Some(remaining) = duration(power) else {
+            return;
+        };
+        self.active.retain(|effect| !(effect.player == player && effect.power == power));
+        self.active.push(Effect { player, power, remaining });
+    }
+
+    // Counts every effect down by one tick of game time, dropping the ones that ran out
+    pub fn tick(&mut self, elapsed: Duration) {
+        for effect in &mut self.active {
+            effect.remaining = effect.remaining.saturating_sub(elapsed);
+        }
+        self.active.retain(|effect| !effect.remaining.is_zero());
+    }
+
+    // Whether a power-up's effect is running for a player
+    pub fn is_active(&self, player: usize, power: PowerUp) -> bool {
+        self.active.iter().any(|effect| effect.player == player && effect.power == power)
+    }
+
+    // Whether a power-up's effect is running for anyone
+    fn any(&self, power: PowerUp) -> bool {
+        self.active.iter().any(|effect| effect.power == power)
+    }
+
+    // Time between moves with every pace effect applied to the base interval
+    // Design Decision: Each pace effect counts once however many players run it, so a boost
+    // and a slow-mo together cancel out rather than compounding
+    pub fn tick_interval(&self, base: Duration) -> Duration {
+        let mut interval = base;
+        if self.any(PowerUp::Boost) {
+            interval /= 2;
+        }
+        if self.any(PowerUp::Slow) {
+            interval *= 2;
+        }
+        interval
+    }
+
+    // Factor applied to a player's food points
+    // Design Decision: The best running multiplier wins rather than multiplying together, so
+    // an x2 picked up during an x3 never turns into x6
+    pub fn multiplier(&self, player: usize) -> u32 {
+        let mut multiplier = 1;
+        if self.is_active(player, PowerUp::Double) {
+            multiplier = 2;
+        }
+        if self.is_active(player, PowerUp::Triple) {
+            multiplier = 3;
+        }
+        multiplier
+    }
+
+    // HUD text listing running effects with their time left, e.g. "Boost 4s"
+    pub fn describe(&self) -> String {
+        self.active
+            .iter()
+            .map(|effect| {
+                let seconds = effect.remaining.as_nanos().div_ceil(1_000_000_000);
+                match effect.player {
+                    0 => format!("{} {seconds}s", effect.power.name()),
+                    player => format!("P{} {} {seconds}s", player + 1, effect.power.name()),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
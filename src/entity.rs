@@ -0,0 +1,153 @@
+// Board entities: everything on the board besides the snakes, built from small components
+// Design Decision: Food, power-ups and obstacles share one list in GameState, each entity
+// carrying only the components it needs (a position and a role always, a lifetime and a
+// flee counter when it has them), so more food at once, moving pickups or new kinds of item
+// are new entities rather than new fields with their own special cases
+use crate::food::FoodKind;
+use crate::game::{Direction, Position};
+use crate::powerup::{PowerUp, PICKUP_LIFETIME};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Ticks between a fleeing entity's own moves
+const FLEE_INTERVAL: u32 = 3;
+
+// Entities start blinking once they have this little time left
+const BLINK_WINDOW: Duration = Duration::from_secs(2);
+
+// Length of each on or off phase of the blink
+const BLINK_PHASE: u128 = 250;
+
+// Entities count as freshly spawned for this much of their lifetime
+const FRESH_WINDOW: Duration = Duration::from_millis(600);
+
+// Drawn in place of freshly spawned food every other animation frame
+pub const SPARKLE_GLYPH: char = '✧';
+
+// What touching an entity does
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Role {
+    Food(FoodKind),    // Eaten by the snake that reaches it
+    PowerUp(PowerUp),  // Collected by the snake that reaches it
+    Obstacle,          // Kills whatever runs into it
+}
+
+// Something on the board
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Entity {
+    pub pos: Position,               // Cell it occupies
+    pub role: Role,                  // What touching it does
+    pub lifetime: Option<Duration>,  // Game time until it expires (None = it stays for good)
+    pub flee: Option<u32>,           // Ticks since it last ran from the snakes (None = it doesn't)
+}
+
+impl Entity {
+    // Food of a kind, with the kind's full lifetime
+    pub fn food(pos: Position, kind: FoodKind) -> Self {
+        Entity { pos, role: Role::Food(kind), lifetime: Some(kind.lifetime()), flee: None }
+    }
+
+    // A power-up waiting to be collected
+    pub fn pickup(pos: Position, power: PowerUp) -> Self {
+        Entity { pos, role: Role::PowerUp(power), lifetime: Some(PICKUP_LIFETIME), flee: None }
+    }
+
+    // A static lethal tile
+    pub fn obstacle(pos: Position) -> Self {
+        Entity { pos, role: Role::Obstacle, lifetime: None, flee: None }
+    }
+
+    // The kind of food this is, if it's food
+    pub fn food_kind(&self) -> Option<FoodKind> {
+        match self.role {
+            Role::Food(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    // The power-up this is, if it's one
+    pub fn power(&self) -> Option<PowerUp> {
+        match self.role {
+            Role::PowerUp(power) => Some(power),
+            _ => None,
+        }
+    }
+
+    // Counts down one tick of game time, returning whether the entity has now expired
+    pub fn age(&mut self, elapsed: Duration) -> bool {
+        let Some(lifetime) = &mut self.lifetime else {
+            return false;
+        };
+        *lifetime = lifetime.saturating_sub(elapsed);
+        lifetime.is_zero()
+    }
+
+    // Whether the entity is drawn this frame (it blinks as expiry approaches)
+    pub fn is_shown(&self) -> bool {
+        self.lifetime.is_none_or(|remaining| remaining > BLINK_WINDOW || (remaining.as_millis() / BLINK_PHASE) % 2 == 1)
+    }
+
+    // Whether the entity appeared only a moment ago (never for ones that stay for good)
+    pub fn is_fresh(&self) -> bool {
+        let full = match self.role {
+            Role::Food(kind) => kind.lifetime(),
+            Role::PowerUp(_) => PICKUP_LIFETIME,
+            Role::Obstacle => return false,
+        };
+        self.lifetime.is_some_and(|remaining| full.saturating_sub(remaining) < FRESH_WINDOW)
+    }
+
+    // Board glyph, also used by network clients to pick its color
+    pub fn glyph(&self) -> char {
+        match self.role {
+            Role::Food(kind) => kind.glyph(),
+            Role::PowerUp(power) => power.glyph(),
+            Role::Obstacle => '#',
+        }
+    }
+
+    // Runs one cell away from the nearest head once every few ticks, onto a free cell that
+    // gains distance; cornered entities, and ones that don't flee, stay put
+    pub fn flee(&mut self, heads: &[Position], is_free: impl Fn(&Position) -> bool) {
+        let Some(since_move) = &mut self.flee else {
+            return;
+        };
+        *since_move += 1;
+        if *since_move < FLEE_INTERVAL {
+            return;
+        }
+        *since_move = 0;
+        let distance = |pos: &Position| {
+            heads.iter().map(|head| head.x.abs_diff(pos.x) + head.y.abs_diff(pos.y)).min().unwrap_or(0)
+        };
+        let best = [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+            .into_iter()
+            .filter_map(|direction| self.pos.step(direction).cell())
+            .filter(|pos| is_free(pos))
+            .max_by_key(|pos| distance(pos))
+            .filter(|pos| distance(pos) > distance(&self.pos));
+        if let Some(pos) = best {
+            self.pos = pos;
+        }
+    }
+
+    // Moves one cell toward a target, along the longer axis first and the other one if that
+    // cell isn't free; stays put when neither is
+    pub fn pull(&mut self, target: Position, is_free: impl Fn(&Position) -> bool) {
+        let step = |from: u16, to: u16| match to.cmp(&from) {
+            std::cmp::Ordering::Less => from - 1,
+            std::cmp::Ordering::Equal => from,
+            std::cmp::Ordering::Greater => from + 1,
+        };
+        let horizontal = Position { x: step(self.pos.x, target.x), y: self.pos.y };
+        let vertical = Position { x: self.pos.x, y: step(self.pos.y, target.y) };
+        let moves = if self.pos.x.abs_diff(target.x) >= self.pos.y.abs_diff(target.y) {
+            [horizontal, vertical]
+        } else {
+            [vertical, horizontal]
+        };
+        if let Some(pos) = moves.into_iter().find(|pos| *pos != self.pos && is_free(pos)) {
+            self.pos = pos;
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Structured events emitted as a game plays out
+// Design Decision: The state reports what happened instead of reaching into timers, pace or
+// records itself; Game reacts to each event first and then passes it on to subscribers, so
+// sound, stats, achievements, replays or outside tooling can hook in without touching update()
+use crate::food::FoodKind;
+use crate::game::Direction;
+use crate::powerup::PowerUp;
+use crate::replay::Input;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Something that happened in a game
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum GameEvent {
+    FoodEaten { player: usize, kind: FoodKind, points: u32 },  // Poison counts too, for no points
+    PowerUpCollected { player: usize, power: PowerUp },
+    DirectionChanged { player: usize, direction: Direction },  // A turn was queued for the snake
+    Collision { player: usize },                               // A snake crashed or the zone wore it away
+    NearMiss { player: usize },                                // A wall or snake just came up beside a head
+    InputMade(Input),                                          // A steering or pace input, as replays record it
+    // A tick ran: game time so far, and player one's food, longest length and share of the
+    // open floor (published before anything the tick caused)
+    Ticked { played: Duration, eaten: u32, longest: usize, fill: f64 },
+    GameOver,
+}
+
+// Hears every event of a game as it happens
+pub type Subscriber = Box<dyn FnMut(&GameEvent)>;
+
+// Fans events out to every subscriber, in the order they subscribed
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<Subscriber>,
+}
+
+impl EventBus {
+    // Adds a subscriber for every event from now on
+    pub fn subscribe(&mut self, subscriber: impl FnMut(&GameEvent) + 'static) {
+        self.subscribers.push(Box::new(subscriber));
+    }
+
+    // Hands an event to every subscriber
+    pub fn publish(&mut self, event: &GameEvent) {
+        for subscriber in &mut self.subscribers {
+            subscriber(event);
+        }
+    }
+}
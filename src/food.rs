@@ -0,0 +1,80 @@
+// The kinds of food on the board
+// Design Decision: Every kind shares one spawn path and one eaten branch in GameState::update,
+// so new kinds only need a row in the weight table and their effect
+// Design Decision: Lifetimes run on game time like power-up effects, so pauses don't eat
+// into them and replays expire food on exactly the same tick
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// How many tail segments poison takes off (the snake never drops below one segment)
+pub const POISON_SHRINK: usize = 3;
+
+// What a piece of food does when eaten
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum FoodKind {
+    Normal,  // One point, grows the snake
+    Golden,  // Five points, grows the snake, vanishes quickly
+    Poison,  // No points, shrinks the snake, rots away quickly
+}
+
+// Spawn chances, out of the total weight
+const WEIGHTS: [(FoodKind, u32); 3] = [(FoodKind::Normal, 80), (FoodKind::Golden, 10), (FoodKind::Poison, 10)];
+
+impl FoodKind {
+    // Picks a kind at random according to the spawn weights
+    pub fn roll(rng: &mut impl Rng) -> FoodKind {
+        let total: u32 = WEIGHTS.iter().map(|(_, weight)| weight).sum();
+        let mut pick = rng.gen_range(0..total);
+        for (kind, weight) in WEIGHTS {
+            if pick < weight {
+                return kind;
+            }
+            pick -= weight;
+        }
+        FoodKind::Normal
+    }
+
+    // Parses a name given by name() (normal, golden, poison)
+    pub fn parse(name: &str) -> Option<FoodKind> {
+        WEIGHTS.iter().map(|&(kind, _)| kind).find(|kind| kind.name() == name)
+    }
+
+    // Name used in state snapshots
+    pub fn name(self) -> &'static str {
+        match self {
+            FoodKind::Normal => "normal",
+            FoodKind::Golden => "golden",
+            FoodKind::Poison => "poison",
+        }
+    }
+
+    // Board glyph, also used by network clients to pick its color
+    pub fn glyph(self) -> char {
+        match self {
+            FoodKind::Normal => '*',
+            FoodKind::Golden => '$',
+            FoodKind::Poison => '%',
+        }
+    }
+
+    // Points scored for eating it, before the difficulty's food value
+    pub fn points(self) -> u32 {
+        match self {
+            FoodKind::Normal => 1,
+            FoodKind::Golden => 5,
+            FoodKind::Poison => 0,
+        }
+    }
+
+    // Game time it stays on the board before respawning elsewhere
+    // Design Decision: Poison rots so it can't block progress, golden food is a race, and
+    // even plain food moves on eventually so nobody can stall next to it
+    pub fn lifetime(self) -> Duration {
+        match self {
+            FoodKind::Normal => Duration::from_secs(15),
+            FoodKind::Golden => Duration::from_secs(6),
+            FoodKind::Poison => Duration::from_secs(5),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// The board as a grid of logical glyphs, the one thing every frontend draws from
+// Design Decision: A Frame says what each cell shows (glyph, dimming, skin tone) and nothing
+// about how; the terminal renderers, GIF export and benchmarks all work from it, so none of
+// them needs the game's internals
+use crate::game::Position;
+
+// One snapshot of the board, in logical glyphs
+#[derive(Clone)]
+pub struct Frame {
+    width: u16,
+    height: u16,
+    cells: Vec<(char, bool)>,  // Glyph and whether it's drawn dimmed, row by row
+    tones: Vec<u8>,            // Skin tone of each snake segment, row by row (0 elsewhere)
+}
+
+impl Frame {
+    // An empty board of the given size
+    pub fn new(width: u16, height: u16) -> Self {
+        let size = width as usize * height as usize;
+        Frame { width, height, cells: vec![(' ', false); size], tones: vec![0; size] }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    // Glyph and dimming at a cell (a blank cell outside the board)
+    pub fn get(&self, x: u16, y: u16) -> (char, bool) {
+        match x < self.width && y < self.height {
+            true => self.cells[y as usize * self.width as usize + x as usize],
+            false => (' ', false),
+        }
+    }
+
+    // Skin tone at a cell (0 outside the board)
+    pub fn tone(&self, x: u16, y: u16) -> u8 {
+        match x < self.width && y < self.height {
+            true => self.tones[y as usize * self.width as usize + x as usize],
+            false => 0,
+        }
+    }
+
+    // Cells that differ from an earlier frame of the same size
+    pub fn changed_since(&self, previous: &Frame) -> Vec<Position> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Position { x, y }))
+            .filter(|pos| {
+                self.get(pos.x, pos.y) != previous.get(pos.x, pos.y) || self.tone(pos.x, pos.y) != previous.tone(pos.x, pos.y)
+            })
+            .collect()
+    }
+
+    // Replaces one cell, ignoring positions outside the board
+    pub fn set(&mut self, x: u16, y: u16, glyph: char, dim: bool) {
+        if x < self.width && y < self.height {
+            self.cells[y as usize * self.width as usize + x as usize] = (glyph, dim);
+        }
+    }
+
+    // Sets the skin tone of one cell, ignoring positions outside the board
+    pub fn set_tone(&mut self, x: u16, y: u16, tone: u8) {
+        if x < self.width && y < self.height {
+            self.tones[y as usize * self.width as usize + x as usize] = tone;
+        }
+    }
+}
@@ -1,234 +1,1165 @@
-// External crate imports for terminal manipulation and game functionality
-use crossterm::{
-    cursor::{Hide, Show, MoveTo},  // Terminal cursor control
-    event::{self, Event, KeyCode}, // Keyboard input handling
-    execute,
-    terminal::{self, Clear, ClearType},
-    style::Print,
-};
-use rand::Rng;  // Random number generation for food placement
-use std::io::{stdout, Write};
+// The game engine: a board's rules plus the clock, bots, inputs and progress around them
+// Design Decision: Nothing here touches the terminal; the frontend (play.rs in the binary)
+// wraps a Game to draw it and feed it keys, and headless runs, bots and tests drive it directly
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};  // Seeded random generation so runs can be replayed
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};  // Time management for game loop
-use std::collections::VecDeque;  // Double-ended queue for efficient snake body management
+use crate::ai::{Bot, Difficulty};
+use crate::campaign::Campaign;
+use crate::config::GameConfig;
+use crate::difficulty;
+use crate::effects::Effects;
+use crate::events::{EventBus, GameEvent};
+use crate::food::FoodKind;
+use crate::lang;
+use crate::log;
+use crate::ghost::Ghost;
+use crate::level::Level;
+use crate::maze;
+use crate::mode::Mode;
+use crate::mods::GameMod;
+use crate::popups::Popups;
+use crate::powerup::PowerUp;
+use crate::replay::{Change, Input, Recorder, Replay};
+use crate::scores::HighScores;
+use crate::state::{BoardRng, GameState};
+use crate::stream::StateStream;
 
-// Game board dimensions
-// Design Decision: Fixed size makes collision detection simpler
-const WIDTH: u16 = 40;
-const HEIGHT: u16 = 20;
+// Board dimensions used when the board can't be fitted to the terminal (and for the campaign)
+// Design Decision: Fixed fallback size keeps the classic board familiar
+pub const WIDTH: u16 = 40;
+pub const HEIGHT: u16 = 20;
+
+// Default time between snake moves
+pub const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+// Default floor for the pace as it speeds up with the score
+pub const MIN_TICK_INTERVAL: Duration = Duration::from_millis(50);
+
+// Limits and step for the pace multiplier the speed keys change
+const MIN_PACE: f64 = 0.5;
+const MAX_PACE: f64 = 2.0;
+const PACE_STEP: f64 = 0.25;
+
+// Length of the countdown before the snakes start (or restart) moving
+const COUNTDOWN: Duration = Duration::from_secs(3);
+
+// Ticks of history practice mode keeps, and how many of them one press of the rewind key undoes
+const REWIND_HISTORY: usize = 100;
+const REWIND_TICKS: usize = 20;
+
+// Slow motion after a close call: only at or below this tick interval, for this many ticks,
+// each taking this many times as long on screen
+const SLOW_MOTION_PACE: Duration = Duration::from_millis(70);
+const SLOW_MOTION_TICKS: u32 = 4;
+const SLOW_MOTION_FACTOR: u32 = 3;
+
+// Number of static obstacles scattered across the board
+// Design Decision: Small enough to leave plenty of room for the snake to roam
+const OBSTACLE_COUNT: usize = 12;
+
+// Head/body glyphs for each player, indexed by snake (colors come from the theme)
+// Design Decision: Distinct glyphs keep players apart even without color support
+pub const PLAYER_GLYPHS: [(char, char); 2] = [('O', 'o'), ('@', '+')];
+
+// A quarter turn relative to a snake's heading, for relative steering
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    // Single-letter code used by the network protocol
+    pub fn code(self) -> char {
+        match self {
+            Turn::Left => 'L',
+            Turn::Right => 'R',
+        }
+    }
+
+    // Parses a single-letter turn code
+    pub fn from_code(code: &str) -> Option<Turn> {
+        match code {
+            "L" => Some(Turn::Left),
+            "R" => Some(Turn::Right),
+            _ => None,
+        }
+    }
+}
 
 // Position struct represents a point on the game board
 // Design Decision: Using u16 because terminal coordinates are never negative
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl Position {
+    // The point one move away in a direction, which may lie off the board
+    pub fn step(self, direction: Direction) -> Point {
+        let (x, y) = (i32::from(self.x), i32::from(self.y));
+        match direction {
+            Direction::Up => Point { x, y: y - 1 },
+            Direction::Down => Point { x, y: y + 1 },
+            Direction::Left => Point { x: x - 1, y },
+            Direction::Right => Point { x: x + 1, y },
+        }
+    }
+}
+
+// A point in signed board coordinates, for where a move lands before the edge rules apply
+// Design Decision: Moves are worked out in signed coordinates and only then resolved back onto
+// the board, so stepping off any edge can't underflow whatever that edge does
 #[derive(Clone, Copy, PartialEq)]
-struct Position {
-    x: u16,
-    y: u16,
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    // The cell at this point, if neither coordinate is negative (or too large for a cell)
+    pub fn cell(self) -> Option<Position> {
+        Some(Position { x: u16::try_from(self.x).ok()?, y: u16::try_from(self.y).ok()? })
+    }
+
+    // The board cell at this point, if it lies on a board of the given size
+    pub fn on_board(self, width: u16, height: u16) -> Option<Position> {
+        self.cell().filter(|pos| pos.x < width && pos.y < height)
+    }
 }
 
 // Direction enum represents possible movement directions
 // Design Decision: Using enum ensures type safety for direction handling
-#[derive(PartialEq, Clone, Copy)]
-enum Direction {
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Direction {
     Up,
     Down,
     Left,
     Right,
 }
 
+impl Direction {
+    // The direction pointing the other way
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    // The direction a quarter turn away, as seen by a snake heading this way
+    pub fn turned(self, turn: Turn) -> Direction {
+        match (self, turn) {
+            (Direction::Up, Turn::Left) | (Direction::Down, Turn::Right) => Direction::Left,
+            (Direction::Up, Turn::Right) | (Direction::Down, Turn::Left) => Direction::Right,
+            (Direction::Left, Turn::Left) | (Direction::Right, Turn::Right) => Direction::Down,
+            (Direction::Left, Turn::Right) | (Direction::Right, Turn::Left) => Direction::Up,
+        }
+    }
+
+    // Single-letter code used by the network protocol and replay files
+    pub fn code(self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+
+    // Parses a single-letter direction code
+    pub fn from_code(code: &str) -> Option<Direction> {
+        match code {
+            "U" => Some(Direction::Up),
+            "D" => Some(Direction::Down),
+            "L" => Some(Direction::Left),
+            "R" => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+// Where a game's board comes from
+// Design Decision: Recorded in replays so playback rebuilds exactly the same board
+#[derive(Clone)]
+pub enum Board {
+    Classic,         // Default board with scattered obstacles
+    Maze,            // Freshly generated maze
+    Campaign,        // Built-in campaign stages
+    Custom(Level),   // A level loaded from disk
+}
+
+impl Board {
+    // Short name for menus and the high score table
+    pub fn name(&self) -> &'static str {
+        match self {
+            Board::Classic => "classic",
+            Board::Maze => "maze",
+            Board::Campaign => "campaign",
+            Board::Custom(_) => "custom level",
+        }
+    }
+}
+
+// Everything a tick changes, as it stood before that tick, for practice mode's rewind
+// and for checkpoints
+struct Snapshot {
+    state: GameState,
+    ticks: u64,
+    played: Duration,
+    longest: usize,
+    inputs: usize,            // Inputs recorded so far; later ones are dropped on rewinding
+    tick_interval: Duration,
+    pace: f64,
+    effects: Effects,
+    campaign: Option<Campaign>,
+}
+
+// Practice mode's rewind buffer
+// Design Decision: A ring of whole-state snapshots rather than undoing moves: the state is
+// already Clone (see state::step), and a snapshot brings back the RNG with everything else,
+// so a rewound run plays on exactly like one that never went wrong, replay included
+#[derive(Default)]
+struct Rewind {
+    history: VecDeque<Snapshot>,  // Oldest first, one per tick, at most REWIND_HISTORY
+    crashed: bool,                // A crash is being held on screen, waiting to be rewound
+}
+
+// A saved point in a game to come back to, such as a network client's last confirmed tick
+pub struct Checkpoint(Snapshot);
+
 // Main game struct containing all game state
 // Design Decision: Encapsulating all game state in one struct makes state management clearer
 pub struct Game {
-    snake: VecDeque<Position>,    // Using VecDeque for O(1) push/pop at both ends
-    food: Position,               // Current food position
-    direction: Direction,         // Current movement direction
-    next_direction: Direction,    // Buffered next direction (prevents rapid 180° turns)
-    score: u32,                  // Current score
-    game_over: bool,             // Game state flag
-    last_update: Instant,        // Time tracking for game loop
+    state: GameState,                   // Board contents and rules
+    bots: Vec<Option<(Difficulty, Box<dyn Bot>)>>,  // Computer controller per snake (None = human)
+    board: Board,                       // Board source, kept for replays
+    settings: GameConfig,               // Board size and pace, kept for replays
+    seed: u64,                          // Seed behind every random choice in this game
+    daily: Option<String>,              // Date of the daily challenge this game is, if it is one
+    ticks: u64,                         // Ticks simulated so far
+    played: Duration,                   // Game time elapsed, for lifetime stats
+    longest: usize,                     // Longest player-one snake so far, for lifetime stats
+    inputs: Recorder,                   // Every steering and pace input, for the replay file
+    last_update: Instant,               // Time tracking for game loop
+    tick_interval: Duration,            // Time between snake moves, before power-up effects
+    pace: f64,                          // Speed multiplier set with the speed keys
+    effects: Effects,                   // Running power-up effects
+    events: EventBus,                   // Subscribers to what happens in this game
+    campaign: Option<Campaign>,         // Stage progression when playing the campaign
+    local_players: usize,               // Snakes steered from this keyboard (the rest are remote)
+    ghost: Option<Box<Ghost>>,          // Personal best run raced alongside this one
+    popups: Popups,                     // Points floating up from eaten food
+    rewind: Option<Rewind>,             // Recent ticks to step back through (practice mode only)
+    slow_motion: Option<u32>,           // Slowed ticks left after a close call (None = never slow down)
+    state_stream: Option<StateStream>,  // Where a snapshot goes after every tick, if anywhere
+    paused: bool,                       // Frozen by the player; no ticks run until unpaused
+    forfeited: Option<usize>,           // Player who gave the round up from the other end, if one did
+    countdown: Option<(Instant, u64)>,  // When the running countdown ends, and the number on screen
 }
 
 impl Game {
     // Creates a new game instance with initial state
-    // Design Decision: Using builder pattern for clear initialization
-    pub fn new() -> Self {
-        let mut snake = VecDeque::new();
-        let center = Position {
-            x: WIDTH / 2,
-            y: HEIGHT / 2,
+    // Design Decision: Every random choice flows from the seed, so (board, players, seed)
+    // plus the recorded inputs reproduce a run exactly
+    pub fn new(board: Board, players: usize, seed: u64, settings: GameConfig) -> Self {
+        let mut rng = BoardRng::seed_from_u64(seed);
+        let mut campaign = None;
+        let level = match &board {
+            Board::Classic => Game::classic_level(players, &settings, &mut rng),
+            Board::Maze => maze::generate(settings.width, settings.height, &mut rng),
+            // The campaign is a single-player experience
+            Board::Campaign => {
+                let first_stage = Campaign::new();
+                let level = first_stage.stage().level(&mut rng);
+                campaign = Some(first_stage);
+                level
+            }
+            Board::Custom(level) => level.clone(),
         };
-        snake.push_back(center);  // Snake starts with one segment in center
-
-        Game {
-            snake,
-            food: Game::generate_food(),
-            direction: Direction::Right,  // Snake starts moving right
-            next_direction: Direction::Right,
-            score: 0,
-            game_over: false,
+        let base_interval = campaign
+            .as_ref()
+            .map_or(settings.tick_interval, |campaign| campaign.stage().tick_interval);
+        let tick_interval = Game::starting_pace(&settings, base_interval);
+
+        let state = GameState::new(level, players, &settings, rng);
+        let practice = settings.mode == Mode::Practice;
+        let mut game = Game {
+            bots: (0..state.snakes.len()).map(|_| None).collect(),
+            state,
+            board,
+            settings,
+            seed,
+            daily: None,
+            ticks: 0,
+            played: Duration::ZERO,
+            longest: 1,
+            inputs: Recorder::default(),
+            pace: 1.0,
             last_update: Instant::now(),
+            tick_interval,
+            effects: Effects::default(),
+            events: EventBus::default(),
+            campaign,
+            local_players: players,
+            ghost: None,
+            popups: Popups::default(),
+            rewind: practice.then(Rewind::default),
+            slow_motion: None,
+            state_stream: None,
+            paused: false,
+            forfeited: None,
+            countdown: None,
+        };
+        // The replay hears about inputs like any other subscriber
+        let recorder = game.inputs.clone();
+        game.subscribe(move |event| recorder.hear(event));
+        game
+    }
+
+    // Builds the classic board: configured size with scattered obstacles
+    fn classic_level(players: usize, settings: &GameConfig, rng: &mut BoardRng) -> Level {
+        let (width, height) = (settings.width, settings.height);
+        let center = Position {
+            x: width / 2,
+            y: height / 2,
+        };
+        let mut spawns = vec![(center, Direction::Right)];
+        if players > 1 {
+            let second = Position { x: width * 3 / 4, y: height * 3 / 4 };
+            spawns.push((second, Direction::Left));
+        }
+        let walls = Game::generate_obstacles(width, height, &spawns, rng);
+        let portals = Game::generate_portals(width, height, &walls, &spawns, rng);
+        Level {
+            width,
+            height,
+            walls,
+            spawn: center,
+            food: Vec::new(),
+            portals,
         }
     }
 
-    // Generates random coordinates for food placement
-    // Design Decision: Separate function for better code organization
-    fn generate_food() -> Position {
-        let mut rng = rand::thread_rng();
-        Position {
-            // Generate position within game bounds (excluding walls)
-            x: rng.gen_range(1..WIDTH-1),
-            y: rng.gen_range(1..HEIGHT-1),
+    // Rebuilds a saved game by re-simulating it up to where it was left
+    // Design Decision: A save is just the replay so far; re-running it restores snakes, food,
+    // score, stage and even the RNG exactly, which a field-by-field dump couldn't
+    pub fn resume(replay: Replay) -> Self {
+        let mut game = Game::new(replay.board, replay.players, replay.seed, replay.settings);
+        game.daily = replay.daily;
+        for (player, difficulty) in replay.bots {
+            game.hand_to_ai(player, difficulty);
+        }
+        let mut inputs = replay.inputs.into_iter().peekable();
+        while game.ticks < replay.ticks && !game.is_over() {
+            while let Some(input) = inputs.next_if(|input| input.tick <= game.ticks) {
+                game.apply(input.change);
+            }
+            game.tick_quietly();
+        }
+        // Turns made just before quitting still apply on the next move
+        for input in inputs {
+            game.apply(input.change);
         }
+        game
     }
 
-    // Places food in a valid position (not on snake)
-    // Design Decision: Retry mechanism ensures valid food placement
-    fn spawn_food(&mut self) {
-        self.food = Game::generate_food();
-        // Keep generating new positions until food doesn't overlap with snake
-        while self.snake.iter().any(|pos| pos.x == self.food.x && pos.y == self.food.y) {
-            self.food = Game::generate_food();
+    // Hands every snake past the first `count` to a remote controller
+    pub fn set_local_players(&mut self, count: usize) {
+        self.local_players = count;
+    }
+
+    // Hands control of a snake to a computer bot of the given difficulty
+    pub fn hand_to_ai(&mut self, player: usize, difficulty: Difficulty) {
+        let bot_seed = self.seed.wrapping_add(player as u64 + 1);
+        if let Some(slot) = self.bots.get_mut(player) {
+            *slot = Some((difficulty, difficulty.bot(bot_seed)));
+            self.local_players = self.local_players.min(player);
         }
     }
 
-    // Updates game state (snake movement, collisions, food collection)
-    // Design Decision: Single function for all state updates maintains consistency
-    fn update(&mut self) {
-        if self.game_over {
-            return;
+    // Stacks a mod on top of the configured ones
+    // Design Decision: Replays only record the built-in mods named in the settings, so a game
+    // with mods added here re-simulates without them
+    pub fn add_mod(&mut self, game_mod: Box<dyn GameMod>) {
+        self.state.add_mod(game_mod);
+    }
+
+    // Marks this game as the daily challenge of a date, so its result is kept with the dailies
+    pub fn set_daily(&mut self, date: String) {
+        self.daily = Some(date);
+    }
+
+    // Races this game against a previous run on the same board and seed
+    pub fn set_ghost(&mut self, ghost: Ghost) {
+        self.ghost = Some(Box::new(ghost));
+    }
+
+    // Turns the slow motion after a local player's close call at speed on or off
+    pub fn set_slow_motion(&mut self, enabled: bool) {
+        self.slow_motion = enabled.then_some(0);
+    }
+
+    // Writes a snapshot of the board to a stream after every tick
+    pub fn set_state_stream(&mut self, stream: StateStream) {
+        self.state_stream = Some(stream);
+    }
+
+    // Hands the state stream back once the game is done with it, for the next game
+    pub fn take_state_stream(&mut self) -> Option<StateStream> {
+        self.state_stream.take()
+    }
+
+    // Streams the state after the tick just run
+    // Design Decision: A reader that goes away (a closed pipe, say) only ends the stream, never the game
+    fn emit_state(&mut self) {
+        if let Some(stream) = &mut self.state_stream {
+            if stream.write(self.ticks, &self.state).is_err() {
+                self.state_stream = None;
+            }
         }
+    }
 
-        // Apply buffered direction change
-        self.direction = self.next_direction;
+    // Whether a snake is computer-controlled
+    pub fn is_bot(&self, player: usize) -> bool {
+        self.bots.get(player).is_some_and(Option::is_some)
+    }
 
-        // Calculate new head position based on current direction
-        let head = self.snake.front().unwrap();
-        let new_head = match self.direction {
-            Direction::Up => Position { x: head.x, y: head.y - 1 },
-            Direction::Down => Position { x: head.x, y: head.y + 1 },
-            Direction::Left => Position { x: head.x - 1, y: head.y },
-            Direction::Right => Position { x: head.x + 1, y: head.y },
-        };
+    // Time between snake moves at the current stage and pace
+    pub fn tick_interval(&self) -> Duration {
+        self.effects.tick_interval(self.tick_interval).div_f64(self.pace).max(Duration::from_millis(1))
+    }
 
-        // Check wall collisions
-        // Design Decision: Early returns for game-ending conditions
-        if new_head.x == 0 || new_head.x == WIDTH - 1 || new_head.y == 0 || new_head.y == HEIGHT - 1 {
-            self.game_over = true;
-            return;
+    // Wall-clock time between snake moves, stretched while slow motion runs
+    // Design Decision: Slow motion only changes how long a tick stays on screen, never the game
+    // time it stands for, so effects, clocks and replays run the same with it on or off
+    fn frame_interval(&self) -> Duration {
+        match self.slow_motion {
+            Some(left) if left > 0 => self.tick_interval() * SLOW_MOTION_FACTOR,
+            _ => self.tick_interval(),
+        }
+    }
+
+    // Whether the speed keys work in this game
+    // Design Decision: Hardcore runs are meant to be played at the floor pace, and bot-only
+    // games have nobody to press them
+    fn pace_adjustable(&self) -> bool {
+        self.local_players > 0 && self.settings.mode != Mode::Hardcore
+    }
+
+    // Sets the pace multiplier, within limits, recording it so replays keep the same timing
+    // Design Decision: The pace scales the game time each tick stands for as well as the wall
+    // clock, so power-ups and timed modes stay in step with what the player sees
+    fn set_pace(&mut self, pace: f64) {
+        let pace = pace.clamp(MIN_PACE, MAX_PACE);
+        if pace != self.pace {
+            self.pace = pace;
+            self.events.publish(&GameEvent::InputMade(Input { tick: self.ticks, change: Change::Pace(pace) }));
+        }
+    }
+
+    // One press of a speed key, returning whether the game took it
+    pub fn change_pace(&mut self, faster: bool) -> bool {
+        if !self.pace_adjustable() || self.paused {
+            return false;
+        }
+        let step = if faster { PACE_STEP } else { -PACE_STEP };
+        self.set_pace(self.pace + step);
+        true
+    }
+
+    // Seed behind this game's obstacles, mazes and food
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // Game time played so far (paused stage transitions don't count)
+    pub fn play_time(&self) -> Duration {
+        self.played
+    }
+
+    // Game time left on the clock in timed modes
+    fn time_left(&self) -> Option<Duration> {
+        self.settings.mode.time_limit().map(|limit| limit.saturating_sub(self.played))
+    }
+
+    // Whether the round ended because the clock ran out
+    fn time_is_up(&self) -> bool {
+        self.time_left().is_some_and(|left| left.is_zero())
+    }
+
+    // Longest player one's snake has grown this game
+    pub fn longest_snake(&self) -> usize {
+        self.longest
+    }
+
+    // Board size and pace this game was created with
+    pub fn settings(&self) -> &GameConfig {
+        &self.settings
+    }
+
+    // Board source this game was created from
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    // The campaign being played, if this game is one
+    pub fn campaign(&self) -> Option<&Campaign> {
+        self.campaign.as_ref()
+    }
+
+    // Current campaign stage number (0 outside the campaign)
+    pub fn stage(&self) -> usize {
+        self.campaign.as_ref().map_or(0, Campaign::stage_number)
+    }
+
+    // Ticks simulated so far
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    // Every steering and pace input so far, in the order they were made
+    pub fn inputs(&self) -> Vec<Input> {
+        self.inputs.inputs()
+    }
+
+    // Everything needed to replay this game from the start
+    pub fn replay(&self) -> Replay {
+        Replay {
+            seed: self.seed,
+            players: self.state.snakes.len(),
+            bots: self.bots
+                .iter()
+                .enumerate()
+                .filter_map(|(player, bot)| bot.as_ref().map(|(difficulty, _)| (player, *difficulty)))
+                .collect(),
+            board: self.board.clone(),
+            settings: self.settings.clone(),
+            inputs: self.inputs.inputs(),
+            ticks: self.ticks,
+            score: self.state.score(),
+            daily: self.daily.clone(),
+        }
+    }
+
+    // Read-only view of the board
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    // Whether the round has ended
+    pub fn is_over(&self) -> bool {
+        self.state.is_over() && !self.crashed()
+    }
+
+    // Whether a practice run's crash is held on screen for the player to rewind
+    pub fn crashed(&self) -> bool {
+        self.rewind.as_ref().is_some_and(|rewind| rewind.crashed)
+    }
+
+    // Whether the player has frozen the game
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    // Freezes the game, as when the terminal gets too small to show it
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    // Freezes or unfreezes the game
+    // Design Decision: Unpausing goes through the countdown, whose end restarts the tick
+    // clock, so the time spent paused isn't caught up as one giant step
+    pub fn toggle_pause(&mut self) {
+        if self.crashed() {
+            return;  // Only rewinding or quitting moves on from a held crash
+        }
+        self.paused = !self.paused;
+        if !self.paused {
+            self.start_countdown();
+        }
+    }
+
+    // Number the countdown shows, while one is running
+    pub fn countdown(&self) -> Option<u64> {
+        self.countdown.map(|(_, number)| number)
+    }
+
+    // Snakes steered from this keyboard
+    pub fn local_players(&self) -> usize {
+        self.local_players
+    }
+
+    // Whether this is a practice run, whose ticks can be stepped back through
+    pub fn is_practice(&self) -> bool {
+        self.rewind.is_some()
+    }
+
+    // The personal best run raced alongside this one, if there is one
+    pub fn ghost(&self) -> Option<&Ghost> {
+        self.ghost.as_deref()
+    }
+
+    // Power-up effects running on the snakes
+    pub fn effects(&self) -> &Effects {
+        &self.effects
+    }
+
+    // Score popups floating up from food eaten lately
+    pub fn popups(&self) -> &Popups {
+        &self.popups
+    }
+
+    // Ends the round early (quit key or lost connection)
+    pub fn quit(&mut self) {
+        if let Some(rewind) = &mut self.rewind {
+            rewind.crashed = false;
         }
+        self.state.end();
+        self.handle_events();
+    }
 
-        // Check self-collision
-        if self.snake.iter().any(|pos| pos.x == new_head.x && pos.y == new_head.y) {
-            self.game_over = true;
+    // Ends the round as lost by a remote player who left or let their reconnect window run out,
+    // so the snake left on the board takes the win
+    pub fn forfeit(&mut self, player: usize) {
+        self.forfeited = Some(player);
+        self.quit();
+    }
+
+    // Calls a subscriber with every event from now on
+    pub fn subscribe(&mut self, subscriber: impl FnMut(&GameEvent) + 'static) {
+        self.events.subscribe(subscriber);
+    }
+
+    // Whether the round was abandoned at this keyboard while every snake was still alive
+    pub fn was_quit(&self) -> bool {
+        self.is_over() && self.forfeited.is_none() && self.state.snakes.iter().all(|snake| snake.alive) && !self.time_is_up()
+    }
+
+    // Buffers a direction change for the given player's snake
+    // Design Decision: Computer-controlled snakes ignore outside steering
+    pub fn steer(&mut self, player: usize, direction: Direction) {
+        if self.is_bot(player) {
             return;
         }
+        if let Some(snake) = self.state.snakes.get_mut(player) {
+            if snake.steer(direction) {
+                self.events.publish(&GameEvent::DirectionChanged { player, direction });
+            }
+            self.events.publish(&GameEvent::InputMade(Input { tick: self.ticks, change: Change::Steer(player, direction) }));
+        }
+    }
 
-        // Move snake by adding new head
-        self.snake.push_front(new_head);
+    // Turns a player's snake a quarter turn from the heading its queued turns leave it on
+    // Design Decision: Relative turns become absolute steering here, so replays and the rest of
+    // the game never need to know which scheme the player used
+    pub fn turn(&mut self, player: usize, turn: Turn) {
+        if let Some(snake) = self.state.snakes.get(player) {
+            let direction = snake.heading().turned(turn);
+            self.steer(player, direction);
+        }
+    }
 
-        // Handle food collection
-        if new_head.x == self.food.x && new_head.y == self.food.y {
-            self.score += 1;
-            self.spawn_food();
-        } else {
-            // Remove tail if no food was eaten
-            self.snake.pop_back();
-        }
-    }
-
-    // Renders the game state to the terminal
-    // Design Decision: Using crossterm for cross-platform terminal manipulation
-    fn draw(&self) -> std::io::Result<()> {
-        let mut stdout = stdout();
-        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
-        
-        // Draw game border
-        for x in 0..WIDTH {
-            execute!(stdout, MoveTo(x, 0), Print("#"))?;
-        }
-
-        // Draw game area and entities
-        for y in 1..HEIGHT-1 {
-            execute!(stdout, MoveTo(0, y), Print("#"))?;
-            for x in 1..WIDTH-1 {
-                let pos = Position { x, y };
-                let char = if self.snake.front() == Some(&pos) {
-                    'O'  // Snake head (distinct from body)
-                } else if self.snake.contains(&pos) {
-                    'o'  // Snake body
-                } else if self.food.x == x && self.food.y == y {
-                    '*'  // Food
-                } else {
-                    ' '  // Empty space
+    // Applies a recorded input, as when replaying or resuming a game
+    pub fn apply(&mut self, change: Change) {
+        match change {
+            Change::Steer(player, direction) => self.steer(player, direction),
+            Change::Pace(pace) => self.set_pace(pace),
+        }
+    }
+
+    // Advances the campaign when the current stage's target is reached, returning
+    // whether a new stage was loaded
+    fn check_stage_progress(&mut self) -> bool {
+        let score = self.state.score();
+        let Some(campaign) = self.campaign.as_mut() else {
+            return false;
+        };
+        if !campaign.is_cleared(score) {
+            return false;
+        }
+        let Some(stage) = campaign.advance() else {
+            return false;
+        };
+
+        let (level, tick_interval) = (stage.level(self.state.rng()), stage.tick_interval);
+        self.state.load_level(level);
+        self.tick_interval = Game::starting_pace(&self.settings, tick_interval);
+        true
+    }
+
+    // Tick interval a board normally played at `base` starts at under these settings
+    // Design Decision: Hardcore skips the ramp and starts at the floor the others speed up to
+    fn starting_pace(settings: &GameConfig, base: Duration) -> Duration {
+        match settings.mode {
+            Mode::Hardcore => settings.min_tick_interval,
+            _ => settings.difficulty.tick_interval(base),
+        }
+    }
+
+    // Scatters obstacles across the board away from the starting positions
+    // Design Decision: Obstacles are never placed next to each other or the border,
+    // so they can't wall off any cell and food is always reachable
+    fn generate_obstacles(width: u16, height: u16, spawns: &[(Position, Direction)], rng: &mut BoardRng) -> Vec<Position> {
+        let mut obstacles: Vec<Position> = Vec::with_capacity(OBSTACLE_COUNT);
+        let mut attempts = 0;
+
+        while obstacles.len() < OBSTACLE_COUNT && attempts < 1000 {
+            attempts += 1;
+            let candidate = Position {
+                x: rng.gen_range(2..width-2),
+                y: rng.gen_range(2..height-2),
+            };
+
+            // Keep the starting rows ahead of each snake clear so none spawns into a wall
+            let blocks_spawn = spawns.iter().any(|(start, direction)| {
+                let ahead = match direction {
+                    Direction::Left => candidate.x <= start.x + 2,
+                    _ => candidate.x + 2 >= start.x,
                 };
-                execute!(stdout, MoveTo(x, y), Print(char))?;
+                candidate.y.abs_diff(start.y) <= 1 && ahead
+            });
+            if blocks_spawn {
+                continue;
+            }
+
+            // Require an empty ring around every obstacle
+            let crowded = obstacles.iter().any(|pos| {
+                pos.x.abs_diff(candidate.x) <= 1 && pos.y.abs_diff(candidate.y) <= 1
+            });
+            if !crowded {
+                obstacles.push(candidate);
+            }
+        }
+
+        obstacles
+    }
+
+    // Places one pair of portals, one end in each outer third of the board
+    // Design Decision: Ends far apart make the shortcut worth taking; the spawn rows stay
+    // clear so nobody is teleported on their first move
+    fn generate_portals(
+        width: u16,
+        height: u16,
+        walls: &[Position],
+        spawns: &[(Position, Direction)],
+        rng: &mut BoardRng,
+    ) -> Vec<(Position, Position)> {
+        let mut pick = |columns: std::ops::Range<u16>| {
+            (0..1000).find_map(|_| {
+                let candidate = Position { x: rng.gen_range(columns.clone()), y: rng.gen_range(2..height - 2) };
+                let in_spawn_row = spawns.iter().any(|(start, _)| candidate.y.abs_diff(start.y) <= 1);
+                (!in_spawn_row && !walls.contains(&candidate)).then_some(candidate)
+            })
+        };
+        let third = (width / 3).max(3);
+        match (pick(2..third), pick(width - third..width - 2)) {
+            (Some(one), Some(two)) => vec![(one, two)],
+            _ => Vec::new(),
+        }
+    }
+
+    // Moves the game forward one tick
+    // Design Decision: Computer-controlled snakes pick their move first, under the same rules as players
+    fn update(&mut self) {
+        for (index, bot) in self.bots.iter_mut().enumerate() {
+            if let Some((_, bot)) = bot {
+                let direction = bot.next_move(&self.state, index);
+                if self.state.snakes[index].steer(direction) {
+                    self.events.publish(&GameEvent::DirectionChanged { player: index, direction });
+                }
             }
-            execute!(stdout, MoveTo(WIDTH-1, y), Print("#"))?;
-        }
-
-        // Draw bottom border
-        for x in 0..WIDTH {
-            execute!(stdout, MoveTo(x, HEIGHT-1), Print("#"))?;
-        }
-
-        // Draw UI elements (score and controls)
-        execute!(
-            stdout,
-            MoveTo(0, HEIGHT),
-            Print(format!("Score: {}", self.score)),
-            MoveTo(0, HEIGHT+1),
-            Print("Use arrow keys to move, 'q' to quit")
-        )?;
-        
-        stdout.flush()?;
-        Ok(())
-    }
-
-    // Main game loop
-    // Design Decision: Using Result for error handling
-    pub fn run(&mut self) -> std::io::Result<()> {
-        // Set up terminal for game display
-        terminal::enable_raw_mode()?;
-        execute!(stdout(), Hide)?;
-
-        self.draw()?;
-
-        while !self.game_over {
-            // Input handling with non-blocking poll
-            // Design Decision: 50ms poll rate for responsive controls
-            if let Ok(true) = event::poll(Duration::from_millis(50)) {
-                if let Ok(Event::Key(key_event)) = event::read() {
-                    match key_event.code {
-                        // Prevent 180° turns by checking opposite direction
-                        KeyCode::Left if self.direction != Direction::Right => {
-                            self.next_direction = Direction::Left;
-                        },
-                        KeyCode::Right if self.direction != Direction::Left => {
-                            self.next_direction = Direction::Right;
-                        },
-                        KeyCode::Up if self.direction != Direction::Down => {
-                            self.next_direction = Direction::Up;
-                        },
-                        KeyCode::Down if self.direction != Direction::Up => {
-                            self.next_direction = Direction::Down;
-                        },
-                        KeyCode::Char('q') => self.game_over = true,
-                        _ => {}
+        }
+        if let Some(left) = &mut self.slow_motion {
+            *left = left.saturating_sub(1);
+        }
+        self.popups.tick();
+        let elapsed = self.tick_interval();
+        self.state.update(&self.effects, elapsed);
+        self.played += elapsed;
+        // Time attack rounds end when the clock runs out, crash or not
+        if self.time_is_up() {
+            self.state.end();
+        }
+        self.effects.tick(elapsed);
+        self.longest = self.longest.max(self.state.snake(0).body.len());
+        self.handle_events();
+    }
+
+    // Reacts to everything the state reported, then passes each event on to subscribers
+    // Design Decision: Player one's progress goes out first, so whoever counts a game when it
+    // ends has already heard how the last tick left it
+    fn handle_events(&mut self) {
+        let snake = self.state.snake(0);
+        self.events.publish(&GameEvent::Ticked {
+            played: self.played,
+            eaten: snake.eaten,
+            longest: self.longest,
+            fill: snake.body.len() as f64 / self.state.floor_cells() as f64,
+        });
+        for event in self.state.take_events() {
+            match event {
+                GameEvent::PowerUpCollected { player, power } => self.effects.add(player, power),
+                // A local player's close call at speed plays out in slow motion
+                GameEvent::NearMiss { player } if player < self.local_players && self.tick_interval() <= SLOW_MOTION_PACE => {
+                    if let Some(left) = &mut self.slow_motion {
+                        *left = SLOW_MOTION_TICKS;
                     }
                 }
+                // The pace tightens with every food, down to the configured floor, and the points
+                // float up from where it was eaten
+                GameEvent::FoodEaten { player, kind, points } if kind != FoodKind::Poison => {
+                    let floor = self.settings.min_tick_interval;
+                    self.tick_interval = self.settings.difficulty.speed_up(self.tick_interval, 1, floor);
+                    if points > 0 {
+                        self.popups.add(self.state.snake(player).head(), points);
+                    }
+                }
+                _ => {}
+            }
+            self.events.publish(&event);
+        }
+    }
+
+    // Whether a cell belongs to a snake passing through things under the ghost power-up
+    pub fn is_phasing(&self, pos: Position) -> bool {
+        self.state.snakes
+            .iter()
+            .enumerate()
+            .any(|(index, snake)| snake.occupies(&pos) && self.effects.is_active(index, PowerUp::Phase))
+    }
+
+    // Score line shown under the board
+    pub fn status_line(&self) -> String {
+        let (snakes, text) = (&self.state.snakes, lang::text());
+        let mut status = if self.settings.mode == Mode::Coop {
+            format!("{}: {}", text.team_score, self.state.score())
+        } else if snakes.len() > 1 {
+            format!("P1: {}  {}: {}", snakes[0].score, self.label(1), snakes[1].score)
+        } else {
+            format!("{}: {}", text.score, self.state.score())
+        };
+        if let Some(ghost) = &self.ghost {
+            status.push_str(&format!("  {}: {}", text.ghost, ghost.score()));
+        }
+        if let Some(campaign) = &self.campaign {
+            let stage = campaign.stage();
+            status.push_str(&format!("  {}", (text.level)(campaign.stage_number(), stage.name)));
+            if let Some(target) = stage.target_score {
+                status.push_str(&format!(" {}", (text.next_at)(target)));
+            }
+        }
+        if let Some(left) = self.time_left() {
+            let seconds = left.as_nanos().div_ceil(1_000_000_000);
+            status.push_str(&format!("  {}: {}:{:02}", text.time, seconds / 60, seconds % 60));
+        }
+        if let Some(left) = self.state.next_shrink() {
+            let seconds = left.as_nanos().div_ceil(1_000_000_000) as u64;
+            let closing = if self.settings.mode == Mode::Royale { text.zone_closes } else { text.walls_close };
+            status.push_str(&format!("  {}", closing(seconds)));
+        }
+        if self.pace_adjustable() {
+            status.push_str(&format!("  {}: x{}", text.speed, self.pace));
+        }
+        let effects = self.effects.describe();
+        if !effects.is_empty() {
+            status.push_str(&format!("  {effects}"));
+        }
+        status
+    }
+
+    // Short name for a snake in the HUD
+    fn label(&self, index: usize) -> &'static str {
+        match index {
+            _ if self.is_bot(index) => "AI",
+            0 => "P1",
+            _ => "P2",
+        }
+    }
+
+    // Who won a finished versus round: None for a draw, and for solo and co-op games
+    pub fn winner(&self) -> Option<usize> {
+        let snakes = &self.state.snakes;
+        if self.settings.mode == Mode::Coop || snakes.len() < 2 {
+            return None;
+        }
+        if let Some(player) = self.forfeited {
+            return Some(1 - player);
+        }
+        let (one, two) = (&snakes[0], &snakes[1]);
+        match (one.alive, two.alive) {
+            (true, false) => Some(0),
+            (false, true) => Some(1),
+            // Both survived the clock, so the points decide
+            (true, true) if self.time_is_up() && one.score != two.score => Some(if one.score > two.score { 0 } else { 1 }),
+            _ => None,
+        }
+    }
+
+    // Summary printed once the round is over
+    pub fn final_message(&self) -> String {
+        let (snakes, text) = (&self.state.snakes, lang::text());
+        let ending = if self.time_is_up() { text.times_up } else { text.game_over };
+        if self.settings.mode == Mode::Coop {
+            format!("{ending} {}: {}", text.team_score, self.state.score())
+        } else if snakes.len() > 1 {
+            let (one, two) = (&snakes[0], &snakes[1]);
+            let verdict = self.winner().map_or(text.draw.to_string(), |winner| (text.wins)(self.label(winner)));
+            format!("{ending} P1: {}  {}: {}  {}", one.score, self.label(1), two.score, verdict)
+        } else {
+            format!("{ending} {}: {}", text.final_score, self.state.score())
+        }
+    }
+
+    // The finished board as plain text for sharing: a banner with the result and the board's
+    // settings, then the board with each snake drawn as a line along its path
+    // Design Decision: Lines show how a snake coiled, which its one-glyph body on screen can't;
+    // everything else keeps the glyphs the game draws when there's no color
+    pub fn board_text(&self) -> String {
+        let (width, height) = (self.state.width(), self.state.height());
+        let mut rows: Vec<Vec<char>> =
+            (0..height).map(|y| (0..width).map(|x| self.state.glyph_at(Position { x, y })).collect()).collect();
+        // The segment before and after each one (across a wrapped border too, not through a portal)
+        let toward = |from: Position, to: Option<&Position>| {
+            let to = to?;
+            match ((to.x + width - from.x) % width, (to.y + height - from.y) % height) {
+                (1, 0) => Some(Direction::Right),
+                (0, 1) => Some(Direction::Down),
+                (dx, 0) if dx == width - 1 => Some(Direction::Left),
+                (0, dy) if dy == height - 1 => Some(Direction::Up),
+                _ => None,
+            }
+        };
+        for (player, snake) in self.state.snakes.iter().enumerate() {
+            let body: Vec<Position> = snake.body.iter().copied().collect();
+            for (index, &segment) in body.iter().enumerate().skip(1) {
+                let links = [toward(segment, body.get(index - 1)), toward(segment, body.get(index + 1))];
+                if let Some(glyph) = path_glyph(links, player == 1) {
+                    rows[usize::from(segment.y)][usize::from(segment.x)] = glyph;
+                }
             }
+        }
+        // Heads go on last, so a crash shows where it happened
+        for (player, snake) in self.state.snakes.iter().enumerate() {
+            let head = snake.head();
+            rows[usize::from(head.y)][usize::from(head.x)] = PLAYER_GLYPHS[player].0;
+        }
+        let mut text = format!("snekrs: {}\n{}\n\n", self.final_message(), self.board_settings());
+        for row in rows {
+            text.extend(row);
+            text.push('\n');
+        }
+        text
+    }
+
+    // Holds the snakes still for a 3-2-1 countdown, so a game never starts moving the
+    // instant it appears
+    pub fn start_countdown(&mut self) {
+        self.countdown = Some((Instant::now() + COUNTDOWN, COUNTDOWN.as_secs()));
+    }
+
+    // Wall-clock time until the next tick is due, or None while nothing runs by itself
+    // (paused, counting down or holding a crash)
+    pub fn next_tick_in(&self) -> Option<Duration> {
+        let waiting = self.paused || self.countdown.is_some() || self.crashed();
+        (!waiting).then(|| self.frame_interval().saturating_sub(self.last_update.elapsed()))
+    }
 
-            // Game state update at fixed time intervals
-            // Design Decision: 100ms update rate for smooth movement
-            if self.last_update.elapsed() >= Duration::from_millis(100) {
-                self.update();
-                self.draw()?;
+    // Starts the wait for the next tick over, as after a pause the clock shouldn't count
+    pub fn restart_clock(&mut self) {
+        self.last_update = Instant::now();
+    }
+
+    // Advances the simulation once the tick interval has elapsed
+    // Design Decision: Returns whether anything changed (a tick ran or the countdown moved on)
+    // so callers know when to redraw
+    pub fn tick_if_due(&mut self) -> bool {
+        if self.paused || self.crashed() {
+            return false;
+        }
+        if let Some((ends, shown)) = self.countdown {
+            let remaining = ends.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                self.countdown = None;
                 self.last_update = Instant::now();
+                return true;
+            }
+            let number = remaining.as_nanos().div_ceil(1_000_000_000) as u64;
+            self.countdown = Some((ends, number));
+            return number != shown;
+        }
+
+        // Interval lives on Game so campaign stages can change the pace
+        let since = self.last_update.elapsed();
+        if since < self.frame_interval() {
+            return false;
+        }
+        // How far behind schedule each tick runs shows up stalls in the loop or the terminal
+        if log::enabled() {
+            let late = since - self.frame_interval();
+            log::record("tick", &[("n", &self.ticks), ("late_ms", &format!("{:.1}", late.as_secs_f64() * 1000.0))]);
+        }
+        self.tick();
+        self.last_update = Instant::now();
+        true
+    }
+
+    // Runs exactly one simulation tick
+    pub fn tick(&mut self) {
+        let running = !self.state.is_over();
+        self.remember();
+        self.update();
+        // A practice run's crash holds on screen instead of ending the game, for whoever is at
+        // the keyboard to rewind (demos and replays just end)
+        let crashed = running && self.state.is_over() && !self.time_is_up() && self.local_players > 0;
+        if let Some(rewind) = self.rewind.as_mut().filter(|_| crashed) {
+            rewind.crashed = true;
+        }
+        self.check_stage_progress();
+        if let Some(ghost) = &mut self.ghost {
+            ghost.tick();
+        }
+        self.ticks += 1;
+        self.emit_state();
+    }
+
+    // Runs one simulation tick with none of the extras a game on screen has, for games raced
+    // in the background
+    pub fn tick_quietly(&mut self) {
+        self.update();
+        self.check_stage_progress();
+        self.ticks += 1;
+        self.emit_state();
+    }
+
+    // Goes back REWIND_TICKS ticks (or as far as the history reaches) in practice mode, then
+    // counts down again so the player can pick a new line before anything moves; returns
+    // whether there was anything to go back to
+    pub fn step_back(&mut self) -> bool {
+        let Some(rewind) = &mut self.rewind else {
+            return false;
+        };
+        let keep = rewind.history.len().saturating_sub(REWIND_TICKS);
+        let Some(snapshot) = rewind.history.drain(keep..).next() else {
+            return false;
+        };
+        rewind.crashed = false;
+        self.restore(snapshot);
+        self.paused = false;
+        self.start_countdown();
+        true
+    }
+
+    // Keeps the state as it is before a tick, in practice mode, so the tick can be undone
+    fn remember(&mut self) {
+        if self.rewind.is_none() || self.local_players == 0 {
+            return;
+        }
+        let snapshot = self.snapshot();
+        if let Some(rewind) = &mut self.rewind {
+            if rewind.history.len() == REWIND_HISTORY {
+                rewind.history.pop_front();
             }
+            rewind.history.push_back(snapshot);
         }
+    }
+
+    // Everything a tick changes, as it stands now
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            state: self.state.clone(),
+            ticks: self.ticks,
+            played: self.played,
+            longest: self.longest,
+            inputs: self.inputs.count(),
+            tick_interval: self.tick_interval,
+            pace: self.pace,
+            effects: self.effects.clone(),
+            campaign: self.campaign.clone(),
+        }
+    }
 
-        // Clean up terminal state
-        terminal::disable_raw_mode()?;
-        execute!(stdout(), Show)?;
-        println!("\nGame Over! Final score: {}", self.score);
-        Ok(())
+    // Puts the game back as it was when the snapshot was taken
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.state = snapshot.state;
+        self.ticks = snapshot.ticks;
+        self.played = snapshot.played;
+        self.longest = snapshot.longest;
+        self.inputs.truncate(snapshot.inputs);
+        self.tick_interval = snapshot.tick_interval;
+        self.pace = snapshot.pace;
+        self.effects = snapshot.effects;
+        self.campaign = snapshot.campaign;
     }
+
+    // Saves the game as it stands, to come back to with rollback()
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.snapshot())
+    }
+
+    // Goes back to a checkpoint, dropping every tick and input since
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.restore(checkpoint.0);
+    }
+
+    // Enters the result in the local score tables, returning its rank in the high score table
+    // and whether it's a new best for the day's daily challenge
+    // Design Decision: Only solo human games enter the table, so bots and shared scores can't crowd
+    // it; neither do practice runs, whose crashes could be rewound
+    pub fn record_score(&self, scores: &mut HighScores) -> (Option<usize>, bool) {
+        let solo = self.state.snakes.len() == 1 && !self.is_bot(0) && self.rewind.is_none();
+        let score = self.state.score();
+        // Daily challenges only count towards that day's result
+        match &self.daily {
+            Some(date) if solo => (None, scores.add_daily(date, score, self.board_settings())),
+            _ if solo => (scores.add(score, self.board_settings()), false),
+            _ => (None, false),
+        }
+    }
+
+    // Date of the daily challenge this game is, if it is one
+    pub fn daily(&self) -> Option<&str> {
+        self.daily.as_deref()
+    }
+
+    // Short description of the board for the high score tables
+    pub fn board_settings(&self) -> String {
+        let mut settings = self.board.name().to_string();
+        // Boards sized from the terminal differ from player to player, so the size is noted
+        if matches!(self.board, Board::Classic | Board::Maze) {
+            settings.push_str(&format!(" {}x{}", self.settings.width, self.settings.height));
+        }
+        if self.settings.difficulty != difficulty::Difficulty::Normal {
+            settings.push_str(&format!(", {}", self.settings.difficulty.name()));
+        }
+        if self.settings.mode != Mode::Standard {
+            settings.push_str(&format!(", {}", self.settings.mode.name()));
+        }
+        for game_mod in &self.settings.mods {
+            settings.push_str(&format!(", {}", game_mod.name()));
+        }
+        format!("{settings}, seed {}", self.seed)
+    }
+}
+
+// Line through a snake segment joining the segments on either side, heavy for player two
+// (None when neither side is next to it, as when the snake went through a portal)
+fn path_glyph(links: [Option<Direction>; 2], heavy: bool) -> Option<char> {
+    let has = |direction| links.contains(&Some(direction));
+    let shape = match (has(Direction::Up), has(Direction::Down), has(Direction::Left), has(Direction::Right)) {
+        (true, true, _, _) | (true, false, false, false) | (false, true, false, false) => 0,
+        (_, _, true, true) | (false, false, true, false) | (false, false, false, true) => 1,
+        (true, _, _, true) => 2,
+        (true, _, true, _) => 3,
+        (_, true, _, true) => 4,
+        (_, true, true, _) => 5,
+        _ => return None,
+    };
+    let lines = if heavy { "┃━┗┛┏┓" } else { "│─└┘┌┐" };
+    lines.chars().nth(shape)
 }
@@ -0,0 +1,65 @@
+// Gamepad input through the Linux joystick interface (/dev/input/js*), on Linux only
+// Design Decision: The kernel's joystick devices speak a fixed 8-byte event format, so a
+// std-only reader covers Xbox and PlayStation pads without a gamepad crate. gilrs would reach
+// macOS and Windows too, but it needs libudev to build on Linux; until then this module is only
+// compiled there, and everywhere else the game is keyboard-only
+use crate::game::Direction;
+use std::fs::File;
+use std::io::Read;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+// Devices tried, first one found wins
+const DEVICES: [&str; 4] = ["/dev/input/js0", "/dev/input/js1", "/dev/input/js2", "/dev/input/js3"];
+
+// js_event type bits
+const EVENT_AXIS: u8 = 0x02;
+const EVENT_INIT: u8 = 0x80;  // Synthetic events describing the initial state
+
+// Axis numbers for the left stick and the d-pad (which the kernel reports as a hat axis)
+const STICK_X: u8 = 0;
+const STICK_Y: u8 = 1;
+const DPAD_X: u8 = 6;
+const DPAD_Y: u8 = 7;
+
+// How far the stick must be pushed before it counts as a direction
+const DEADZONE: i16 = i16::MAX / 2;
+
+// Opens the first connected gamepad and streams its directions from a background thread
+// Design Decision: A reader thread feeding a channel, like the network inbox, so polling for
+// input never blocks the game loop
+pub(crate) fn connect() -> Option<Receiver<Direction>> {
+    let mut device = DEVICES.iter().find_map(|path| File::open(path).ok())?;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut event = [0u8; 8];
+        let mut held: Option<Direction> = None;
+        while device.read_exact(&mut event).is_ok() {
+            // struct js_event { u32 time; i16 value; u8 type; u8 number; }
+            let value = i16::from_le_bytes([event[4], event[5]]);
+            let (kind, number) = (event[6], event[7]);
+            if kind & EVENT_INIT != 0 || kind & EVENT_AXIS == 0 {
+                continue;
+            }
+
+            let pushed = value.unsigned_abs() > DEADZONE.unsigned_abs();
+            let direction = match number {
+                STICK_X | DPAD_X => pushed.then_some(if value < 0 { Direction::Left } else { Direction::Right }),
+                STICK_Y | DPAD_Y => pushed.then_some(if value < 0 { Direction::Up } else { Direction::Down }),
+                _ => continue,  // Right stick and triggers
+            };
+
+            // Only send fresh presses, so holding the stick doesn't flood the channel
+            if direction == held {
+                continue;
+            }
+            held = direction;
+            if let Some(direction) = direction {
+                if sender.send(direction).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    Some(receiver)
+}
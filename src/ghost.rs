@@ -0,0 +1,106 @@
+// Ghost of the player's personal best run
+// Design Decision: The best solo run on each board and seed is kept as a replay, and the
+// ghost is a second game re-simulated in lockstep with the live one, so it costs no extra
+// file format and stays exactly in sync with what the player saw back then
+use crate::config::GameConfig;
+use crate::difficulty::Difficulty;
+use crate::mode::Mode;
+use crate::game::{Board, Game, Position};
+use crate::paths;
+use crate::replay::{Input, Replay};
+use std::path::PathBuf;
+
+// Drawn where the ghost snake passes over empty floor
+pub const GHOST_GLYPH: char = '░';
+
+// A previous run replaying alongside the live game
+pub struct Ghost {
+    game: Game,          // Re-simulation of the recorded run
+    inputs: Vec<Input>,  // Recorded steering, applied as the ghost reaches each tick
+    next: usize,         // Index of the next input to apply
+}
+
+impl Ghost {
+    // Rebuilds the recorded run from its replay
+    fn new(replay: Replay) -> Ghost {
+        let mut game = Game::new(replay.board, replay.players, replay.seed, replay.settings);
+        game.set_local_players(0);
+        Ghost { game, inputs: replay.inputs, next: 0 }
+    }
+
+    // Advances the ghost by one tick, feeding in the inputs recorded for it
+    pub fn tick(&mut self) {
+        if self.game.is_over() {
+            return;
+        }
+        while let Some(input) = self.inputs.get(self.next).filter(|input| input.tick <= self.game.ticks()) {
+            self.game.apply(input.change);
+            self.next += 1;
+        }
+        self.game.tick_quietly();
+    }
+
+    // Whether the ghost snake covers a cell on the given campaign stage
+    // Design Decision: The ghost fades out once it crashes or is on another stage's board
+    pub fn occupies(&self, pos: Position, stage: usize) -> bool {
+        !self.game.is_over() && self.game.stage() == stage && self.game.state().snake(0).occupies(&pos)
+    }
+
+    // Length, head and tail of the ghost snake while it's shown on the given stage
+    pub fn ends(&self, stage: usize) -> Option<(usize, Position, Position)> {
+        let body = &self.game.state().snake(0).body;
+        let shown = !self.game.is_over() && self.game.stage() == stage;
+        Some((body.len(), *body.front()?, *body.back()?)).filter(|_| shown)
+    }
+
+    // Score the ghost has reached so far
+    pub fn score(&self) -> u32 {
+        self.game.state().score()
+    }
+}
+
+// Loads the personal best for a board and seed, if one has been recorded
+pub fn load(board: &Board, seed: u64, settings: &GameConfig) -> Option<Ghost> {
+    Replay::load(&best_path(board, seed, settings)).ok().map(Ghost::new)
+}
+
+// Keeps a finished solo run as the new personal best when it beats the old one,
+// returning whether it did
+pub fn record_if_best(replay: &Replay) -> std::io::Result<bool> {
+    if replay.players != 1 || !replay.bots.is_empty() || replay.score == 0 {
+        return Ok(false);
+    }
+    let path = best_path(&replay.board, replay.seed, &replay.settings);
+    if Replay::load(&path).is_ok_and(|best| best.score >= replay.score) {
+        return Ok(false);
+    }
+    replay.save(&path)?;
+    Ok(true)
+}
+
+// Where the best run for a board and seed is stored
+// Design Decision: Generated boards include their size, since one seed makes a different board per size,
+// and other difficulties, modes and mods than the defaults get their own bests since they score differently
+fn best_path(board: &Board, seed: u64, settings: &GameConfig) -> PathBuf {
+    let mut board = match board {
+        Board::Classic => format!("classic-{}x{}", settings.width, settings.height),
+        Board::Maze => format!("maze-{}x{}", settings.width, settings.height),
+        Board::Campaign => "campaign".to_string(),
+        Board::Custom(level) => format!("level-{:016x}", fingerprint(&level.to_grid())),
+    };
+    if settings.difficulty != Difficulty::Normal {
+        board.push_str(&format!("-{}", settings.difficulty.name()));
+    }
+    if settings.mode != Mode::Standard {
+        board.push_str(&format!("-{}", settings.mode.name()));
+    }
+    for game_mod in &settings.mods {
+        board.push_str(&format!("-{}", game_mod.name()));
+    }
+    paths::data_dir().join("ghosts").join(format!("{board}-{seed}.replay"))
+}
+
+// FNV-1a hash, stable across builds so custom levels keep their ghosts
+fn fingerprint(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
@@ -0,0 +1,214 @@
+// Animated GIFs of replays, in builds with `--features gif`
+// Design Decision: The gif crate does the file format and its LZW compression; this module only
+// turns boards into palette indices and works out which part of each frame changed. It sits
+// behind a feature, so the default build doesn't carry a dependency most players never use
+//
+// Every board cell becomes a CELL_PIXELS square in the color the pixel renderers give it, and
+// every tick a frame, shown for as long as the tick lasted at the chosen playback speed
+use crate::frame::Frame;
+use crate::headless;
+use crate::keymap::Keymap;
+use crate::play::Play;
+use crate::replay::Replay;
+use crate::theme::{self, Theme};
+use gif::{DisposalMethod, Repeat};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+// Width and height of a board cell, in pixels
+const CELL_PIXELS: u16 = 8;
+
+// Empty floor, where a terminal would show its own background
+const BACKGROUND: [u8; 3] = [0, 0, 0];
+
+// Shortest frame delay viewers honor, in hundredths of a second; ticks shorter than that are
+// folded into the next frame instead of being slowed down
+const MIN_DELAY: u16 = 2;
+
+// How long the final board stays up before the GIF loops, in hundredths of a second
+const END_DELAY: u16 = 300;
+
+// Writes a replay as an animated GIF, returning how many frames it took
+// Design Decision: The replay is played twice, first to find every color it shows (a GIF has
+// one palette of at most 256) and then to draw it; replays always play out the same, and two
+// quick headless runs beat holding every frame in memory
+pub fn export(replay: &Replay, theme: &Theme, speed: f64, path: &Path) -> io::Result<usize> {
+    // Item animations follow the wall clock, which a GIF doesn't have, so everything holds still
+    let theme = Theme { steady: true, ..theme.clone() };
+    let mut palette = Palette::default();
+    play(replay, &theme, speed, |frame, _| palette.scan(frame, &theme));
+
+    let (width, height) = {
+        let game = headless::start(replay);
+        (game.state().width() * CELL_PIXELS, game.state().height() * CELL_PIXELS)
+    };
+    let mut encoder = Encoder::new(BufWriter::new(File::create(path)?), width, height, palette)?;
+    let mut pending = None;  // Last board drawn, until the next one says how long it stays up
+    let mut written = Ok(());  // First failure while playing, which stops the writing
+    play(replay, &theme, speed, |frame, start| {
+        // A board that would be up for less than MIN_DELAY is skipped, its time going to the next
+        if let Some(pixels) = pending.take() {
+            let delay = encoder.delay_until(start);
+            if delay >= MIN_DELAY && written.is_ok() {
+                written = encoder.add_frame(pixels, delay);
+            }
+        }
+        pending = Some(encoder.raster(frame, &theme));
+    });
+    written?;
+    if let Some(pixels) = pending {
+        encoder.add_frame(pixels, END_DELAY)?;
+    }
+    let frames = encoder.frames;
+    encoder.finish()?;
+    Ok(frames)
+}
+
+// Plays a replay without a terminal, handing over each board it shows along with the time, in
+// seconds of playback, it appears
+fn play(replay: &Replay, theme: &Theme, speed: f64, mut show: impl FnMut(&Frame, f64)) {
+    let mut game = Play::new(headless::start(replay));
+    game.set_preferences(Keymap::default(), theme.clone());
+    let mut inputs = replay.inputs.iter().peekable();
+    let mut clock = 0.0;
+    loop {
+        show(&game.frame(false), clock);
+        if game.is_over() || game.ticks() >= replay.ticks {
+            return;
+        }
+        while let Some(input) = inputs.next_if(|input| input.tick <= game.ticks()) {
+            game.apply(input.change);
+        }
+        clock += game.tick_interval().as_secs_f64() / speed;
+        game.tick_quietly();
+    }
+}
+
+// The colors a GIF can show, by index
+#[derive(Default)]
+struct Palette {
+    colors: Vec<[u8; 3]>,
+    indices: HashMap<[u8; 3], u8>,
+}
+
+impl Palette {
+    // Adds every color on a board not seen yet, up to the 256 a GIF allows
+    fn scan(&mut self, frame: &Frame, theme: &Theme) {
+        if self.colors.is_empty() {
+            self.add(BACKGROUND);
+        }
+        for y in 0..frame.height() {
+            for x in 0..frame.width() {
+                self.add(cell_color(frame, x, y, theme));
+            }
+        }
+    }
+
+    // Adds one color, unless it's known or the palette is full
+    fn add(&mut self, color: [u8; 3]) {
+        if self.colors.len() < 256 && !self.indices.contains_key(&color) {
+            self.indices.insert(color, self.colors.len() as u8);
+            self.colors.push(color);
+        }
+    }
+
+    // Index of a color, or of the nearest one when the palette ran out of room for it
+    fn index(&self, color: [u8; 3]) -> u8 {
+        self.indices.get(&color).copied().unwrap_or_else(|| {
+            let distance = |other: &[u8; 3]| (0..3).map(|at| (i32::from(color[at]) - i32::from(other[at])).pow(2)).sum::<i32>();
+            self.colors.iter().enumerate().min_by_key(|(_, other)| distance(other)).map_or(0, |(index, _)| index as u8)
+        })
+    }
+
+    // The colors as the global color table: RGB triples, in index order
+    fn table(&self) -> Vec<u8> {
+        self.colors.concat()
+    }
+}
+
+// A GIF being written, one frame at a time
+struct Encoder {
+    out: gif::Encoder<BufWriter<File>>,
+    width: u16,
+    palette: Palette,
+    shown: Vec<u8>,      // Palette index of every pixel as the last frame left the picture
+    written: u64,        // Playback time covered by the frames so far, in hundredths of a second
+    frames: usize,
+}
+
+impl Encoder {
+    // Starts a looping GIF of the given size, with the palette as its global color table
+    fn new(file: BufWriter<File>, width: u16, height: u16, palette: Palette) -> io::Result<Encoder> {
+        let mut out = gif::Encoder::new(file, width, height, &palette.table()).map_err(io::Error::other)?;
+        out.set_repeat(Repeat::Infinite).map_err(io::Error::other)?;
+        let shown = vec![0; usize::from(width) * usize::from(height)];
+        Ok(Encoder { out, width, palette, shown, written: 0, frames: 0 })
+    }
+
+    // Hundredths of a second from the end of the frames written so far to `seconds` into playback
+    fn delay_until(&self, seconds: f64) -> u16 {
+        ((seconds * 100.0).round() as u64).saturating_sub(self.written).min(u64::from(u16::MAX)) as u16
+    }
+
+    // A board as palette indices, one CELL_PIXELS square per cell
+    fn raster(&self, frame: &Frame, theme: &Theme) -> Vec<u8> {
+        let (width, cell) = (usize::from(self.width), usize::from(CELL_PIXELS));
+        let mut pixels = vec![0; self.shown.len()];
+        for y in 0..frame.height() {
+            for x in 0..frame.width() {
+                let index = self.palette.index(cell_color(frame, x, y, theme));
+                let (left, top) = (usize::from(x) * cell, usize::from(y) * cell);
+                for row in top..top + cell {
+                    pixels[row * width + left..row * width + left + cell].fill(index);
+                }
+            }
+        }
+        pixels
+    }
+
+    // Adds a frame shown for `delay` hundredths of a second
+    // Design Decision: Only the rectangle that changed since the last frame is stored, drawn over
+    // the picture so far, which keeps a long replay's GIF small
+    fn add_frame(&mut self, pixels: Vec<u8>, delay: u16) -> io::Result<()> {
+        let width = usize::from(self.width);
+        let changed: Vec<usize> = (0..pixels.len()).filter(|&at| pixels[at] != self.shown[at] || self.frames == 0).collect();
+        let (mut left, mut top, mut right, mut bottom) = (0, 0, 0, 0);
+        if let (Some(&first), Some(&last)) = (changed.first(), changed.last()) {
+            (top, bottom) = (first / width, last / width);
+            left = changed.iter().map(|at| at % width).min().unwrap_or(0);
+            right = changed.iter().map(|at| at % width).max().unwrap_or(0);
+        }
+        let area: Vec<u8> = (top..=bottom).flat_map(|y| pixels[y * width + left..=y * width + right].iter().copied()).collect();
+        // Each frame is kept under the next one, which only covers what changed
+        let frame = gif::Frame {
+            delay,
+            dispose: DisposalMethod::Keep,
+            left: left as u16,
+            top: top as u16,
+            width: (right - left + 1) as u16,
+            height: (bottom - top + 1) as u16,
+            buffer: Cow::Owned(area),
+            ..gif::Frame::default()
+        };
+        self.out.write_frame(&frame).map_err(io::Error::other)?;
+        self.shown = pixels;
+        self.written += u64::from(delay);
+        self.frames += 1;
+        Ok(())
+    }
+
+    // Ends the file and writes out whatever is still buffered
+    fn finish(self) -> io::Result<()> {
+        let mut file = self.out.into_inner()?;
+        io::Write::flush(&mut file)
+    }
+}
+
+// Color of a board cell as a pixel
+fn cell_color(frame: &Frame, x: u16, y: u16, theme: &Theme) -> [u8; 3] {
+    let (glyph, _) = frame.get(x, y);
+    theme.pixel(glyph, frame.tone(x, y)).map_or(BACKGROUND, theme::rgb)
+}
@@ -0,0 +1,37 @@
+// Games simulated without a terminal, for testing and bot experiments
+// Design Decision: A headless game is the normal engine ticked as fast as it will go with no
+// drawing at all; bots steer whichever snakes were handed to them and a script of recorded
+// inputs steers the rest, so the same run plays out exactly as it would on screen
+use crate::game::Game;
+use crate::replay::{Input, Replay};
+
+// Ticks a headless game gives up after, so a game nobody can lose (zen mode, say) still ends
+pub const MAX_TICKS: u64 = 100_000;
+
+// Plays a game until it's over or has run `max_ticks` ticks, applying each scripted input
+// (oldest first) before the tick it was recorded at
+pub fn run(game: &mut Game, script: &[Input], max_ticks: u64) {
+    let mut script = script.iter().peekable();
+    while !game.is_over() && game.ticks() < max_ticks {
+        while let Some(input) = script.next_if(|input| input.tick <= game.ticks()) {
+            game.apply(input.change);
+        }
+        game.tick_quietly();
+    }
+}
+
+// A recorded game set up as it was at the start, with its bots, ready for `run`
+pub fn start(replay: &Replay) -> Game {
+    let mut game = Game::new(replay.board.clone(), replay.players, replay.seed, replay.settings.clone());
+    for &(player, difficulty) in &replay.bots {
+        game.hand_to_ai(player, difficulty);
+    }
+    game
+}
+
+// Re-simulates a recorded game to where its recording stopped
+pub fn rerun(replay: Replay) -> Game {
+    let mut game = start(&replay);
+    run(&mut game, &replay.inputs, replay.ticks);
+    game
+}
@@ -0,0 +1,61 @@
+// Terminal input, read on a dedicated thread
+// Design Decision: One reader thread owns every crossterm read and feeds a channel, like the
+// gamepad, so key presses queue up while the game is ticking or drawing instead of waiting for
+// the loop to come back around to poll. Every screen reads through here, so none of them races
+// the thread for events
+use crate::log;
+use crossterm::event::{self, Event};
+use std::io;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+// Events from the reader thread, which starts with the first read
+static EVENTS: OnceLock<Mutex<Receiver<io::Result<Event>>>> = OnceLock::new();
+
+// Starts the reader thread, which runs until reading fails or the program exits
+fn spawn() -> Mutex<Receiver<io::Result<Event>>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || loop {
+        let event = event::read();
+        // Logged as it arrives, so a key the game never acted on still shows up
+        if log::enabled() {
+            match &event {
+                Ok(Event::Key(key)) => log::record("input", &[("key", &format!("{:?}", key.code)), ("press", &format!("{:?}", key.kind))]),
+                Ok(other) => log::record("input", &[("event", &format!("{other:?}"))]),
+                Err(err) => log::record("input", &[("error", err)]),
+            }
+        }
+        let failed = event.is_err();
+        if sender.send(event).is_err() || failed {
+            break;
+        }
+    });
+    Mutex::new(receiver)
+}
+
+// Waits up to `timeout` for the next event (None when nothing arrived in time)
+pub fn next(timeout: Duration) -> io::Result<Option<Event>> {
+    let events = EVENTS.get_or_init(spawn).lock().unwrap_or_else(PoisonError::into_inner);
+    match events.recv_timeout(timeout) {
+        Ok(event) => event.map(Some),
+        Err(RecvTimeoutError::Timeout) => Ok(None),
+        Err(RecvTimeoutError::Disconnected) => Err(io::Error::other("terminal input closed")),
+    }
+}
+
+// Blocks until the next event
+pub fn read() -> io::Result<Event> {
+    loop {
+        if let Some(event) = next(Duration::from_secs(60))? {
+            return Ok(event);
+        }
+    }
+}
+
+// Throws away everything pressed so far, so it can't act on whatever is shown next
+pub fn discard() -> io::Result<()> {
+    while next(Duration::ZERO)?.is_some() {}
+    Ok(())
+}
@@ -0,0 +1,253 @@
+// Keymap layer between raw key events and game actions
+// Design Decision: Every input path (local game, network client) asks the keymap what a
+// key means, so rebinding in the config works everywhere and nothing matches KeyCodes directly
+use crate::game::{Direction, Turn};
+use crate::lang;
+use crossterm::event::KeyCode;
+
+// Something a key can do
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Action {
+    Steer(usize, Direction),  // Turn a local player's snake
+    Turn(usize, Turn),        // Rotate a local player's snake relative to its heading
+    Pause,                    // Freeze or unfreeze the game
+    Faster,                   // Shorten the tick interval a notch
+    Slower,                   // Lengthen it a notch
+    Rewind,                   // Step back a few ticks (practice mode only)
+    Chat,                     // Start typing a chat message (network games only)
+    Quit,
+}
+
+impl Action {
+    // Parses a config action name: up/down/left/right or turn_left/turn_right (p2_ prefix for
+    // player two), pause, faster, slower, rewind, chat or quit
+    pub fn parse(name: &str) -> Option<Action> {
+        match name {
+            "pause" => return Some(Action::Pause),
+            "faster" => return Some(Action::Faster),
+            "slower" => return Some(Action::Slower),
+            "rewind" => return Some(Action::Rewind),
+            "chat" => return Some(Action::Chat),
+            "quit" => return Some(Action::Quit),
+            _ => {}
+        }
+        let (player, direction) = match name.strip_prefix("p2_") {
+            Some(direction) => (1, direction),
+            None => (0, name),
+        };
+        let direction = match direction {
+            "turn_left" => return Some(Action::Turn(player, Turn::Left)),
+            "turn_right" => return Some(Action::Turn(player, Turn::Right)),
+            "up" => Direction::Up,
+            "down" => Direction::Down,
+            "left" => Direction::Left,
+            "right" => Direction::Right,
+            _ => return None,
+        };
+        Some(Action::Steer(player, direction))
+    }
+}
+
+// Extra movement keys for player one, for keyboards where the arrows are awkward
+#[derive(Clone, Copy)]
+pub enum Controls {
+    Arrows,  // Arrow keys only
+    Wasd,    // W/A/S/D as well
+    Vim,     // H/J/K/L as well
+    All,     // Both alias sets
+}
+
+impl Controls {
+    // Parses a setting name (arrows, wasd, vim, all)
+    pub fn parse(name: &str) -> Option<Controls> {
+        match name {
+            "arrows" => Some(Controls::Arrows),
+            "wasd" => Some(Controls::Wasd),
+            "vim" | "hjkl" => Some(Controls::Vim),
+            "all" => Some(Controls::All),
+            _ => None,
+        }
+    }
+
+    // Alias keys in up, down, left, right order
+    fn aliases(self) -> Vec<[KeyCode; 4]> {
+        let wasd = [KeyCode::Char('w'), KeyCode::Char('s'), KeyCode::Char('a'), KeyCode::Char('d')];
+        let vim = [KeyCode::Char('k'), KeyCode::Char('j'), KeyCode::Char('h'), KeyCode::Char('l')];
+        match self {
+            Controls::Arrows => vec![],
+            Controls::Wasd => vec![wasd],
+            Controls::Vim => vec![vim],
+            Controls::All => vec![wasd, vim],
+        }
+    }
+}
+
+// How the movement keys steer
+#[derive(Clone, Copy, PartialEq)]
+pub enum Steering {
+    Absolute,  // Each key points the snake one way
+    Relative,  // Left and right rotate the snake from its heading; up and down do nothing
+}
+
+impl Steering {
+    // Parses a setting name (absolute, relative)
+    pub fn parse(name: &str) -> Option<Steering> {
+        match name {
+            "absolute" => Some(Steering::Absolute),
+            "relative" => Some(Steering::Relative),
+            _ => None,
+        }
+    }
+
+    // What a bound action does under this scheme (None = nothing)
+    fn apply(self, action: Action) -> Option<Action> {
+        match (self, action) {
+            (Steering::Relative, Action::Steer(player, Direction::Left)) => Some(Action::Turn(player, Turn::Left)),
+            (Steering::Relative, Action::Steer(player, Direction::Right)) => Some(Action::Turn(player, Turn::Right)),
+            (Steering::Relative, Action::Steer(..)) => None,
+            _ => Some(action),
+        }
+    }
+}
+
+// Order directions are listed in for hints
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+// Keys bound to each action; an action can have several keys
+#[derive(Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyCode, Action)>,
+    steering: Steering,  // Applied as keys are looked up, so it doesn't matter where it's set
+}
+
+impl Default for Keymap {
+    // Player one on the arrows, player two on WASD, 'p' or Esc to pause, '+' and '-' to change
+    // speed, 'u' to rewind, 't' to chat, 'q' to quit
+    fn default() -> Self {
+        let mut bindings = Vec::new();
+        for (player, keys) in [
+            [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right],
+            [KeyCode::Char('w'), KeyCode::Char('s'), KeyCode::Char('a'), KeyCode::Char('d')],
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            for (key, direction) in keys.into_iter().zip(DIRECTIONS) {
+                bindings.push((key, Action::Steer(player, direction)));
+            }
+        }
+        bindings.push((KeyCode::Char('p'), Action::Pause));
+        bindings.push((KeyCode::Esc, Action::Pause));
+        bindings.push((KeyCode::Char('+'), Action::Faster));
+        bindings.push((KeyCode::Char('='), Action::Faster));  // '+' without shift
+        bindings.push((KeyCode::Char('-'), Action::Slower));
+        bindings.push((KeyCode::Char('u'), Action::Rewind));
+        bindings.push((KeyCode::Char('t'), Action::Chat));
+        bindings.push((KeyCode::Char('q'), Action::Quit));
+        Keymap { bindings, steering: Steering::Absolute }
+    }
+}
+
+impl Keymap {
+    // What a key does with this many local players, if it's bound
+    // Design Decision: Bindings for absent players are skipped, so WASD can steer player one
+    // in a solo game while still belonging to player two when both play
+    pub fn action(&self, code: KeyCode, local_players: usize) -> Option<Action> {
+        self.bindings
+            .iter()
+            .filter(|(_, action)| {
+                !matches!(action, Action::Steer(player, _) | Action::Turn(player, _) if *player >= local_players)
+            })
+            .find(|(key, _)| *key == code)
+            .and_then(|(_, action)| self.steering.apply(*action))
+    }
+
+    // Switches between absolute and relative steering
+    pub fn set_steering(&mut self, steering: Steering) {
+        self.steering = steering;
+    }
+
+    // Adds a control scheme's alias keys for player one, after every existing binding
+    pub fn add_aliases(&mut self, controls: Controls) {
+        for keys in controls.aliases() {
+            for (key, direction) in keys.into_iter().zip(DIRECTIONS) {
+                self.bindings.push((key, Action::Steer(0, direction)));
+            }
+        }
+    }
+
+    // Replaces the keys for an action; a key moved here is taken off whatever it did before
+    pub fn bind(&mut self, action: Action, keys: &[KeyCode]) {
+        self.bindings.retain(|(key, bound)| *bound != action && !keys.contains(key));
+        self.bindings.extend(keys.iter().map(|&key| (key, action)));
+    }
+
+    // Keys bound to an action, in binding order
+    fn keys_for(&self, action: Action) -> impl Iterator<Item = KeyCode> + '_ {
+        self.bindings.iter().filter(move |(_, bound)| *bound == action).map(|(key, _)| *key)
+    }
+
+    // Short description of a player's movement keys for the controls hint
+    pub fn describe(&self, player: usize) -> String {
+        if self.steering == Steering::Relative {
+            let turns: Vec<String> = [(Turn::Left, Direction::Left), (Turn::Right, Direction::Right)]
+                .iter()
+                .map(|&(turn, direction)| {
+                    let turn_keys = self.keys_for(Action::Turn(player, turn));
+                    let mut keys = turn_keys.chain(self.keys_for(Action::Steer(player, direction)));
+                    keys.next().map_or("-".to_string(), key_name)
+                })
+                .collect();
+            return (lang::text().turning_keys)(&turns.join("/"));
+        }
+        let first: Vec<Option<KeyCode>> = DIRECTIONS
+            .iter()
+            .map(|&direction| self.keys_for(Action::Steer(player, direction)).next())
+            .collect();
+        if first == [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right].map(Some) {
+            return lang::text().arrow_keys.to_string();
+        }
+        first.iter().map(|key| key.map_or("-".to_string(), key_name)).collect::<Vec<_>>().join("/")
+    }
+
+    // Name of the first key bound to an action, for hints
+    pub fn key_label(&self, action: Action) -> String {
+        self.keys_for(action).next().map_or("-".to_string(), key_name)
+    }
+}
+
+// Parses a key name: a single character, or Up/Down/Left/Right/Esc/Enter/Space/Tab/F1-F12
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    match name.to_lowercase().as_str() {
+        "up" => Some(KeyCode::Up),
+        "down" => Some(KeyCode::Down),
+        "left" => Some(KeyCode::Left),
+        "right" => Some(KeyCode::Right),
+        "esc" | "escape" => Some(KeyCode::Esc),
+        "enter" | "return" => Some(KeyCode::Enter),
+        "space" => Some(KeyCode::Char(' ')),
+        "tab" => Some(KeyCode::Tab),
+        name => name.strip_prefix('f').and_then(|n| n.parse().ok()).filter(|n| (1..=12).contains(n)).map(KeyCode::F),
+    }
+}
+
+// Display name of a key, the inverse of parse_key
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{n}"),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        other => format!("{other:?}"),
+    }
+}
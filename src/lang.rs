@@ -0,0 +1,648 @@
+// Translations of the text shown while playing: HUD, hints, menus and results
+// Design Decision: Each language is one table of plain strings and small formatting functions,
+// so a contributor adds a language by writing a table (and a name for the config) without
+// touching game logic, and every sentence can put its numbers where that language wants them.
+// Command-line help and error messages stay in English, like the config keys they mention
+use std::cmp::Ordering;
+use std::sync::OnceLock;
+
+// The language picked for this run (unset = taken from the environment on first use)
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+// Languages the game's text is translated into
+#[derive(Clone, Copy, PartialEq)]
+pub enum Language {
+    English,
+    Spanish,
+    German,
+}
+
+impl Language {
+    // Parses a config name (en, es, de)
+    pub fn parse(name: &str) -> Option<Language> {
+        match name {
+            "en" => Some(Language::English),
+            "es" => Some(Language::Spanish),
+            "de" => Some(Language::German),
+            _ => None,
+        }
+    }
+
+    // The language of the user's locale, from LC_ALL, LC_MESSAGES or LANG in that order
+    // (e.g. de_DE.UTF-8), falling back to English for C, POSIX and untranslated languages
+    pub fn from_env() -> Language {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+            .unwrap_or_default();
+        let code = locale.split(['_', '.', '@']).next().unwrap_or_default();
+        Language::parse(code).unwrap_or(Language::English)
+    }
+
+    // Every translated string of this language
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
+            Language::German => &GERMAN,
+        }
+    }
+}
+
+// Picks the language for the rest of the run; calls after the first text was shown are ignored
+pub fn set(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
+
+// The strings of the run's language
+pub fn text() -> &'static Strings {
+    LANGUAGE.get_or_init(Language::from_env).strings()
+}
+
+// Everything the game shows in words, in one language
+// Design Decision: Labels that sit in front of a value ("Score", "Ghost") are plain words the
+// caller puts a colon after; anything longer is a function so word order can change
+pub struct Strings {
+    // In-game HUD and hints
+    pub score: &'static str,
+    pub ghost: &'static str,
+    pub skin: &'static str,
+    pub time: &'static str,
+    pub speed: &'static str,
+    pub level: fn(usize, &str) -> String,
+    pub next_at: fn(u32) -> String,
+    pub zone_closes: fn(u64) -> String,
+    pub walls_close: fn(u64) -> String,
+    pub demo_hint: &'static str,
+    pub resume_hint: fn(&str, &str) -> String,             // Pause key, quit key
+    pub solo_hint: fn(&str, &str, &str) -> String,         // Movement keys, pause key, quit key
+    pub duo_hint: fn(&str, &str, &str, &str) -> String,    // Player one's keys, player two's, pause, quit
+    pub arrow_keys: &'static str,                          // The movement keys when they're the arrows
+    pub turning_keys: fn(&str) -> String,                  // Left and right turn keys, for relative steering
+    pub paused: &'static str,
+    pub rewind_hint: fn(&str) -> String,                   // Rewind key, added to the hint in practice mode
+    pub crashed: &'static str,
+    pub crashed_hint: fn(&str, &str) -> String,            // Rewind key, quit key
+    pub terminal_too_small: &'static str,
+    pub needs_size: fn(u16, u16, u16, u16) -> String,      // Needed width and height, actual ones
+    pub achievement: fn(&str, &str) -> String,             // Name, description
+    pub stage_cleared: fn(u32) -> String,
+    pub get_ready: &'static str,
+    pub play_again_hint: &'static str,                     // In the window's title once a game is over
+
+    // Results
+    pub game_over: &'static str,
+    pub times_up: &'static str,
+    pub final_score: &'static str,
+    pub team_score: &'static str,
+    pub wins: fn(&str) -> String,
+    pub draw: &'static str,
+    pub best_score: &'static str,
+    pub high_scores: &'static str,
+    pub no_scores: &'static str,
+    pub new_entry: &'static str,
+    pub daily_best: fn(&str, u32) -> String,               // Date, best score
+    pub daily_results: &'static str,
+    pub seed_note: fn(u64) -> String,
+    pub challenge_note: fn(&str) -> String,
+    pub challenge_result: fn(u32, u32) -> String,          // This score, the score to beat
+    pub rating_note: fn(i32, i32, &str) -> String,         // New rating, its change, the opponent
+    pub new_best: &'static str,
+    pub replay_saved: fn(&str) -> String,
+    pub cast_saved: fn(&str) -> String,
+    pub hardcore_logged: fn(usize, &str) -> String,        // Run number, where the history is
+    pub score_submitted: fn(&str) -> String,               // Name the score went in under
+    pub global_leaderboard: &'static str,
+    pub stats_not_saved: fn(&str) -> String,               // The error
+    pub run_not_logged: fn(&str) -> String,                // The error
+    pub ratings_not_saved: fn(&str) -> String,             // The error
+    pub best_not_saved: fn(&str) -> String,                // The error
+    pub replay_not_saved: fn(&str, &str) -> String,        // Path, the error
+    pub cast_not_saved: fn(&str, &str) -> String,          // Path, the error
+    pub score_not_submitted: fn(&str) -> String,           // The error
+    pub leaderboard_unavailable: fn(&str) -> String,       // The error
+    pub restart_prompt: &'static str,
+    pub back_prompt: &'static str,
+    pub game_saved: &'static str,
+
+    // Menus
+    pub new_game: &'static str,
+    pub settings: &'static str,
+    pub high_scores_item: &'static str,
+    pub quit: &'static str,
+    pub restart: &'static str,
+    pub title_screen: &'static str,
+    pub export_board: &'static str,
+    pub board_exported: fn(&str) -> String,                // Where the board was written
+    pub board_not_exported: fn(&str, &str) -> String,      // Path, the error
+    pub board_on_exit: &'static str,
+    pub back: &'static str,
+    pub resume: &'static str,
+    pub saved_game: &'static str,
+
+    // Tutorial
+    pub tutorial: &'static str,
+    pub tutorial_move: fn(&str) -> String,                 // Movement keys
+    pub tutorial_food: &'static str,
+    pub tutorial_walls: fn(u64) -> String,                 // Seconds to survive
+    pub tutorial_power: &'static str,
+    pub tutorial_passed: &'static str,
+    pub tutorial_crashed: &'static str,
+    pub tutorial_done: &'static str,
+
+    // Settings screen
+    pub settings_title: &'static str,
+    pub difficulty: &'static str,
+    pub mode: &'static str,
+    pub board: &'static str,
+    pub opponent: &'static str,
+    pub computer: &'static str,
+    pub on: &'static str,
+    pub off: &'static str,
+    pub nobody: &'static str,
+    pub second_player: &'static str,
+    pub volume: &'static str,
+
+    // Replays
+    pub replay_banner: fn(f64) -> String,                  // Playback speed
+    pub replay_finished: &'static str,
+    pub gif_written: fn(usize, &str) -> String,            // Frames, where the GIF went
+
+    // Network play
+    pub hosting: fn(u16) -> String,                        // Port
+    pub joined: fn(&str) -> String,                        // Address of whoever joined
+    pub waiting_for_return: fn(u64) -> String,             // Seconds left for player two to come back
+    pub opponent_disconnected: &'static str,
+    pub waiting_for_host: &'static str,
+    pub client_hint: fn(&str, &str) -> String,             // Movement keys, quit key
+    pub watching_hint: fn(&str) -> String,                 // Quit key
+    pub stopped_watching: &'static str,
+    pub left_game: &'static str,
+    pub reconnecting: &'static str,
+    pub host_lost: &'static str,
+    pub hosting_udp: fn(u16) -> String,                    // Port
+    pub waiting_at: fn(&str) -> String,                    // Host's address
+    pub udp_client_hint: fn(u64, &str, &str) -> String,    // Round trip in ms, movement keys, quit key
+
+    // Chat
+    pub chat_draft: fn(&str) -> String,                    // The message being typed
+    pub chat_key: fn(&str) -> String,                      // Key that starts a message
+
+    // Lobby
+    pub lobby_title: &'static str,
+    pub lobby_empty: &'static str,
+    pub lobby_hint: &'static str,
+    pub lobby_game: fn(&str, &str, &str, bool) -> String,  // Host's name, board, address, whether a seat is free
+
+    // Stats (`snekrs stats`)
+    pub lifetime_stats: fn(u64, u64, u64, usize, f64) -> String,  // Games, food, seconds played, longest snake, average score
+    pub hardcore_runs: fn(usize, u32, u64) -> String,      // Runs, best score, seconds survived in all
+    pub ratings: &'static str,
+    pub rated_matches: fn(u32) -> String,
+    pub achievements: &'static str,
+    pub unlocks_skin: fn(&str) -> String,                  // Skin name
+}
+
+const ENGLISH: Strings = Strings {
+    score: "Score",
+    ghost: "Ghost",
+    skin: "Skin",
+    time: "Time",
+    speed: "Speed",
+    level: |number, name| format!("Level {number}: {name}"),
+    next_at: |target| format!("(next at {target})"),
+    zone_closes: |seconds| format!("Zone closes in {seconds}s"),
+    walls_close: |seconds| format!("Walls close in {seconds}s"),
+    demo_hint: "DEMO - press any key to exit",
+    resume_hint: |pause, quit| format!("'{pause}' to resume, '{quit}' to quit"),
+    solo_hint: |keys, pause, quit| format!("Use {keys} to move, '{pause}' to pause, '{quit}' to quit"),
+    duo_hint: |one, two, pause, quit| format!("P1: {one}, P2: {two}, '{pause}' to pause, '{quit}' to quit"),
+    arrow_keys: "arrow keys",
+    turning_keys: |keys| format!("{keys} (turning)"),
+    paused: "PAUSED",
+    rewind_hint: |rewind| format!(", '{rewind}' to rewind"),
+    crashed: "CRASHED",
+    crashed_hint: |rewind, quit| format!("'{rewind}' to rewind and try again, '{quit}' to end the run"),
+    terminal_too_small: "Terminal too small",
+    needs_size: |width, height, columns, rows| format!("Needs {width}x{height}, is {columns}x{rows}"),
+    achievement: |name, description| format!("Achievement unlocked: {name} ({description})"),
+    stage_cleared: |score| format!("Stage cleared! Score: {score}"),
+    get_ready: "Get ready...",
+    play_again_hint: "Enter: play again",
+
+    game_over: "Game Over!",
+    times_up: "Time's up!",
+    final_score: "Final score",
+    team_score: "Team score",
+    wins: |player| format!("{player} wins!"),
+    draw: "It's a draw!",
+    best_score: "Best score",
+    high_scores: "HIGH SCORES",
+    no_scores: "No scores yet",
+    new_entry: "<- new!",
+    daily_best: |date, score| format!("Daily challenge {date}: best {score}"),
+    daily_results: "DAILY CHALLENGES",
+    seed_note: |seed| format!("Seed: {seed} (play the same board again with `--seed {seed}`)"),
+    challenge_note: |code| format!("Challenge code: {code} (anyone can play this run with `snekrs challenge <code>`)"),
+    challenge_result: |score, target| match score.cmp(&target) {
+        Ordering::Greater => format!("Challenge beaten: {score} against {target}!"),
+        Ordering::Equal => format!("Challenge tied at {score}"),
+        Ordering::Less => format!("Challenge lost: {score} against {target}"),
+    },
+    rating_note: |rating, change, opponent| format!("Rating: {rating} ({change:+}) after playing {opponent}"),
+    new_best: "New personal best! Its ghost will race you on this seed",
+    replay_saved: |path| format!("Replay saved to {path} (watch it with `snekrs replay`)"),
+    cast_saved: |path| format!("Cast saved to {path} (play it with `asciinema play`)"),
+    hardcore_logged: |count, path| format!("Hardcore run #{count} logged to {path}"),
+    score_submitted: |name| format!("Score submitted to the leaderboard as {name}"),
+    global_leaderboard: "GLOBAL LEADERBOARD",
+    stats_not_saved: |err| format!("Couldn't save stats: {err}"),
+    run_not_logged: |err| format!("Couldn't log the run: {err}"),
+    ratings_not_saved: |err| format!("Couldn't save ratings: {err}"),
+    best_not_saved: |err| format!("Couldn't save personal best: {err}"),
+    replay_not_saved: |path, err| format!("Couldn't save replay to {path}: {err}"),
+    cast_not_saved: |path, err| format!("Couldn't save the cast to {path}: {err}"),
+    score_not_submitted: |err| format!("Couldn't submit score: {err}"),
+    leaderboard_unavailable: |err| format!("Couldn't fetch the leaderboard: {err}"),
+    restart_prompt: "Press R to restart / Q to quit",
+    back_prompt: "Press any key or click to go back",
+    game_saved: "Game saved. Run `snekrs` with no arguments to resume it.",
+
+    new_game: "New game",
+    settings: "Settings",
+    high_scores_item: "High scores",
+    quit: "Quit",
+    restart: "Restart",
+    title_screen: "Title screen",
+    export_board: "Export board",
+    board_exported: |path| format!("Board saved to {path}"),
+    board_not_exported: |path, err| format!("Couldn't export the board to {path}: {err}"),
+    board_on_exit: "The board will be printed when you quit",
+    back: "Back",
+    resume: "Resume",
+    saved_game: "You have a saved game",
+
+    tutorial: "Tutorial",
+    tutorial_move: |keys| format!("Steer with {keys}: make four turns"),
+    tutorial_food: "Food scores points and makes you longer: eat three",
+    tutorial_walls: |seconds| format!("Walls and your own body end the game: stay alive for {seconds} seconds"),
+    tutorial_power: "Power-ups give you a few seconds of something special: grab one",
+    tutorial_passed: "Well done!",
+    tutorial_crashed: "Crashed! Let's try that again",
+    tutorial_done: "That's all there is to it. Press any key to start playing",
+
+    settings_title: "SETTINGS",
+    difficulty: "Difficulty",
+    mode: "Mode",
+    board: "Board",
+    opponent: "Opponent",
+    computer: "Computer",
+    on: "on",
+    off: "off",
+    nobody: "none",
+    second_player: "second player",
+    volume: "Volume",
+
+    replay_banner: |speed| format!("REPLAY x{speed}  '+'/'-' change speed, 'q' to stop"),
+    replay_finished: "Replay finished.",
+    gif_written: |frames, path| format!("Wrote {frames} frames to {path}"),
+
+    hosting: |port| format!("Hosting on port {port}, waiting for a player to join..."),
+    joined: |address| format!("{address} joined!"),
+    waiting_for_return: |seconds| format!("Player 2 lost connection, waiting {seconds}s for them to come back"),
+    opponent_disconnected: "Game Over! The other player disconnected",
+    waiting_for_host: "Connected, waiting for the host...",
+    client_hint: |keys, quit| format!("You are player 2: {keys} to move, '{quit}' to quit"),
+    watching_hint: |quit| format!("Watching: the game is full, '{quit}' to leave"),
+    stopped_watching: "You stopped watching",
+    left_game: "You left the game",
+    reconnecting: "Connection lost, reconnecting...",
+    host_lost: "Connection to the host was lost",
+    hosting_udp: |port| format!("Hosting over UDP on port {port}, waiting for a player to join..."),
+    waiting_at: |address| format!("Waiting for the host at {address}..."),
+    udp_client_hint: |rtt, keys, quit| format!("You are player 2 ({rtt} ms): {keys} to move, '{quit}' to quit"),
+
+    chat_draft: |draft| format!("Say: {draft}_  (Enter to send, Esc to cancel)"),
+    chat_key: |key| format!("'{key}' to chat"),
+
+    lobby_title: "Games on your network",
+    lobby_empty: "Looking for games... (start one with `snekrs host`)",
+    lobby_hint: "Enter to join, 'q' to go back",
+    lobby_game: |name, board, address, open| format!("{name}'s game: {board} ({address}) - {}", if open { "join" } else { "watch" }),
+
+    lifetime_stats: |games, food, seconds, longest, average| {
+        format!(
+            "Lifetime stats\n\
+             --------------\n\
+             Games played:  {games}\n\
+             Food eaten:    {food}\n\
+             Play time:     {}h {:02}m {:02}s\n\
+             Longest snek:  {longest}\n\
+             Average score: {average:.1}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    },
+    hardcore_runs: |runs, best, seconds| format!("Hardcore runs: {runs} (best score {best}, {}m {:02}s survived in all)", seconds / 60, seconds % 60),
+    ratings: "Ratings",
+    rated_matches: |matches| if matches == 1 { "1 match".to_string() } else { format!("{matches} matches") },
+    achievements: "Achievements",
+    unlocks_skin: |skin| format!("unlocks the {skin} skin"),
+};
+
+const SPANISH: Strings = Strings {
+    score: "Puntos",
+    ghost: "Fantasma",
+    skin: "Aspecto",
+    time: "Tiempo",
+    speed: "Velocidad",
+    level: |number, name| format!("Nivel {number}: {name}"),
+    next_at: |target| format!("(siguiente a {target})"),
+    zone_closes: |seconds| format!("La zona se cierra en {seconds}s"),
+    walls_close: |seconds| format!("Los muros se cierran en {seconds}s"),
+    demo_hint: "DEMO - pulsa cualquier tecla para salir",
+    resume_hint: |pause, quit| format!("'{pause}' para seguir, '{quit}' para salir"),
+    solo_hint: |keys, pause, quit| format!("Muévete con {keys}, '{pause}' para pausar, '{quit}' para salir"),
+    duo_hint: |one, two, pause, quit| format!("P1: {one}, P2: {two}, '{pause}' para pausar, '{quit}' para salir"),
+    arrow_keys: "las flechas",
+    turning_keys: |keys| format!("{keys} (girando)"),
+    paused: "PAUSA",
+    rewind_hint: |rewind| format!(", '{rewind}' para rebobinar"),
+    crashed: "CHOQUE",
+    crashed_hint: |rewind, quit| format!("'{rewind}' para rebobinar y volver a intentarlo, '{quit}' para terminar"),
+    terminal_too_small: "Terminal demasiado pequeño",
+    needs_size: |width, height, columns, rows| format!("Necesita {width}x{height}, tiene {columns}x{rows}"),
+    achievement: |name, description| format!("Logro desbloqueado: {name} ({description})"),
+    stage_cleared: |score| format!("¡Fase superada! Puntos: {score}"),
+    get_ready: "Prepárate...",
+    play_again_hint: "Intro: jugar otra vez",
+
+    game_over: "¡Fin de la partida!",
+    times_up: "¡Se acabó el tiempo!",
+    final_score: "Puntuación final",
+    team_score: "Puntuación del equipo",
+    wins: |player| format!("¡Gana {player}!"),
+    draw: "¡Empate!",
+    best_score: "Mejor puntuación",
+    high_scores: "MEJORES PUNTUACIONES",
+    no_scores: "Todavía no hay puntuaciones",
+    new_entry: "<- ¡nueva!",
+    daily_best: |date, score| format!("Reto diario {date}: mejor {score}"),
+    daily_results: "RETOS DIARIOS",
+    seed_note: |seed| format!("Semilla: {seed} (juega el mismo tablero con `--seed {seed}`)"),
+    challenge_note: |code| format!("Código de reto: {code} (cualquiera puede jugar esta partida con `snekrs challenge <código>`)"),
+    challenge_result: |score, target| match score.cmp(&target) {
+        Ordering::Greater => format!("¡Reto superado: {score} contra {target}!"),
+        Ordering::Equal => format!("Reto empatado a {score}"),
+        Ordering::Less => format!("Reto perdido: {score} contra {target}"),
+    },
+    rating_note: |rating, change, opponent| format!("Puntuación Elo: {rating} ({change:+}) tras jugar contra {opponent}"),
+    new_best: "¡Nuevo récord personal! Su fantasma competirá contigo en esta semilla",
+    replay_saved: |path| format!("Repetición guardada en {path} (mírala con `snekrs replay`)"),
+    cast_saved: |path| format!("Grabación guardada en {path} (reprodúcela con `asciinema play`)"),
+    hardcore_logged: |count, path| format!("Partida hardcore n.º {count} registrada en {path}"),
+    score_submitted: |name| format!("Puntuación enviada a la clasificación como {name}"),
+    global_leaderboard: "CLASIFICACIÓN MUNDIAL",
+    stats_not_saved: |err| format!("No se pudieron guardar las estadísticas: {err}"),
+    run_not_logged: |err| format!("No se pudo registrar la partida: {err}"),
+    ratings_not_saved: |err| format!("No se pudieron guardar las puntuaciones Elo: {err}"),
+    best_not_saved: |err| format!("No se pudo guardar la mejor marca personal: {err}"),
+    replay_not_saved: |path, err| format!("No se pudo guardar la repetición en {path}: {err}"),
+    cast_not_saved: |path, err| format!("No se pudo guardar la grabación en {path}: {err}"),
+    score_not_submitted: |err| format!("No se pudo enviar la puntuación: {err}"),
+    leaderboard_unavailable: |err| format!("No se pudo obtener la clasificación: {err}"),
+    restart_prompt: "Pulsa R para reiniciar / Q para salir",
+    back_prompt: "Pulsa cualquier tecla o haz clic para volver",
+    game_saved: "Partida guardada. Ejecuta `snekrs` sin argumentos para continuarla.",
+
+    new_game: "Nueva partida",
+    settings: "Ajustes",
+    high_scores_item: "Mejores puntuaciones",
+    quit: "Salir",
+    restart: "Reiniciar",
+    title_screen: "Pantalla de título",
+    export_board: "Exportar tablero",
+    board_exported: |path| format!("Tablero guardado en {path}"),
+    board_not_exported: |path, err| format!("No se pudo exportar el tablero a {path}: {err}"),
+    board_on_exit: "El tablero se mostrará al salir",
+    back: "Volver",
+    resume: "Continuar",
+    saved_game: "Tienes una partida guardada",
+
+    tutorial: "Tutorial",
+    tutorial_move: |keys| format!("Muévete con {keys}: gira cuatro veces"),
+    tutorial_food: "La comida da puntos y te hace más largo: come tres",
+    tutorial_walls: |seconds| format!("Los muros y tu propio cuerpo acaban la partida: sobrevive {seconds} segundos"),
+    tutorial_power: "Los potenciadores te dan unos segundos de algo especial: coge uno",
+    tutorial_passed: "¡Bien hecho!",
+    tutorial_crashed: "¡Te has estrellado! Vamos a intentarlo otra vez",
+    tutorial_done: "Eso es todo. Pulsa cualquier tecla para empezar a jugar",
+
+    settings_title: "AJUSTES",
+    difficulty: "Dificultad",
+    mode: "Modo",
+    board: "Tablero",
+    opponent: "Rival",
+    computer: "Ordenador",
+    on: "sí",
+    off: "no",
+    nobody: "ninguno",
+    second_player: "segundo jugador",
+    volume: "Volumen",
+
+    replay_banner: |speed| format!("REPETICIÓN x{speed}  '+'/'-' cambia la velocidad, 'q' para parar"),
+    replay_finished: "Repetición terminada.",
+    gif_written: |frames, path| format!("{frames} fotogramas escritos en {path}"),
+
+    hosting: |port| format!("Alojando en el puerto {port}, esperando a que se una un jugador..."),
+    joined: |address| format!("¡{address} se ha unido!"),
+    waiting_for_return: |seconds| format!("El jugador 2 perdió la conexión, esperando {seconds}s a que vuelva"),
+    opponent_disconnected: "¡Fin de la partida! El otro jugador se desconectó",
+    waiting_for_host: "Conectado, esperando al anfitrión...",
+    client_hint: |keys, quit| format!("Eres el jugador 2: muévete con {keys}, '{quit}' para salir"),
+    watching_hint: |quit| format!("Mirando: la partida está llena, '{quit}' para irte"),
+    stopped_watching: "Has dejado de mirar",
+    left_game: "Has abandonado la partida",
+    reconnecting: "Conexión perdida, reconectando...",
+    host_lost: "Se perdió la conexión con el anfitrión",
+    hosting_udp: |port| format!("Alojando por UDP en el puerto {port}, esperando a que se una un jugador..."),
+    waiting_at: |address| format!("Esperando al anfitrión en {address}..."),
+    udp_client_hint: |rtt, keys, quit| format!("Eres el jugador 2 ({rtt} ms): muévete con {keys}, '{quit}' para salir"),
+
+    chat_draft: |draft| format!("Di: {draft}_  (Intro para enviar, Esc para cancelar)"),
+    chat_key: |key| format!("'{key}' para chatear"),
+
+    lobby_title: "Partidas en tu red",
+    lobby_empty: "Buscando partidas... (crea una con `snekrs host`)",
+    lobby_hint: "Intro para unirte, 'q' para volver",
+    lobby_game: |name, board, address, open| format!("Partida de {name}: {board} ({address}) - {}", if open { "unirse" } else { "mirar" }),
+
+    lifetime_stats: |games, food, seconds, longest, average| {
+        format!(
+            "Estadísticas totales\n\
+             --------------------\n\
+             Partidas jugadas:    {games}\n\
+             Comida ingerida:     {food}\n\
+             Tiempo de juego:     {}h {:02}m {:02}s\n\
+             Serpiente más larga: {longest}\n\
+             Puntuación media:    {average:.1}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    },
+    hardcore_runs: |runs, best, seconds| format!("Partidas hardcore: {runs} (mejor puntuación {best}, {}m {:02}s sobrevividos en total)", seconds / 60, seconds % 60),
+    ratings: "Puntuaciones Elo",
+    rated_matches: |matches| if matches == 1 { "1 partida".to_string() } else { format!("{matches} partidas") },
+    achievements: "Logros",
+    unlocks_skin: |skin| format!("desbloquea el aspecto {skin}"),
+};
+
+const GERMAN: Strings = Strings {
+    score: "Punkte",
+    ghost: "Geist",
+    skin: "Aussehen",
+    time: "Zeit",
+    speed: "Tempo",
+    level: |number, name| format!("Level {number}: {name}"),
+    next_at: |target| format!("(nächstes bei {target})"),
+    zone_closes: |seconds| format!("Zone schließt in {seconds}s"),
+    walls_close: |seconds| format!("Mauern schließen in {seconds}s"),
+    demo_hint: "DEMO - beliebige Taste zum Beenden",
+    resume_hint: |pause, quit| format!("'{pause}' zum Fortsetzen, '{quit}' zum Beenden"),
+    solo_hint: |keys, pause, quit| format!("{keys} zum Steuern, '{pause}' für Pause, '{quit}' zum Beenden"),
+    duo_hint: |one, two, pause, quit| format!("P1: {one}, P2: {two}, '{pause}' für Pause, '{quit}' zum Beenden"),
+    arrow_keys: "Pfeiltasten",
+    turning_keys: |keys| format!("{keys} (Abbiegen)"),
+    paused: "PAUSE",
+    rewind_hint: |rewind| format!(", '{rewind}' zum Zurückspulen"),
+    crashed: "CRASH",
+    crashed_hint: |rewind, quit| format!("'{rewind}' zum Zurückspulen und neu Versuchen, '{quit}' zum Beenden"),
+    terminal_too_small: "Terminal zu klein",
+    needs_size: |width, height, columns, rows| format!("Braucht {width}x{height}, hat {columns}x{rows}"),
+    achievement: |name, description| format!("Erfolg freigeschaltet: {name} ({description})"),
+    stage_cleared: |score| format!("Level geschafft! Punkte: {score}"),
+    get_ready: "Mach dich bereit...",
+    play_again_hint: "Enter: neue Runde",
+
+    game_over: "Spiel vorbei!",
+    times_up: "Zeit abgelaufen!",
+    final_score: "Endstand",
+    team_score: "Teampunkte",
+    wins: |player| format!("{player} gewinnt!"),
+    draw: "Unentschieden!",
+    best_score: "Bestleistung",
+    high_scores: "BESTENLISTE",
+    no_scores: "Noch keine Punktzahlen",
+    new_entry: "<- neu!",
+    daily_best: |date, score| format!("Tägliche Herausforderung {date}: bestes {score}"),
+    daily_results: "TÄGLICHE HERAUSFORDERUNGEN",
+    seed_note: |seed| format!("Seed: {seed} (dasselbe Spielfeld nochmal mit `--seed {seed}`)"),
+    challenge_note: |code| format!("Herausforderungscode: {code} (jeder kann diese Runde mit `snekrs challenge <Code>` spielen)"),
+    challenge_result: |score, target| match score.cmp(&target) {
+        Ordering::Greater => format!("Herausforderung geschafft: {score} gegen {target}!"),
+        Ordering::Equal => format!("Herausforderung unentschieden bei {score}"),
+        Ordering::Less => format!("Herausforderung verloren: {score} gegen {target}"),
+    },
+    rating_note: |rating, change, opponent| format!("Wertung: {rating} ({change:+}) nach dem Spiel gegen {opponent}"),
+    new_best: "Neue persönliche Bestleistung! Ihr Geist tritt auf diesem Seed gegen dich an",
+    replay_saved: |path| format!("Wiederholung gespeichert unter {path} (ansehen mit `snekrs replay`)"),
+    cast_saved: |path| format!("Aufnahme gespeichert unter {path} (abspielen mit `asciinema play`)"),
+    hardcore_logged: |count, path| format!("Hardcore-Lauf Nr. {count} gespeichert unter {path}"),
+    score_submitted: |name| format!("Punktzahl als {name} an die Bestenliste gesendet"),
+    global_leaderboard: "WELTWEITE BESTENLISTE",
+    stats_not_saved: |err| format!("Statistik konnte nicht gespeichert werden: {err}"),
+    run_not_logged: |err| format!("Lauf konnte nicht protokolliert werden: {err}"),
+    ratings_not_saved: |err| format!("Wertungen konnten nicht gespeichert werden: {err}"),
+    best_not_saved: |err| format!("Persönliche Bestleistung konnte nicht gespeichert werden: {err}"),
+    replay_not_saved: |path, err| format!("Wiederholung konnte nicht unter {path} gespeichert werden: {err}"),
+    cast_not_saved: |path, err| format!("Aufnahme konnte nicht unter {path} gespeichert werden: {err}"),
+    score_not_submitted: |err| format!("Punktzahl konnte nicht übermittelt werden: {err}"),
+    leaderboard_unavailable: |err| format!("Bestenliste konnte nicht abgerufen werden: {err}"),
+    restart_prompt: "R für Neustart / Q zum Beenden",
+    back_prompt: "Beliebige Taste oder Klick für zurück",
+    game_saved: "Spiel gespeichert. Starte `snekrs` ohne Argumente, um weiterzuspielen.",
+
+    new_game: "Neues Spiel",
+    settings: "Einstellungen",
+    high_scores_item: "Bestenliste",
+    quit: "Beenden",
+    restart: "Neustart",
+    title_screen: "Titelbildschirm",
+    export_board: "Spielfeld exportieren",
+    board_exported: |path| format!("Spielfeld gespeichert unter {path}"),
+    board_not_exported: |path, err| format!("Spielfeld konnte nicht nach {path} exportiert werden: {err}"),
+    board_on_exit: "Das Spielfeld wird beim Beenden ausgegeben",
+    back: "Zurück",
+    resume: "Fortsetzen",
+    saved_game: "Du hast ein gespeichertes Spiel",
+
+    tutorial: "Tutorial",
+    tutorial_move: |keys| format!("Steuere mit {keys}: biege viermal ab"),
+    tutorial_food: "Futter bringt Punkte und macht dich länger: friss drei",
+    tutorial_walls: |seconds| format!("Mauern und dein eigener Körper beenden das Spiel: überlebe {seconds} Sekunden"),
+    tutorial_power: "Power-ups geben dir ein paar Sekunden etwas Besonderes: schnapp dir eins",
+    tutorial_passed: "Gut gemacht!",
+    tutorial_crashed: "Zusammengestoßen! Versuch es noch einmal",
+    tutorial_done: "Mehr gibt es nicht zu wissen. Beliebige Taste zum Losspielen",
+
+    settings_title: "EINSTELLUNGEN",
+    difficulty: "Schwierigkeit",
+    mode: "Modus",
+    board: "Spielfeld",
+    opponent: "Gegner",
+    computer: "Computer",
+    on: "an",
+    off: "aus",
+    nobody: "keiner",
+    second_player: "zweiter Spieler",
+    volume: "Lautstärke",
+
+    replay_banner: |speed| format!("WIEDERHOLUNG x{speed}  '+'/'-' ändert das Tempo, 'q' zum Beenden"),
+    replay_finished: "Wiederholung beendet.",
+    gif_written: |frames, path| format!("{frames} Bilder nach {path} geschrieben"),
+
+    hosting: |port| format!("Hoste auf Port {port}, warte auf einen Mitspieler..."),
+    joined: |address| format!("{address} ist beigetreten!"),
+    waiting_for_return: |seconds| format!("Spieler 2 hat die Verbindung verloren, warte {seconds}s auf die Rückkehr"),
+    opponent_disconnected: "Spiel vorbei! Der andere Spieler hat die Verbindung getrennt",
+    waiting_for_host: "Verbunden, warte auf den Host...",
+    client_hint: |keys, quit| format!("Du bist Spieler 2: {keys} zum Steuern, '{quit}' zum Beenden"),
+    watching_hint: |quit| format!("Zuschauen: das Spiel ist voll, '{quit}' zum Verlassen"),
+    stopped_watching: "Du schaust nicht mehr zu",
+    left_game: "Du hast das Spiel verlassen",
+    reconnecting: "Verbindung verloren, verbinde neu...",
+    host_lost: "Die Verbindung zum Host ist abgebrochen",
+    hosting_udp: |port| format!("Hoste über UDP auf Port {port}, warte auf einen Mitspieler..."),
+    waiting_at: |address| format!("Warte auf den Host unter {address}..."),
+    udp_client_hint: |rtt, keys, quit| format!("Du bist Spieler 2 ({rtt} ms): {keys} zum Steuern, '{quit}' zum Beenden"),
+
+    chat_draft: |draft| format!("Sag: {draft}_  (Enter zum Senden, Esc zum Abbrechen)"),
+    chat_key: |key| format!("'{key}' zum Chatten"),
+
+    lobby_title: "Spiele in deinem Netzwerk",
+    lobby_empty: "Suche nach Spielen... (starte eins mit `snekrs host`)",
+    lobby_hint: "Enter zum Beitreten, 'q' für zurück",
+    lobby_game: |name, board, address, open| format!("Spiel von {name}: {board} ({address}) - {}", if open { "beitreten" } else { "zuschauen" }),
+
+    lifetime_stats: |games, food, seconds, longest, average| {
+        format!(
+            "Gesamtstatistik\n\
+             ---------------\n\
+             Gespielte Spiele:       {games}\n\
+             Gefressenes Futter:     {food}\n\
+             Spielzeit:              {}h {:02}m {:02}s\n\
+             Längste Schlange:       {longest}\n\
+             Durchschnittspunktzahl: {average:.1}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    },
+    hardcore_runs: |runs, best, seconds| format!("Hardcore-Läufe: {runs} (beste Punktzahl {best}, insgesamt {}m {:02}s überlebt)", seconds / 60, seconds % 60),
+    ratings: "Wertungen",
+    rated_matches: |matches| if matches == 1 { "1 Spiel".to_string() } else { format!("{matches} Spiele") },
+    achievements: "Erfolge",
+    unlocks_skin: |skin| format!("schaltet das Aussehen {skin} frei"),
+};
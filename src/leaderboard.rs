@@ -0,0 +1,130 @@
+// Optional online leaderboard client
+// Design Decision: Plain HTTP/1.0 over a std TcpStream keeps the game free of an HTTP
+// stack; HTTP/1.0 also means the server closes the connection and never chunks the body
+//
+// Protocol against the configured endpoint URL:
+//   POST <url>  form fields name, score, seed, board  -> any 2xx status
+//   GET  <url>  -> text/plain, one "<score>\t<name>\t<board>" line per entry, best first
+use std::io::{Error, ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+// How long to wait on the server before giving up
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// Entries shown from the global list
+const SHOWN_ENTRIES: usize = 10;
+
+// One line of the global list
+pub(crate) struct Entry {
+    pub(crate) score: u32,
+    pub(crate) name: String,
+    pub(crate) board: String,
+}
+
+// A parsed http:// endpoint
+struct Endpoint {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl Endpoint {
+    // Splits an http:// URL into host, port and path
+    fn parse(url: &str) -> std::io::Result<Endpoint> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            Error::new(ErrorKind::InvalidInput, "leaderboard URL must start with http:// (https isn't supported)")
+        })?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port.parse().map_err(|_| Error::new(ErrorKind::InvalidInput, "bad leaderboard port"))?;
+                (host, port)
+            }
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "leaderboard URL has no host"));
+        }
+        Ok(Endpoint { host: host.to_string(), port, path: path.to_string() })
+    }
+
+    // Sends one request and returns the response body of a 2xx reply
+    fn request(&self, method: &str, body: Option<&str>) -> std::io::Result<String> {
+        let address = (self.host.as_str(), self.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "leaderboard host not found"))?;
+        let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        let mut request = format!("{method} {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: snekrs\r\n", self.path, self.host);
+        if let Some(body) = body {
+            request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+            request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+        }
+        request.push_str("\r\n");
+        request.push_str(body.unwrap_or_default());
+        stream.write_all(request.as_bytes())?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+        let status = head.split_whitespace().nth(1).unwrap_or_default();
+        if !status.starts_with('2') {
+            return Err(Error::other(format!("leaderboard replied with status {status}")));
+        }
+        Ok(body.to_string())
+    }
+}
+
+// Submits a final score
+pub(crate) fn submit(url: &str, name: &str, score: u32, seed: u64, board: &str) -> std::io::Result<()> {
+    let body = format!(
+        "name={}&score={score}&seed={seed}&board={}",
+        form_encode(name),
+        form_encode(board)
+    );
+    Endpoint::parse(url)?.request("POST", Some(&body)).map(|_| ())
+}
+
+// Fetches the top of the global list
+pub(crate) fn fetch(url: &str) -> std::io::Result<Vec<Entry>> {
+    let body = Endpoint::parse(url)?.request("GET", None)?;
+    Ok(body
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some(Entry {
+                score: fields.next()?.trim().parse().ok()?,
+                name: fields.next()?.to_string(),
+                board: fields.next().unwrap_or_default().to_string(),
+            })
+        })
+        .take(SHOWN_ENTRIES)
+        .collect())
+}
+
+// Name submitted with scores: the login name, which players can override with SNEKRS_NAME
+pub(crate) fn player_name() -> String {
+    ["SNEKRS_NAME", "USER", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|name| !name.is_empty()))
+        .unwrap_or_else(|| "anonymous".to_string())
+}
+
+// Percent-encodes a form value
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            b' ' => "+".to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
@@ -0,0 +1,235 @@
+// Level loading from plain ASCII grid files
+// Design Decision: A text grid is easy to hand-edit and diff, so no extra parser crates are needed
+//
+// File format (one character per cell, one line per row):
+//   '#'       wall (the outer border is always a wall, whatever is drawn there)
+//   'S'       snake spawn point (defaults to the board center when absent)
+//   '*'       food spawn spot (food is placed at random when no spots are given)
+//   '1'-'9'   portal ends (each digit appears exactly twice, linking its two cells)
+//   ' ' / '.' empty floor
+//
+// ASCII art (Level::from_art) is looser: any drawing becomes a board, see there
+use crate::game::Position;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+// Smallest board that still leaves room to move inside the border
+const MIN_SIZE: u16 = 5;
+
+// Largest board we accept, keeps coordinates comfortably inside u16 math
+const MAX_SIZE: u16 = 500;
+
+// Board glyph for a portal end, whichever pair it belongs to
+pub const PORTAL_GLYPH: char = 'Ø';
+
+// Parsed level description consumed by Game::from_level
+#[derive(Clone)]
+pub struct Level {
+    pub width: u16,
+    pub height: u16,
+    pub walls: Vec<Position>,   // Interior walls (border excluded)
+    pub spawn: Position,        // Snake starting cell
+    pub food: Vec<Position>,    // Preferred food spawn spots
+    pub portals: Vec<(Position, Position)>,  // Linked portal ends
+}
+
+impl Level {
+    // Reads and parses a level file from disk
+    pub fn load(path: &Path) -> std::io::Result<Level> {
+        let contents = fs::read_to_string(path)?;
+        Level::parse(&contents)
+    }
+
+    // Parses a level from its textual grid representation
+    // Design Decision: Ragged lines are padded with floor so trailing spaces are optional
+    pub fn parse(contents: &str) -> std::io::Result<Level> {
+        let rows: Vec<&str> = contents.lines().collect();
+        let height = rows.len();
+        let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+
+        if width < MIN_SIZE as usize || height < MIN_SIZE as usize {
+            return Err(invalid(format!("level must be at least {MIN_SIZE}x{MIN_SIZE} cells")));
+        }
+        if width > MAX_SIZE as usize || height > MAX_SIZE as usize {
+            return Err(invalid(format!("level must be at most {MAX_SIZE}x{MAX_SIZE} cells")));
+        }
+
+        let (width, height) = (width as u16, height as u16);
+        let mut walls = Vec::new();
+        let mut food = Vec::new();
+        let mut portal_ends: Vec<Vec<Position>> = vec![Vec::new(); 9];
+        let mut spawn = None;
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, ch) in row.chars().enumerate() {
+                let pos = Position { x: x as u16, y: y as u16 };
+                let on_border = pos.x == 0 || pos.y == 0 || pos.x == width - 1 || pos.y == height - 1;
+                match ch {
+                    '#' if !on_border => walls.push(pos),
+                    'S' | '*' | '1'..='9' if on_border => {
+                        return Err(invalid(format!("'{ch}' at {x},{y} sits on the border wall")));
+                    }
+                    'S' if spawn.is_some() => {
+                        return Err(invalid("level has more than one spawn point".to_string()));
+                    }
+                    'S' => spawn = Some(pos),
+                    '*' => food.push(pos),
+                    '1'..='9' => portal_ends[ch as usize - '1' as usize].push(pos),
+                    '#' | ' ' | '.' => {}
+                    other => {
+                        return Err(invalid(format!("unknown tile '{other}' at {x},{y}")));
+                    }
+                }
+            }
+        }
+
+        let mut portals = Vec::new();
+        for (digit, ends) in portal_ends.iter().enumerate() {
+            match ends[..] {
+                [] => {}
+                [one, two] => portals.push((one, two)),
+                _ => {
+                    return Err(invalid(format!("portal '{}' needs exactly two ends, found {}", digit + 1, ends.len())));
+                }
+            }
+        }
+
+        let spawn = spawn.unwrap_or(Position { x: width / 2, y: height / 2 });
+        if walls.contains(&spawn) {
+            return Err(invalid("spawn point is inside a wall".to_string()));
+        }
+        if portals.iter().any(|&(one, two)| spawn == one || spawn == two) {
+            return Err(invalid("spawn point is on a portal".to_string()));
+        }
+
+        Ok(Level { width, height, walls, spawn, food, portals })
+    }
+
+    // Reads a free-form ASCII art file and builds a level from it
+    pub fn load_art(path: &Path) -> std::io::Result<Level> {
+        let contents = fs::read_to_string(path)?;
+        Level::from_art(&contents)
+    }
+
+    // Builds a level from free-form ASCII art: '.' and blanks are floor, the first 'S' is the
+    // spawn, and '#' and every other mark is a wall
+    // Design Decision: Nothing in a drawing is an error. The art is cut to the area it covers
+    // (blank lines around it and a shared indent dropped, tabs expanded) and walled in from the
+    // outside, so marks on its edges stay in play and it needs no border of its own
+    pub fn from_art(contents: &str) -> std::io::Result<Level> {
+        let mut lines: Vec<String> = contents.lines().map(|line| expand_tabs(line).trim_end().to_string()).collect();
+        while lines.last().is_some_and(String::is_empty) {
+            lines.pop();
+        }
+        let first = lines.iter().position(|line| !line.is_empty()).unwrap_or(lines.len());
+        lines.drain(..first);
+        let indent = lines.iter().filter(|line| !line.is_empty()).map(|line| line.chars().take_while(|c| c.is_whitespace()).count()).min().unwrap_or(0);
+
+        let art_width = lines.iter().map(|line| line.chars().count().saturating_sub(indent)).max().unwrap_or(0);
+        let width = (art_width + 2).max(MIN_SIZE as usize);
+        let height = (lines.len() + 2).max(MIN_SIZE as usize);
+        if width > MAX_SIZE as usize || height > MAX_SIZE as usize {
+            return Err(invalid(format!("art must fit in {} by {} characters", MAX_SIZE - 2, MAX_SIZE - 2)));
+        }
+
+        let mut grid = vec![vec!['#'; width]; height];
+        let mut spawned = false;
+        for (y, row) in grid.iter_mut().enumerate().take(height - 1).skip(1) {
+            let mut marks = lines.get(y - 1).map(|line| line.chars().skip(indent)).into_iter().flatten();
+            for cell in row.iter_mut().take(width - 1).skip(1) {
+                *cell = match marks.next().unwrap_or(' ') {
+                    'S' if !spawned => {
+                        spawned = true;
+                        'S'
+                    }
+                    'S' | '.' => ' ',
+                    mark if mark.is_whitespace() => ' ',
+                    _ => '#',
+                };
+            }
+        }
+
+        // Without an 'S' the snake starts on the free cell nearest the middle
+        if !spawned {
+            let middle = Position { x: width as u16 / 2, y: height as u16 / 2 };
+            let free = (1..height - 1)
+                .flat_map(|y| (1..width - 1).map(move |x| Position { x: x as u16, y: y as u16 }))
+                .filter(|pos| grid[usize::from(pos.y)][usize::from(pos.x)] == ' ')
+                .min_by_key(|pos| pos.x.abs_diff(middle.x) + pos.y.abs_diff(middle.y));
+            let Some(spawn) = free else {
+                return Err(invalid("the art leaves no floor to play on".to_string()));
+            };
+            grid[usize::from(spawn.y)][usize::from(spawn.x)] = 'S';
+        }
+        let text: String = grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect();
+        Level::parse(&text)
+    }
+
+    // Renders the level back into the grid format accepted by `parse`
+    pub fn to_grid(&self) -> String {
+        let mut grid = String::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let pos = Position { x, y };
+                let on_border = x == 0 || y == 0 || x == self.width - 1 || y == self.height - 1;
+                grid.push(if on_border || self.walls.contains(&pos) {
+                    '#'
+                } else if pos == self.spawn {
+                    'S'
+                } else if self.food.contains(&pos) {
+                    '*'
+                } else if let Some(pair) = self.portals.iter().position(|&(one, two)| pos == one || pos == two) {
+                    char::from_digit(pair as u32 + 1, 10).unwrap_or(' ')
+                } else {
+                    ' '
+                });
+            }
+            grid.push('\n');
+        }
+        grid
+    }
+
+    // Picks a starting cell for a second snake, heading left
+    // Design Decision: Mirror the main spawn for fairness, nudging it away when they'd start too close
+    pub fn second_spawn(&self) -> Position {
+        let mut target = Position { x: self.width - 1 - self.spawn.x, y: self.spawn.y };
+        if target.x.abs_diff(self.spawn.x) < self.width / 3 {
+            target = Position {
+                x: (self.spawn.x + self.width / 4).min(self.width - 2),
+                y: (self.spawn.y + self.height / 4).min(self.height - 2),
+            };
+        }
+
+        let free = |pos: &Position| {
+            !self.walls.contains(pos)
+                && *pos != self.spawn
+                && !self.portals.iter().any(|&(one, two)| *pos == one || *pos == two)
+        };
+        (1..self.height - 1)
+            .flat_map(|y| (2..self.width - 1).map(move |x| Position { x, y }))
+            .filter(|pos| free(pos) && free(&Position { x: pos.x - 1, y: pos.y }))
+            .filter(|pos| pos.y.abs_diff(self.spawn.y) > 1 || pos.x.abs_diff(self.spawn.x) > 3)
+            .min_by_key(|pos| pos.x.abs_diff(target.x) + pos.y.abs_diff(target.y))
+            .unwrap_or(target)
+    }
+}
+
+// Replaces tabs with spaces up to the next multiple of eight columns, as terminals show them
+fn expand_tabs(line: &str) -> String {
+    let mut expanded = String::new();
+    for ch in line.chars() {
+        if ch == '\t' {
+            let column = expanded.chars().count();
+            expanded.extend(std::iter::repeat_n(' ', 8 - column % 8));
+        } else {
+            expanded.push(ch);
+        }
+    }
+    expanded
+}
+
+// Builds an InvalidData error for malformed level files
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,51 @@
+// The snekrs engine: boards, rules, bots and replays, and the Frame of glyphs a frontend draws
+// Design Decision: Everything a game needs lives in the library and nothing in it touches the
+// terminal, so bots, tests and other frontends can embed it; the binary adds the drawing,
+// input, config file, menus, flags and networking around it
+//
+// Driving a game without a terminal:
+//
+//   let mut game = snekrs::Game::new(snekrs::game::Board::Classic, 1, seed, snekrs::GameConfig::default());
+//   game.hand_to_ai(0, snekrs::ai::Difficulty::Hard);
+//   snekrs::headless::run(&mut game, &[], snekrs::headless::MAX_TICKS);
+//   println!("{}", game.state().score());
+pub mod achievements;
+pub mod ai;
+pub mod campaign;
+pub mod cast;
+pub mod challenge;
+pub mod config;
+pub mod daily;
+pub mod difficulty;
+pub mod effects;
+pub mod entity;
+pub mod events;
+pub mod food;
+pub mod frame;
+pub mod game;
+pub mod ghost;
+pub mod headless;
+pub mod lang;
+pub mod level;
+pub mod log;
+#[cfg(feature = "lua")]
+pub mod lua;
+pub mod maze;
+pub mod mode;
+pub mod mods;
+pub mod paths;
+pub mod popups;
+pub mod powerup;
+pub mod rating;
+pub mod replay;
+pub mod runs;
+pub mod scores;
+pub mod scoring;
+pub mod snake;
+pub mod state;
+pub mod stats;
+pub mod stream;
+
+pub use config::GameConfig;
+pub use game::{Direction, Game, Position};
+pub use state::GameState;
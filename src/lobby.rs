@@ -0,0 +1,200 @@
+// LAN lobby: hosted games announce themselves on the local network, and `snekrs lobby`
+// lists them to join with a keypress
+// Design Decision: Hosts multicast a short announcement every second rather than answering
+// queries, so the lobby needs no request protocol and a game that ends simply stops showing
+// up; the lobby reads each host's address off the datagram itself, so nobody has to know
+// their own IP
+//
+// Announcement: one datagram to DISCOVERY_GROUP:DISCOVERY_PORT
+//   SNEKRS <port> <tcp|udp> <open|full> <host's name>\t<board settings>
+use crate::chat;
+use crate::game::Game;
+use crate::input;
+use crate::lang;
+use crate::leaderboard;
+use crate::menu::Menu;
+use crate::play;
+use crossterm::{
+    cursor::MoveTo,
+    event::{Event, KeyCode},
+    execute,
+    style::Print,
+    terminal::{self, Clear, ClearType},
+};
+use std::io::{stdout, Write};
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Organization-local multicast group and port the announcements go to
+const DISCOVERY_GROUP: Ipv4Addr = Ipv4Addr::new(239, 255, 78, 78);
+const DISCOVERY_PORT: u16 = 7877;
+
+// How often a host announces its game
+const ANNOUNCE_INTERVAL: Duration = Duration::from_secs(1);
+
+// A game not heard from for this long has ended (or its host went away)
+const LISTING_EXPIRY: Duration = Duration::from_secs(3);
+
+// Rows between the title and the list of games
+const LIST_TOP: u16 = 3;
+
+// Announces a hosted game on the local network until it's dropped
+pub(crate) struct Beacon {
+    open: Sender<bool>,  // Whether a player can still join (false = full, spectators only)
+}
+
+impl Beacon {
+    // Starts announcing a game hosted on this port over TCP or UDP
+    // Design Decision: Announcing runs on its own thread, so the host's game loop never waits
+    // on the network; sends that fail (no multicast route, say) are ignored, since the game
+    // can still be joined by address
+    pub(crate) fn start(port: u16, udp: bool, game: &Game) -> Beacon {
+        let (open, updates) = mpsc::channel();
+        let transport = if udp { "udp" } else { "tcp" };
+        let about = format!("{}\t{}", leaderboard::player_name().replace('\t', " "), game.board_settings());
+        thread::spawn(move || {
+            let Ok(socket) = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)) else {
+                return;
+            };
+            let mut state = "open";
+            loop {
+                let announcement = format!("SNEKRS {port} {transport} {state} {about}");
+                let _ = socket.send_to(announcement.as_bytes(), (DISCOVERY_GROUP, DISCOVERY_PORT));
+                match updates.recv_timeout(ANNOUNCE_INTERVAL) {
+                    Ok(open) => state = if open { "open" } else { "full" },
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+        Beacon { open }
+    }
+
+    // Announces the game as full from now on, for the lobby to offer watching instead
+    pub(crate) fn fill(&self) {
+        let _ = self.open.send(false);
+    }
+}
+
+// A game found on the local network
+pub(crate) struct Listing {
+    pub(crate) address: SocketAddr,  // Where to join it
+    pub(crate) udp: bool,            // Hosted with --udp
+    open: bool,                      // Still waiting for its second player
+    name: String,                    // Who is hosting
+    settings: String,                // Board, difficulty, mode and mods
+    seen: Instant,                   // When it was last announced
+}
+
+impl Listing {
+    // Reads an announcement, returning None for anything malformed
+    // Design Decision: Anyone on the network can send one, so the name and settings are cleaned
+    // like chat before they get near the screen
+    fn parse(text: &str, from: SocketAddr) -> Option<Listing> {
+        let mut fields = text.splitn(5, ' ');
+        if fields.next()? != "SNEKRS" {
+            return None;
+        }
+        let port = fields.next()?.parse().ok()?;
+        let udp = match fields.next()? {
+            "udp" => true,
+            "tcp" => false,
+            _ => return None,
+        };
+        let open = match fields.next()? {
+            "open" => true,
+            "full" => false,
+            _ => return None,
+        };
+        let (name, settings) = fields.next()?.split_once('\t')?;
+        Some(Listing {
+            address: SocketAddr::new(from.ip(), port),
+            udp,
+            open,
+            name: chat::clean(name),
+            settings: chat::clean(settings),
+            seen: Instant::now(),
+        })
+    }
+
+    // One line of the lobby's list
+    fn label(&self) -> String {
+        let transport = if self.udp { ", UDP" } else { "" };
+        (lang::text().lobby_game)(&self.name, &self.settings, &format!("{}{transport}", self.address), self.open)
+    }
+}
+
+// Lists the games hosted on the local network until the player picks one to join (or
+// watch), or backs out with q or Esc
+pub(crate) fn pick() -> std::io::Result<Option<Listing>> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, DISCOVERY_PORT))?;
+    socket.join_multicast_v4(&DISCOVERY_GROUP, &Ipv4Addr::UNSPECIFIED)?;
+    socket.set_nonblocking(true)?;
+
+    let game_screen = play::enter_game_screen()?;
+    let mut listings: Vec<Listing> = Vec::new();
+    let mut selected = 0;
+    let mut buffer = [0; 1024];
+    let mut stale = true;  // Whether the list on screen is out of date
+    let picked = loop {
+        // Games already listed are updated in place, so the list doesn't reorder under the cursor
+        while let Ok((size, from)) = socket.recv_from(&mut buffer) {
+            let Some(listing) = std::str::from_utf8(&buffer[..size]).ok().and_then(|text| Listing::parse(text, from)) else {
+                continue;
+            };
+            match listings.iter_mut().find(|known| known.address == listing.address) {
+                Some(known) => {
+                    stale |= known.open != listing.open || known.settings != listing.settings;
+                    *known = listing;
+                }
+                None => {
+                    listings.push(listing);
+                    stale = true;
+                }
+            }
+        }
+        let before = listings.len();
+        listings.retain(|listing| listing.seen.elapsed() < LISTING_EXPIRY);
+        stale |= listings.len() != before;
+
+        let labels: Vec<String> = listings.iter().map(Listing::label).collect();
+        let items: Vec<&str> = labels.iter().map(String::as_str).collect();
+        let mut menu = Menu::new(&items, 2, LIST_TOP).with_selected(selected);
+        if stale {
+            draw(&menu, listings.is_empty())?;
+            stale = false;
+        }
+        let Some(event) = input::next(Duration::from_millis(100))? else {
+            continue;
+        };
+        if let Event::Key(key_event) = &event {
+            if matches!(key_event.code, KeyCode::Char('q') | KeyCode::Char('Q')) {
+                break None;
+            }
+        }
+        match menu.handle(&event)? {
+            Some(Some(index)) => break Some(index),
+            Some(None) => break None,
+            None => selected = menu.selected(),
+        }
+        stale |= matches!(event, Event::Resize(..));
+    };
+    game_screen.leave()?;
+    Ok(picked.map(|index| listings.swap_remove(index)))
+}
+
+// Draws the lobby: the games found so far, or a note that none have been yet
+fn draw(menu: &Menu, empty: bool) -> std::io::Result<()> {
+    let (mut stdout, text) = (stdout(), lang::text());
+    execute!(stdout, Clear(ClearType::All), MoveTo(2, 1), Print(text.lobby_title))?;
+    if empty {
+        execute!(stdout, MoveTo(2, LIST_TOP), Print(text.lobby_empty))?;
+    } else {
+        menu.draw()?;
+    }
+    let (_, rows) = terminal::size().unwrap_or((0, 24));
+    execute!(stdout, MoveTo(2, rows.saturating_sub(2)), Print(text.lobby_hint))?;
+    stdout.flush()
+}
@@ -0,0 +1,48 @@
+// Opt-in debug log of what the game saw and did, with timestamps
+// Design Decision: A small logfmt writer (`t=1.234567 kind=tick n=42 late_ms=3`) instead of
+// a logging crate: one line per record, flushed as it's written so nothing is lost when the
+// game is killed, and easy to grep or load into a script when chasing dropped keys or
+// stutters over a slow connection. Nothing is formatted unless a log file was opened
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// The open log and when it was opened (unset = logging is off)
+static LOG: OnceLock<(Mutex<LineWriter<File>>, Instant)> = OnceLock::new();
+
+// Starts logging to a file, replacing what was in it; later calls are ignored
+pub fn open(path: &Path) -> io::Result<()> {
+    let file = File::create(path)?;
+    let _ = LOG.set((Mutex::new(LineWriter::new(file)), Instant::now()));
+    let unix = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    record("start", &[("version", &env!("CARGO_PKG_VERSION")), ("unix", &unix)]);
+    Ok(())
+}
+
+// Whether a log file is open, for skipping work that only feeds the log
+pub fn enabled() -> bool {
+    LOG.get().is_some()
+}
+
+// Writes one record: seconds since the log opened, its kind, then each field in order
+// (values with spaces or quotes are quoted); write errors are ignored so logging never
+// gets in the way of playing
+pub fn record(kind: &str, fields: &[(&str, &dyn Display)]) {
+    let Some((file, opened)) = LOG.get() else {
+        return;
+    };
+    let mut line = format!("t={:.6} kind={kind}", opened.elapsed().as_secs_f64());
+    for (key, value) in fields {
+        let value = value.to_string();
+        if value.is_empty() || value.contains([' ', '"', '=']) {
+            line.push_str(&format!(" {key}=\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"")));
+        } else {
+            line.push_str(&format!(" {key}={value}"));
+        }
+    }
+    let mut file = file.lock().unwrap_or_else(PoisonError::into_inner);
+    let _ = writeln!(file, "{line}");
+}
@@ -0,0 +1,166 @@
+// Game mods written in Lua, in builds with `--features lua`
+// Design Decision: A script is one more GameMod whose hooks call the global Lua function of the
+// same name, when the script defines one, so scripts run exactly where the built-in mods do.
+// Scripts only get Lua's string, table and math libraries on top of the basics, so rules
+// downloaded from someone else can't read files or run programs
+//
+// Example rules.lua, with poison turned golden, triple points and a hunger of its own:
+//
+//   ticks = 0
+//
+//   function on_tick(game)
+//     ticks = ticks + 1
+//     if ticks % 30 == 0 then
+//       for player = 1, game:snakes() do game:shrink(player, 1) end
+//     end
+//   end
+//
+//   function on_food_eaten(snake, kind, points)
+//     return points * 3
+//   end
+//
+//   function modify_spawn(spawn, game)
+//     if spawn.kind == "poison" then spawn.kind = "golden" end
+//   end
+use crate::config::MAX_SIZE;
+use crate::food::FoodKind;
+use crate::game::Position;
+use crate::mods::{GameMod, Spawn};
+use crate::snake::Snake;
+use crate::state::GameState;
+use mlua::{Function, Lua, LuaOptions, StdLib, UserData, UserDataMethods};
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::rc::Rc;
+
+// A loaded script, ready to stack on a game with Game::add_mod
+// Design Decision: Copies of the mod share one Lua state, so a script's own variables aren't
+// rewound with the board the way the built-in mods' counters are
+#[derive(Clone)]
+pub struct LuaMod {
+    lua: Rc<Lua>,
+}
+
+impl LuaMod {
+    // Loads a script from a file
+    pub fn load(path: &Path) -> std::io::Result<LuaMod> {
+        LuaMod::parse(&fs::read_to_string(path)?, &path.display().to_string())
+    }
+
+    // Runs a script's top level, which defines its hooks; `name` labels its error messages
+    pub fn parse(source: &str, name: &str) -> std::io::Result<LuaMod> {
+        let lua = Lua::new_with(StdLib::STRING | StdLib::TABLE | StdLib::MATH, LuaOptions::default()).map_err(invalid)?;
+        lua.load(source).set_name(name).exec().map_err(invalid)?;
+        Ok(LuaMod { lua: Rc::new(lua) })
+    }
+
+    // The script's function for a hook, if it has one
+    fn hook(&self, name: &str) -> Option<Function<'_>> {
+        self.lua.globals().get(name).ok()
+    }
+}
+
+// Design Decision: An error raised in a hook only ends that call of it, so a broken
+// script spoils its own rules but never the game
+impl GameMod for LuaMod {
+    fn on_tick(&mut self, state: &mut GameState) {
+        if let Some(hook) = self.hook("on_tick") {
+            let _ = self.lua.scope(|scope| hook.call::<_, ()>(scope.create_userdata_ref_mut(state)?));
+        }
+    }
+
+    // A hook that returns nothing keeps the points as they were
+    fn on_food_eaten(&mut self, snake: &mut Snake, kind: FoodKind, points: u32) -> u32 {
+        let Some(hook) = self.hook("on_food_eaten") else {
+            return points;
+        };
+        let awarded = self.lua.scope(|scope| hook.call::<_, Option<u32>>((scope.create_userdata_ref_mut(snake)?, kind.name(), points)));
+        awarded.ok().flatten().unwrap_or(points)
+    }
+
+    // The hook gets the spawn as a table of x, y (from 0) and kind, and changes it in place
+    fn modify_spawn(&mut self, spawn: &mut Spawn, state: &GameState) {
+        let Some(hook) = self.hook("modify_spawn") else {
+            return;
+        };
+        let changed = self.lua.scope(|scope| {
+            let table = self.lua.create_table()?;
+            table.set("x", spawn.pos.x)?;
+            table.set("y", spawn.pos.y)?;
+            table.set("kind", spawn.kind.name())?;
+            hook.call::<_, ()>((table.clone(), scope.create_userdata_ref(state)?))?;
+            Ok((table.get::<_, u16>("x")?, table.get::<_, u16>("y")?, table.get::<_, String>("kind")?))
+        });
+        if let Ok((x, y, kind)) = changed {
+            if let Some(kind) = FoodKind::parse(&kind) {
+                *spawn = Spawn { pos: Position { x, y }, kind };
+            }
+        }
+    }
+}
+
+// What on_tick and modify_spawn see of the game, players numbered from 1 as Lua counts
+// (grow and shrink only work in on_tick)
+impl UserData for GameState {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("width", |_, state, ()| Ok(state.width()));
+        methods.add_method("height", |_, state, ()| Ok(state.height()));
+        methods.add_method("snakes", |_, state, ()| Ok(state.snakes.len()));
+        methods.add_method("length", |_, state, player: usize| Ok(state.snakes[index(state, player)?].body.len()));
+        methods.add_method("score", |_, state, player: usize| Ok(state.snakes[index(state, player)?].score));
+        methods.add_method("alive", |_, state, player: usize| Ok(state.snakes[index(state, player)?].alive));
+        methods.add_method_mut("grow", |_, state, (player, segments): (usize, usize)| {
+            let player = index(state, player)?;
+            grow(&mut state.snakes[player], segments);
+            Ok(())
+        });
+        methods.add_method_mut("shrink", |_, state, (player, segments): (usize, usize)| {
+            let player = index(state, player)?;
+            shrink(&mut state.snakes[player], segments);
+            Ok(())
+        });
+    }
+}
+
+// The snake that's eating, in on_food_eaten
+impl UserData for Snake {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("length", |_, snake, ()| Ok(snake.body.len()));
+        methods.add_method("score", |_, snake, ()| Ok(snake.score));
+        methods.add_method_mut("grow", |_, snake, segments: usize| {
+            grow(snake, segments);
+            Ok(())
+        });
+        methods.add_method_mut("shrink", |_, snake, segments: usize| {
+            shrink(snake, segments);
+            Ok(())
+        });
+    }
+}
+
+// Index into the snakes of a player numbered from 1, or a Lua error naming a missing one
+fn index(state: &GameState, player: usize) -> mlua::Result<usize> {
+    match player {
+        1.. if player <= state.snakes.len() => Ok(player - 1),
+        _ => Err(mlua::Error::RuntimeError(format!("there is no player {player}"))),
+    }
+}
+
+// New segments stack on the tail and unfold as the snake moves on, like a big meal's, up to
+// as many as the largest board has cells
+fn grow(snake: &mut Snake, segments: usize) {
+    let tail = *snake.body.back().unwrap();
+    snake.body.extend(std::iter::repeat_n(tail, segments.min(usize::from(MAX_SIZE).pow(2))));
+}
+
+// Takes segments off the tail, always leaving the head
+fn shrink(snake: &mut Snake, segments: usize) {
+    let length = snake.body.len().saturating_sub(segments).max(1);
+    snake.body.truncate(length);
+}
+
+// Builds an InvalidData error for a script that failed to load
+fn invalid(err: mlua::Error) -> Error {
+    Error::new(ErrorKind::InvalidData, err.to_string())
+}
@@ -1,7 +1,250 @@
-mod game;
+// The terminal frontend: command line, screens, demo and networking around the snekrs engine
+// (only built with the default `terminal` feature, which brings in crossterm)
+mod app;
+mod bench;
+mod chat;
+mod cli;
+mod config_file;
+mod demo;
+mod editor;
+#[cfg(target_os = "linux")]
+mod gamepad;
+#[cfg(feature = "gif")]
+mod gif;
+mod input;
+mod keymap;
+mod leaderboard;
+mod lobby;
+mod menu;
+#[cfg(feature = "rodio")]
+mod music;
+mod net;
+mod play;
+mod render;
+mod skin;
+mod sound;
+mod theme;
+#[cfg(feature = "tui")]
+mod tui;
+mod tutorial;
+mod udp;
+#[cfg(feature = "window")]
+mod window;
 
-fn main() -> std::io::Result<()> {
-    let mut game = game::Game::new();
-    game.run()?;
+// Design Decision: The engine modules are brought in at the crate root, so the frontend can
+// keep addressing them as crate::game and friends
+use snekrs::{
+    achievements, ai, cast, challenge, config, daily, difficulty, entity, events, frame, game, ghost, headless, lang, level, log, mode, mods, paths,
+    powerup, rating, replay, runs, scores, state, stats, stream,
+};
+#[cfg(feature = "lua")]
+use snekrs::lua;
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::Print,
+    terminal::{Clear, ClearType},
+};
+use std::io::IsTerminal;
+
+// Counts allocations for `snekrs bench`
+#[global_allocator]
+static ALLOCATOR: bench::CountingAllocator = bench::CountingAllocator;
+
+// Offers to pick up the game saved when the last one was quit, discarding the save once answered
+fn offer_resume() -> std::io::Result<Option<game::Game>> {
+    let path = replay::suspended_path();
+    let Ok(saved) = replay::Replay::load(&path) else {
+        return Ok(None);
+    };
+    if !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+    let game_screen = play::enter_game_screen()?;
+    let text = lang::text();
+    execute!(std::io::stdout(), Clear(ClearType::All), MoveTo(2, 1), Print(text.saved_game))?;
+    let choice = menu::Menu::new(&[text.resume, text.new_game], 2, 3).choose();
+    game_screen.leave()?;
+    std::fs::remove_file(&path)?;
+
+    // Esc starts fresh too, so the save never blocks a new game
+    Ok((choice? == Some(0)).then(|| game::Game::resume(saved)))
+}
+
+// Plays the tutorial on its own game screen
+fn run_tutorial(config: &config_file::Config) -> std::io::Result<()> {
+    let game_screen = play::enter_game_screen()?;
+    let result = tutorial::run(config);
+    game_screen.leave()?;
+    result
+}
+
+// Plays a game with no terminal I/O and prints each snake's final score on one line (the team
+// score in co-op)
+// Design Decision: Every snake is handed to the computer (or steered by a replay's inputs), so
+// nothing ever waits on a key
+fn run_headless(cli: &cli::Cli, settings: config::GameConfig, board: game::Board) -> std::io::Result<()> {
+    let game = match &cli.command {
+        cli::Command::Replay(path) => {
+            let path = path.clone().unwrap_or_else(replay::default_path);
+            let replay = replay::Replay::load(&path)?;
+            let mut game = headless::start(&replay);
+            if let Some(path) = &cli.emit_state {
+                game.set_state_stream(stream::StateStream::create(path)?);
+            }
+            headless::run(&mut game, &replay.inputs, replay.ticks);
+            game
+        }
+        _ => {
+            let seed = cli.seed.unwrap_or_else(rand::random);
+            let players = settings.mode.players().unwrap_or(cli.players);
+            let mut game = game::Game::new(board, players, seed, settings);
+            for player in 0..players {
+                game.hand_to_ai(player, cli.difficulty);
+            }
+            if let Some(path) = &cli.emit_state {
+                game.set_state_stream(stream::StateStream::create(path)?);
+            }
+            headless::run(&mut game, &[], headless::MAX_TICKS);
+            if let Some(path) = &cli.record {
+                game.replay().save(path)?;
+            }
+            game
+        }
+    };
+    // Co-op snakes share one team score, so that's the result rather than a score per snake
+    if game.settings().mode == mode::Mode::Coop {
+        println!("{}", game.state().score());
+        return Ok(());
+    }
+    let scores: Vec<String> = game.state().snakes.iter().map(|snake| snake.score.to_string()).collect();
+    println!("{}", scores.join(" "));
     Ok(())
-}
\ No newline at end of file
+}
+
+fn main() -> std::io::Result<()> {
+    // See `snekrs --help` (cli.rs) for every command and flag
+    let cli = cli::Cli::parse();
+    if let Some(path) = &cli.log {
+        log::open(path)?;
+    }
+    if cli.uses_terminal() {
+        play::install_panic_hook();
+    }
+
+    // Defaults for board size, pace, keys and colors come from config.toml when there is one,
+    // and command-line flags override them
+    let mut config = config_file::Config::load()?;
+    if let Some(language) = config.language {
+        lang::set(language);
+    }
+    if let Some(controls) = cli.controls {
+        config.keys.add_aliases(controls);
+    }
+    if let Some(scheme) = cli.theme {
+        config.theme = scheme.theme();
+    }
+    if cli.emoji {
+        config.theme.glyphs = theme::Glyphs::emoji();
+    }
+    if let Some(renderer) = cli.renderer {
+        config.theme.renderer = renderer;
+    }
+    if !cli.color {
+        config.theme.color = false;
+    }
+    config.theme.skin = skin::Skin::load();
+    // The board fills the terminal unless a size is configured or given on the command line
+    if config.fit && cli.width.is_none() && cli.height.is_none() && cli.uses_terminal() {
+        // TCP hosts keep the chat in view
+        let chat_rows = if matches!(cli.command, cli::Command::Host) && !cli.udp { chat::CHAT_ROWS } else { 0 };
+        if let Some((width, height)) = config_file::fitted_board(&config.theme, chat_rows) {
+            (config.game.width, config.game.height) = (width, height);
+        }
+    }
+    let settings = cli.game_config(config.game.clone());
+    let board = match (&cli.level, &cli.art) {
+        (Some(path), _) => game::Board::Custom(level::Level::load(path)?),
+        // The editor imports the art itself, to start the level it edits from
+        (None, Some(path)) if !matches!(cli.command, cli::Command::Edit(_)) => game::Board::Custom(level::Level::load_art(path)?),
+        _ if cli.campaign => game::Board::Campaign,
+        _ if cli.maze => game::Board::Maze,
+        _ => game::Board::Classic,
+    };
+
+    match &cli.command {
+        _ if cli.headless => return run_headless(&cli, settings, board),
+        cli::Command::Demo => return demo::run(cli.difficulty, settings, &config),
+        cli::Command::Bench(ticks) => {
+            bench::run(*ticks, board, settings, cli.seed.unwrap_or(0));
+            return Ok(());
+        }
+        cli::Command::Lobby => {
+            return match lobby::pick()? {
+                Some(listing) if listing.udp => udp::join(&listing.address.to_string(), &config),
+                Some(listing) => net::join(&listing.address.to_string(), &config),
+                None => Ok(()),
+            };
+        }
+        cli::Command::Join(address) if cli.udp => return udp::join(address, &config),
+        cli::Command::Join(address) => return net::join(address, &config),
+        cli::Command::Tutorial => return run_tutorial(&config),
+        #[cfg(feature = "window")]
+        cli::Command::Window => return window::run(&cli, &config, settings, board),
+        // Rejected by Cli::parse in builds without the window
+        #[cfg(not(feature = "window"))]
+        cli::Command::Window => unreachable!(),
+        cli::Command::Edit(path) => return editor::run(path, cli.art.as_deref(), settings, &config),
+        cli::Command::Replay(path) => {
+            let path = path.clone().unwrap_or_else(replay::default_path);
+            #[cfg(feature = "gif")]
+            if let Some(gif_path) = &cli.gif {
+                let frames = gif::export(&replay::Replay::load(&path)?, &config.theme, cli.speed, gif_path)?;
+                println!("{}", (lang::text().gif_written)(frames, &gif_path.display().to_string()));
+                return Ok(());
+            }
+            return play::play_replay(&path, cli.speed, &config, cli.cast.as_deref());
+        }
+        cli::Command::Stats => {
+            let stats = stats::Stats::load();
+            println!("{}", stats.summary());
+            if let Some(runs) = runs::summary() {
+                println!("{runs}");
+            }
+            if let Some(ratings) = rating::Ratings::load().summary() {
+                println!("\n{ratings}");
+            }
+            println!("\n{}", lang::text().achievements);
+            for (achievement, unlocked) in achievements::Achievements::load(stats.food()).list() {
+                let mark = if unlocked { "x" } else { " " };
+                let reward = skin::Skin::reward(achievement.id).map(|skin| format!(" ({})", (lang::text().unlocks_skin)(skin.name()))).unwrap_or_default();
+                println!("[{mark}] {}: {}{reward}", achievement.name, achievement.description);
+            }
+            return Ok(());
+        }
+        cli::Command::Play | cli::Command::Host | cli::Command::Daily | cli::Command::Challenge(_) => {}
+    }
+    let hosting = matches!(cli.command, cli::Command::Host);
+
+    let mut session = app::Session::new(&cli, &config, settings, board)?;
+    if hosting {
+        let mut game = session.new_game();
+        if cli.udp {
+            udp::host(&mut game, cli.port)?;
+        } else {
+            net::host(&mut game, cli.port)?;
+        }
+        for note in session.finish(&game) {
+            println!("{note}");
+        }
+        return Ok(());
+    }
+
+    // The very first bare launch walks through the basics before anything else
+    if cli.bare && !tutorial::seen() && std::io::stdin().is_terminal() {
+        run_tutorial(&config)?;
+    }
+    // Quitting with 'q' saves the game, and launching with no arguments offers to resume it
+    let resumed = if cli.bare { offer_resume()? } else { None };
+    session.run(resumed)
+}
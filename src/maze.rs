@@ -0,0 +1,88 @@
+// Procedural maze generation
+// Design Decision: Mazes are produced as a Level so the game treats them exactly like loaded boards
+use crate::game::Position;
+use crate::level::Level;
+use rand::{seq::SliceRandom, Rng};
+
+// Fraction of remaining interior walls knocked out after carving
+// Design Decision: A perfect maze has no loops, which traps a growing snake; a few shortcuts fix that
+const BRAID_CHANCE: f64 = 0.15;
+
+// Generates a maze with the recursive backtracker algorithm
+// Design Decision: Maze cells sit on odd coordinates, the even ones between them are walls to carve
+// The caller's RNG drives every choice so seeded runs get the same maze
+pub fn generate(width: u16, height: u16, rng: &mut impl Rng) -> Level {
+    let (w, h) = (width as usize, height as usize);
+    let mut open = vec![false; w * h];
+
+    // Explicit stack instead of recursion so large boards can't overflow
+    let start = (1usize, 1usize);
+    open[start.1 * w + start.0] = true;
+    let mut stack = vec![start];
+
+    while let Some(&(x, y)) = stack.last() {
+        let mut neighbours: Vec<(usize, usize)> = Vec::with_capacity(4);
+        if x >= 3 {
+            neighbours.push((x - 2, y));
+        }
+        if y >= 3 {
+            neighbours.push((x, y - 2));
+        }
+        if x + 2 < w - 1 {
+            neighbours.push((x + 2, y));
+        }
+        if y + 2 < h - 1 {
+            neighbours.push((x, y + 2));
+        }
+        neighbours.retain(|&(nx, ny)| !open[ny * w + nx]);
+
+        match neighbours.choose(rng) {
+            Some(&(nx, ny)) => {
+                // Carve through the wall between the current cell and the chosen neighbour
+                open[((y + ny) / 2) * w + (x + nx) / 2] = true;
+                open[ny * w + nx] = true;
+                stack.push((nx, ny));
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    // Knock out some walls separating two corridors to add loops
+    for y in 1..h - 1 {
+        for x in 1..w - 1 {
+            if open[y * w + x] {
+                continue;
+            }
+            let horizontal = open[y * w + x - 1] && open[y * w + x + 1];
+            let vertical = open[(y - 1) * w + x] && open[(y + 1) * w + x];
+            if (horizontal || vertical) && rng.gen_bool(BRAID_CHANCE) {
+                open[y * w + x] = true;
+            }
+        }
+    }
+
+    // Spawn in the open cell closest to the center that has room to move right
+    let center = (w / 2, h / 2);
+    let spawn = (1..h - 1)
+        .flat_map(|y| (1..w - 2).map(move |x| (x, y)))
+        .filter(|&(x, y)| open[y * w + x] && open[y * w + x + 1])
+        .min_by_key(|&(x, y)| x.abs_diff(center.0) + y.abs_diff(center.1))
+        .unwrap_or(start);
+
+    let walls = (1..h - 1)
+        .flat_map(|y| (1..w - 1).map(move |x| (x, y)))
+        .filter(|&(x, y)| !open[y * w + x])
+        .map(|(x, y)| Position { x: x as u16, y: y as u16 })
+        .collect();
+
+    Level {
+        width,
+        height,
+        walls,
+        spawn: Position { x: spawn.0 as u16, y: spawn.1 as u16 },
+        food: Vec::new(),
+        portals: Vec::new(),
+    }
+}
@@ -0,0 +1,136 @@
+// Clickable menus for the screens between games
+// Design Decision: One list widget drives every menu, so keyboard navigation (arrows, Enter,
+// number keys) and mouse clicks behave the same wherever a choice is offered
+use crate::input;
+use crossterm::{
+    cursor::MoveTo,
+    event::{Event, KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    execute,
+    style::{Attribute, Print, SetAttribute},
+};
+use std::io::{stdout, Write};
+
+// A vertical list of entries drawn at a fixed spot on screen
+pub(crate) struct Menu<'a> {
+    items: &'a [&'a str],
+    shortcuts: &'a [char],  // Letter that picks each entry straight away, in entry order
+    selected: usize,  // Highlighted entry
+    x: u16,           // Column of the first entry's marker
+    y: u16,           // Row of the first entry
+}
+
+impl<'a> Menu<'a> {
+    // A menu with its first entry at (x, y), starting on the first entry
+    pub(crate) fn new(items: &'a [&'a str], x: u16, y: u16) -> Self {
+        Menu { items, shortcuts: &[], selected: 0, x, y }
+    }
+
+    // Starts with another entry highlighted, e.g. the one picked last time
+    pub(crate) fn with_selected(mut self, index: usize) -> Self {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+        self
+    }
+
+    // Gives entries single-letter shortcuts, matched case-insensitively
+    pub(crate) fn with_shortcuts(mut self, shortcuts: &'a [char]) -> Self {
+        self.shortcuts = shortcuts;
+        self
+    }
+
+    // Waits for the player to pick an entry, returning its index (None = backed out with Esc)
+    // Expects raw mode and mouse capture, which play::enter_game_screen turns on
+    pub(crate) fn choose(mut self) -> std::io::Result<Option<usize>> {
+        self.draw()?;
+        loop {
+            if let Some(choice) = self.handle(&input::read()?)? {
+                return Ok(choice);
+            }
+        }
+    }
+
+    // Applies one input event, redrawing if the highlight moved, and returns the player's
+    // choice once they've made one (Some(None) = backed out with Esc)
+    // Design Decision: Split out of choose() so screens that refresh their entries while
+    // waiting can drive the menu from their own event loop
+    pub(crate) fn handle(&mut self, event: &Event) -> std::io::Result<Option<Option<usize>>> {
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(self.items.len().saturating_sub(1)),
+                KeyCode::Enter | KeyCode::Char(' ') if !self.items.is_empty() => return Ok(Some(Some(self.selected))),
+                KeyCode::Esc => return Ok(Some(None)),
+                KeyCode::Char(c) if self.shortcuts.contains(&c.to_ascii_lowercase()) => {
+                    return Ok(Some(self.shortcuts.iter().position(|&key| key == c.to_ascii_lowercase())));
+                }
+                KeyCode::Char(c) => {
+                    let number = c.to_digit(10).unwrap_or(0) as usize;
+                    if (1..=self.items.len()).contains(&number) {
+                        return Ok(Some(Some(number - 1)));
+                    }
+                }
+                _ => {}
+            },
+            Event::Mouse(mouse_event) => {
+                let Some(index) = self.entry_at(*mouse_event) else {
+                    return Ok(None);
+                };
+                match mouse_event.kind {
+                    MouseEventKind::Down(MouseButton::Left) => return Ok(Some(Some(index))),
+                    MouseEventKind::Moved => self.selected = index,
+                    _ => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        }
+        self.draw()?;
+        Ok(None)
+    }
+
+    // Index of the highlighted entry
+    pub(crate) fn selected(&self) -> usize {
+        self.selected
+    }
+
+    // Entry under the mouse pointer, if any
+    fn entry_at(&self, mouse_event: MouseEvent) -> Option<usize> {
+        let index = usize::from(mouse_event.row.checked_sub(self.y)?);
+        let item = self.items.get(index)?;
+        let width = item.chars().count() as u16 + 2;  // Marker and space before the label
+        (self.x..self.x + width).contains(&mouse_event.column).then_some(index)
+    }
+
+    // Draws every entry, numbered, with the selected one highlighted
+    pub(crate) fn draw(&self) -> std::io::Result<()> {
+        #[cfg(feature = "tui")]
+        return crate::tui::draw_menu(self.items, self.selected, self.x, self.y);
+        #[cfg(not(feature = "tui"))]
+        self.print()
+    }
+
+    // Prints every entry at its spot, the selected one marked and reversed
+    #[cfg_attr(feature = "tui", allow(dead_code))]
+    fn print(&self) -> std::io::Result<()> {
+        let mut stdout = stdout();
+        for (index, item) in self.items.iter().enumerate() {
+            let row = self.y + index as u16;
+            if index == self.selected {
+                execute!(
+                    stdout,
+                    MoveTo(self.x, row),
+                    Print("> "),
+                    SetAttribute(Attribute::Reverse),
+                    Print(item),
+                    SetAttribute(Attribute::Reset),
+                )?;
+            } else {
+                execute!(stdout, MoveTo(self.x, row), Print(format!("  {item}")))?;
+            }
+        }
+        stdout.flush()
+    }
+}
+
+// Whether an input event should dismiss a "press any key" screen
+pub(crate) fn is_dismissal(event: &Event) -> bool {
+    matches!(event, Event::Key(_) | Event::Mouse(MouseEvent { kind: MouseEventKind::Down(_), .. }))
+}
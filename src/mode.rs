@@ -0,0 +1,93 @@
+// Game modes that change the rules of a round
+// Design Decision: A mode is part of GameConfig like the difficulty, so replays, ghosts and
+// high scores keep runs under different rules apart
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Mode {
+    Standard,    // The classic rules
+    Fleeing,     // Food runs away from the snakes every few ticks
+    TimeAttack,  // Score as much as possible before a fixed clock runs out
+    Survival,    // The walls close in one ring at a time
+    Zen,         // No crashes: the border wraps and biting yourself only costs length
+    Hardcore,    // Top speed from the first move, no saving mid-run, every run logged
+    Royale,      // Battle royale: a damaging zone closes in from the edges
+    Practice,    // The classic rules, but the last few seconds can be rewound, crashes included
+    Coop,        // Two snakes on one keyboard share a score; the run ends when either crashes
+}
+
+impl Mode {
+    // Parses a CLI or config name (standard, fleeing, time-attack, survival, zen, hardcore, royale,
+    // practice, coop)
+    pub fn parse(name: &str) -> Option<Mode> {
+        match name {
+            "standard" => Some(Mode::Standard),
+            "fleeing" => Some(Mode::Fleeing),
+            "time-attack" => Some(Mode::TimeAttack),
+            "survival" => Some(Mode::Survival),
+            "zen" => Some(Mode::Zen),
+            "hardcore" => Some(Mode::Hardcore),
+            "royale" => Some(Mode::Royale),
+            "practice" => Some(Mode::Practice),
+            "coop" => Some(Mode::Coop),
+            _ => None,
+        }
+    }
+
+    // CLI name, also used in replay files
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Standard => "standard",
+            Mode::Fleeing => "fleeing",
+            Mode::TimeAttack => "time-attack",
+            Mode::Survival => "survival",
+            Mode::Zen => "zen",
+            Mode::Hardcore => "hardcore",
+            Mode::Royale => "royale",
+            Mode::Practice => "practice",
+            Mode::Coop => "coop",
+        }
+    }
+
+    // The next mode, wrapping around, for the Settings screen
+    pub fn next(self) -> Mode {
+        match self {
+            Mode::Standard => Mode::Fleeing,
+            Mode::Fleeing => Mode::TimeAttack,
+            Mode::TimeAttack => Mode::Survival,
+            Mode::Survival => Mode::Zen,
+            Mode::Zen => Mode::Hardcore,
+            Mode::Hardcore => Mode::Royale,
+            Mode::Royale => Mode::Practice,
+            Mode::Practice => Mode::Coop,
+            Mode::Coop => Mode::Standard,
+        }
+    }
+
+    // Snakes a game in this mode always has, whatever the opponent setting (None = it's up
+    // to the player)
+    pub fn players(self) -> Option<usize> {
+        match self {
+            Mode::Coop => Some(2),
+            _ => None,
+        }
+    }
+
+    // Game time a round lasts before the clock ends it (None = until someone crashes)
+    pub fn time_limit(self) -> Option<Duration> {
+        match self {
+            Mode::TimeAttack => Some(Duration::from_secs(120)),
+            _ => None,
+        }
+    }
+
+    // Game time between the walls (or the zone) closing in by one ring (None = the board never shrinks)
+    pub fn shrink_interval(self) -> Option<Duration> {
+        match self {
+            Mode::Survival => Some(Duration::from_secs(30)),
+            Mode::Royale => Some(Duration::from_secs(10)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// Game mods: hooks that bend the rules without touching the update loop
+// Design Decision: A mod is a trait object the state calls at fixed points (each tick, each
+// bite, each food spawn), so embedding crates can add their own rules with Game::add_mod;
+// the built-in ones are named so the config can stack them and replays can record them
+use crate::food::FoodKind;
+use crate::game::Position;
+use crate::snake::Snake;
+use crate::state::GameState;
+
+// Ticks between the tail segments hunger takes from every snake
+const HUNGER_INTERVAL: u64 = 40;
+
+// Extra segments a big meal grows the snake by, on top of the usual one
+const BIG_MEAL_GROWTH: usize = 2;
+
+// Food about to be placed on the board, which mods may move or change
+pub struct Spawn {
+    pub pos: Position,
+    pub kind: FoodKind,
+}
+
+// Rule hooks a mod can implement; every one defaults to leaving the game alone
+// Design Decision: Mods are cloned along with the state they belong to (see state::step), so
+// each one has to be Clone; deriving it is all ModClone asks for
+pub trait GameMod: ModClone {
+    // Runs after every tick the board moved on, unless that tick ended the game
+    fn on_tick(&mut self, _state: &mut GameState) {}
+
+    // Runs as a snake eats, after the food took effect, and returns the points to award
+    fn on_food_eaten(&mut self, _snake: &mut Snake, _kind: FoodKind, points: u32) -> u32 {
+        points
+    }
+
+    // Runs before food is placed; a move onto a cell the food can't use is ignored
+    fn modify_spawn(&mut self, _spawn: &mut Spawn, _state: &GameState) {}
+}
+
+// Copies a boxed mod, implemented for every mod that is Clone
+pub trait ModClone {
+    fn clone_mod(&self) -> Box<dyn GameMod>;
+}
+
+impl<T: GameMod + Clone + 'static> ModClone for T {
+    fn clone_mod(&self) -> Box<dyn GameMod> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn GameMod> {
+    fn clone(&self) -> Self {
+        self.clone_mod()
+    }
+}
+
+// The mods that ship with the game
+#[derive(Clone, Copy, PartialEq)]
+pub enum ModKind {
+    NoPoison,      // Poison never spawns, plain food takes its place
+    DoublePoints,  // Every bite scores twice as much
+    BigMeals,      // Every bite grows the snake by three segments instead of one
+    Hunger,        // Every snake loses a tail segment every few seconds
+}
+
+impl ModKind {
+    // Every built-in mod
+    pub const ALL: [ModKind; 4] = [ModKind::NoPoison, ModKind::DoublePoints, ModKind::BigMeals, ModKind::Hunger];
+
+    // Parses a config or replay name (no-poison, double-points, big-meals, hunger)
+    pub fn parse(name: &str) -> Option<ModKind> {
+        match name {
+            "no-poison" => Some(ModKind::NoPoison),
+            "double-points" => Some(ModKind::DoublePoints),
+            "big-meals" => Some(ModKind::BigMeals),
+            "hunger" => Some(ModKind::Hunger),
+            _ => None,
+        }
+    }
+
+    // Config name, also used in replay files and score labels
+    pub fn name(self) -> &'static str {
+        match self {
+            ModKind::NoPoison => "no-poison",
+            ModKind::DoublePoints => "double-points",
+            ModKind::BigMeals => "big-meals",
+            ModKind::Hunger => "hunger",
+        }
+    }
+
+    // A fresh instance of the mod, ready for a new game
+    pub fn build(self) -> Box<dyn GameMod> {
+        match self {
+            ModKind::NoPoison => Box::new(NoPoison),
+            ModKind::DoublePoints => Box::new(DoublePoints),
+            ModKind::BigMeals => Box::new(BigMeals),
+            ModKind::Hunger => Box::new(Hunger { ticks: 0 }),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct NoPoison;
+
+impl GameMod for NoPoison {
+    fn modify_spawn(&mut self, spawn: &mut Spawn, _state: &GameState) {
+        if spawn.kind == FoodKind::Poison {
+            spawn.kind = FoodKind::Normal;
+        }
+    }
+}
+
+#[derive(Clone)]
+struct DoublePoints;
+
+impl GameMod for DoublePoints {
+    fn on_food_eaten(&mut self, _snake: &mut Snake, _kind: FoodKind, points: u32) -> u32 {
+        points * 2
+    }
+}
+
+#[derive(Clone)]
+struct BigMeals;
+
+impl GameMod for BigMeals {
+    fn on_food_eaten(&mut self, snake: &mut Snake, kind: FoodKind, points: u32) -> u32 {
+        // The new segments stack on the tail and unfold as the snake moves on
+        if kind != FoodKind::Poison {
+            let tail = *snake.body.back().unwrap();
+            snake.body.extend([tail; BIG_MEAL_GROWTH]);
+        }
+        points
+    }
+}
+
+#[derive(Clone)]
+struct Hunger {
+    ticks: u64,  // Ticks since the game started
+}
+
+impl GameMod for Hunger {
+    fn on_tick(&mut self, state: &mut GameState) {
+        self.ticks += 1;
+        if self.ticks.is_multiple_of(HUNGER_INTERVAL) {
+            for snake in &mut state.snakes {
+                if snake.body.len() > 1 {
+                    snake.body.pop_back();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+// Background music, in builds with `--features rodio`
+// Design Decision: The tune is written out as notes and played on a square and a triangle
+// voice, like the sound chips it imitates, so the binary carries a short score instead of a
+// sample file. The audio device is opened and held on a thread of its own and rodio mixes on
+// another, so nothing here can hold up a tick; without a device the game simply stays quiet
+use crate::sound::Volume;
+use rodio::{OutputStreamBuilder, Sink, Source};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+const SAMPLE_RATE: u32 = 22_050;
+const STEP_SAMPLES: u32 = SAMPLE_RATE / 8;  // One sixteenth note at 120 beats a minute
+const REST: u8 = 0;
+
+// The melody, as (MIDI note, sixteenths), over C, A minor, F and G
+const LEAD: [(u8, u32); 28] = [
+    (76, 2), (79, 2), (84, 2), (79, 2), (76, 2), (79, 2), (84, 4),
+    (76, 2), (81, 2), (84, 2), (81, 2), (76, 2), (81, 2), (84, 4),
+    (77, 2), (81, 2), (84, 2), (81, 2), (77, 2), (81, 2), (86, 4),
+    (79, 2), (83, 2), (86, 2), (83, 2), (79, 2), (REST, 2), (79, 4),
+];
+
+// The bass line under it, the same four bars long
+const BASS: [(u8, u32); 16] = [
+    (48, 4), (55, 4), (48, 4), (55, 4),
+    (45, 4), (52, 4), (45, 4), (52, 4),
+    (41, 4), (48, 4), (41, 4), (48, 4),
+    (43, 4), (50, 4), (43, 4), (50, 4),
+];
+
+// The music for a session; dropping it stops the music and closes the audio device
+pub(crate) struct Music {
+    volume: Sender<Volume>,
+}
+
+impl Music {
+    // Starts the tune looping at the given volume (a muted tune keeps its place, silently)
+    pub(crate) fn start(volume: Volume) -> Music {
+        let (sender, changes) = mpsc::channel::<Volume>();
+        thread::spawn(move || {
+            let Ok(mut stream) = OutputStreamBuilder::open_default_stream() else {
+                return;
+            };
+            // rodio reports the stream closing on stderr, which would land on the game screen
+            stream.log_on_drop(false);
+            let sink = Sink::connect_new(stream.mixer());
+            sink.set_volume(volume.gain());
+            sink.append(Tune::new());
+            // Runs until the session drops its end of the channel
+            for volume in changes {
+                sink.set_volume(volume.gain());
+            }
+        });
+        Music { volume: sender }
+    }
+
+    // Changes the volume, taking effect straight away
+    pub(crate) fn set_volume(&self, volume: Volume) {
+        let _ = self.volume.send(volume);
+    }
+}
+
+// One line of the score and where playback is in it
+struct Voice {
+    score: &'static [(u8, u32)],
+    wave: fn(f32) -> f32,
+    loudness: f32,
+    note: usize,   // Index into the score
+    elapsed: u32,  // Samples played of the current note
+    phase: f32,    // Position in the current wave cycle, 0 to 1
+}
+
+impl Voice {
+    fn new(score: &'static [(u8, u32)], wave: fn(f32) -> f32, loudness: f32) -> Voice {
+        Voice { score, wave, loudness, note: 0, elapsed: 0, phase: 0.0 }
+    }
+
+    // The voice's next sample, moving on to the next note (and round the loop) when one ends
+    // Design Decision: The last eighth of every note is silent, so repeated notes are heard as
+    // separate notes instead of one long one
+    fn sample(&mut self) -> f32 {
+        let (note, steps) = self.score[self.note];
+        let length = steps * STEP_SAMPLES;
+        let value = if note == REST || self.elapsed >= length - length / 8 {
+            0.0
+        } else {
+            self.phase = (self.phase + frequency(note) / SAMPLE_RATE as f32).fract();
+            (self.wave)(self.phase) * self.loudness
+        };
+        self.elapsed += 1;
+        if self.elapsed == length {
+            self.elapsed = 0;
+            self.note = (self.note + 1) % self.score.len();
+        }
+        value
+    }
+}
+
+// The whole tune, looping forever as a mono rodio source
+struct Tune {
+    lead: Voice,
+    bass: Voice,
+}
+
+impl Tune {
+    fn new() -> Tune {
+        Tune { lead: Voice::new(&LEAD, square, 0.12), bass: Voice::new(&BASS, triangle, 0.25) }
+    }
+}
+
+impl Iterator for Tune {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        Some(self.lead.sample() + self.bass.sample())
+    }
+}
+
+impl Source for Tune {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+// Pitch of a MIDI note in hertz (69 is the A above middle C)
+fn frequency(note: u8) -> f32 {
+    440.0 * 2f32.powf((f32::from(note) - 69.0) / 12.0)
+}
+
+fn square(phase: f32) -> f32 {
+    if phase < 0.5 { 1.0 } else { -1.0 }
+}
+
+fn triangle(phase: f32) -> f32 {
+    4.0 * (phase - 0.5).abs() - 1.0
+}
@@ -0,0 +1,522 @@
+// Networked two-player mode over TCP
+// Design Decision: The host runs the only simulation; the client is a thin terminal that
+// sends its direction and draws the cell changes it receives. Anyone who connects after the
+// second player gets the same updates as a spectator, and nothing they send is read
+//
+// Protocol: newline-terminated text messages
+//   host -> client: BOARD <w> <h> | CELLS x,y,c;x,y,c;... | STATUS <text> | OVER <text> | WATCH |
+//                   SEAT <token> | CHAT <who> <text>
+//   client -> host: HELLO | RESUME <token> (first line) | DIR <U|D|L|R> | TURN <L|R> | SAY <text> | QUIT
+use crate::chat::{self, Chat, CHAT_ROWS};
+use crate::config;
+use crate::config_file::Config;
+use crate::frame::Frame;
+use crate::game::{Direction, Game, Position, Turn};
+use crate::input;
+use crate::keymap::Action;
+use crate::lang;
+use crate::lobby::Beacon;
+use crate::play::{self, Play};
+use crate::render::Layout;
+use crossterm::{
+    cursor::MoveTo,
+    event::Event,
+    execute,
+    style::Print,
+    terminal::{Clear, ClearType},
+};
+use std::io::{stdout, BufRead, BufReader, BufWriter, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Port used when none is given
+pub const DEFAULT_PORT: u16 = 7878;
+
+// Longest the host waits on a spectator's connection before dropping them, so someone who
+// stops reading can't stall the game
+const SPECTATOR_TIMEOUT: Duration = Duration::from_millis(100);
+
+// How long player two has to reconnect after losing the connection before they forfeit
+const RECONNECT_GRACE: Duration = Duration::from_secs(30);
+
+// Longest the host waits for a newcomer's first line
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(500);
+
+// Messages exchanged between host and client
+enum Message {
+    Board { width: u16, height: u16 },
+    Cells(Vec<(u16, u16, char)>),
+    Status(String),
+    Over(String),
+    Watch,           // The game is full, so this client only watches
+    Seat(String),    // Player two's token for taking their seat back after a dropped connection
+    Hello,           // First line from a client joining afresh
+    Resume(String),  // First line from player two coming back, with their seat's token
+    Chat { who: String, text: String },  // A chat line for everyone's screen
+    Say(String),     // A chat message from player two, for the host to pass on
+    Dir(Direction),
+    Turn(Turn),
+    Quit,
+}
+
+impl Message {
+    // Serializes the message as a single protocol line (without the newline)
+    fn encode(&self) -> String {
+        match self {
+            Message::Board { width, height } => format!("BOARD {width} {height}"),
+            Message::Cells(cells) => {
+                let cells: Vec<String> = cells.iter().map(|(x, y, c)| format!("{x},{y},{c}")).collect();
+                format!("CELLS {}", cells.join(";"))
+            }
+            Message::Status(text) => format!("STATUS {text}"),
+            Message::Over(text) => format!("OVER {text}"),
+            Message::Watch => "WATCH".to_string(),
+            Message::Seat(token) => format!("SEAT {token}"),
+            Message::Hello => "HELLO".to_string(),
+            Message::Resume(token) => format!("RESUME {token}"),
+            Message::Chat { who, text } => format!("CHAT {who} {text}"),
+            Message::Say(text) => format!("SAY {text}"),
+            Message::Dir(direction) => format!("DIR {}", direction.code()),
+            Message::Turn(turn) => format!("TURN {}", turn.code()),
+            Message::Quit => "QUIT".to_string(),
+        }
+    }
+
+    // Parses a protocol line, returning None for anything malformed
+    fn parse(line: &str) -> Option<Message> {
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match kind {
+            // Sizes past the board limits would have the client allocate whatever a host asks
+            "BOARD" => {
+                let (width, height) = rest.split_once(' ')?;
+                let size = |value: &str| config::board_size(value.parse().ok()?).ok();
+                Some(Message::Board { width: size(width)?, height: size(height)? })
+            }
+            "CELLS" => {
+                let cells = rest
+                    .split(';')
+                    .map(|cell| {
+                        let mut parts = cell.splitn(3, ',');
+                        let x = parts.next()?.parse().ok()?;
+                        let y = parts.next()?.parse().ok()?;
+                        // Glyphs go straight to the terminal, so control characters would let a
+                        // host slip in escape sequences
+                        let glyph = parts.next()?.chars().next().filter(|glyph| !glyph.is_control())?;
+                        Some((x, y, glyph))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Message::Cells(cells))
+            }
+            "STATUS" => Some(Message::Status(rest.to_string())),
+            "OVER" => Some(Message::Over(rest.to_string())),
+            "WATCH" => Some(Message::Watch),
+            "SEAT" => Some(Message::Seat(rest.to_string())),
+            "HELLO" => Some(Message::Hello),
+            "RESUME" => Some(Message::Resume(rest.to_string())),
+            "CHAT" => {
+                let (who, text) = rest.split_once(' ')?;
+                Some(Message::Chat { who: who.to_string(), text: text.to_string() })
+            }
+            "SAY" => Some(Message::Say(rest.to_string())),
+            "DIR" => Some(Message::Dir(Direction::from_code(rest)?)),
+            "TURN" => Some(Message::Turn(Turn::from_code(rest)?)),
+            "QUIT" => Some(Message::Quit),
+            _ => None,
+        }
+    }
+}
+
+// Reads messages on a background thread so the game loop never blocks on the socket
+// Design Decision: The channel disconnects when the peer goes away, which ends the game
+fn spawn_reader(stream: TcpStream) -> Receiver<Message> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else { break };
+            if let Some(message) = Message::parse(&line) {
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+// Writes one message and flushes it straight away
+fn send(writer: &mut impl Write, message: Message) -> std::io::Result<()> {
+    writeln!(writer, "{}", message.encode())?;
+    writer.flush()
+}
+
+// Sends the cells that changed since the last frame plus the score line
+fn send_frame(writer: &mut impl Write, game: &Game, previous: &mut Vec<char>) -> std::io::Result<()> {
+    let state = game.state();
+    let (width, height) = (state.width(), state.height());
+    let frame: Vec<char> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| Position { x, y }))
+        .map(|pos| state.glyph_at(pos))
+        .collect();
+
+    let changed: Vec<(u16, u16, char)> = frame
+        .iter()
+        .enumerate()
+        .filter(|(index, glyph)| previous.get(*index) != Some(glyph))
+        .map(|(index, glyph)| ((index % width as usize) as u16, (index / width as usize) as u16, *glyph))
+        .collect();
+    if !changed.is_empty() {
+        send(writer, Message::Cells(changed))?;
+    }
+    send(writer, Message::Status(game.status_line()))?;
+    *previous = frame;
+    Ok(())
+}
+
+// Someone watching a hosted game: where their updates go, and the board as they last saw it
+struct Spectator {
+    outbox: BufWriter<TcpStream>,
+    previous: Vec<char>,
+}
+
+impl Spectator {
+    // Welcomes a new connection as a spectator, sending the whole board so far
+    fn admit(stream: TcpStream, game: &Game) -> std::io::Result<Spectator> {
+        stream.set_nodelay(true)?;
+        stream.set_write_timeout(Some(SPECTATOR_TIMEOUT))?;
+        let mut spectator = Spectator { outbox: BufWriter::new(stream), previous: Vec::new() };
+        send(&mut spectator.outbox, Message::Watch)?;
+        send(&mut spectator.outbox, Message::Board { width: game.state().width(), height: game.state().height() })?;
+        send_frame(&mut spectator.outbox, game, &mut spectator.previous)?;
+        Ok(spectator)
+    }
+}
+
+// Player two's connection to the host
+struct Seat {
+    inbox: Receiver<Message>,
+    outbox: BufWriter<TcpStream>,
+    previous: Vec<char>,  // Board as the player last saw it
+}
+
+impl Seat {
+    // Seats player two on a connection, handing them the token to come back with and the
+    // whole board so far
+    fn take(stream: TcpStream, game: &Game, token: &str) -> std::io::Result<Seat> {
+        stream.set_nodelay(true)?;
+        let inbox = spawn_reader(stream.try_clone()?);
+        let mut seat = Seat { inbox, outbox: BufWriter::new(stream), previous: Vec::new() };
+        send(&mut seat.outbox, Message::Seat(token.to_string()))?;
+        send(&mut seat.outbox, Message::Board { width: game.state().width(), height: game.state().height() })?;
+        send_frame(&mut seat.outbox, game, &mut seat.previous)?;
+        Ok(seat)
+    }
+}
+
+// Reads newcomers' greetings on threads of their own, handing each connection to the game
+// loop with its greeting (None if it never sent a readable one in time)
+// Design Decision: The whole greeting shares one deadline, so a connection trickling bytes
+// can't hold one up for longer than a silent one; and since the reading happens off the game
+// thread, neither can freeze the match
+fn spawn_greeter(stream: TcpStream, greeted: &Sender<(TcpStream, Option<Message>)>) {
+    let greeted = greeted.clone();
+    thread::spawn(move || {
+        let message = greeting(&stream);
+        let _ = greeted.send((stream, message));
+    });
+}
+
+// Reads a newcomer's greeting, giving up once HANDSHAKE_TIMEOUT has passed
+// Design Decision: Read a byte at a time rather than through a buffer, so nothing the
+// client sends after the greeting is swallowed before the seat's reader takes over
+fn greeting(stream: &TcpStream) -> Option<Message> {
+    let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+    stream.set_nonblocking(false).ok()?;
+    let mut line = Vec::new();
+    let mut byte = [0];
+    loop {
+        let left = deadline.checked_duration_since(Instant::now()).filter(|left| !left.is_zero())?;
+        stream.set_read_timeout(Some(left)).ok()?;
+        (&*stream).read_exact(&mut byte).ok()?;
+        if byte[0] == b'\n' || line.len() >= 64 {
+            break;
+        }
+        line.push(byte[0]);
+    }
+    stream.set_read_timeout(None).ok()?;
+    Message::parse(String::from_utf8(line).ok()?.trim_end())
+}
+
+// Hosts a game and waits for one player to join as player two; anyone joining after them
+// watches
+// Design Decision: The host is the only authority: player two's messages are requests it
+// checks like its own keys, and a player whose connection drops keeps their snake for a
+// grace window, with the game frozen, until they come back with their seat's token
+pub fn host(game: &mut Play, port: u16) -> std::io::Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    let beacon = Beacon::start(port, false, game);
+    println!("{}", (lang::text().hosting)(port));
+    let (stream, address) = listener.accept()?;
+    println!("{}", (lang::text().joined)(&address.to_string()));
+    beacon.fill();
+    let token = format!("{:016x}", rand::random::<u64>());
+    let mut seat = Some(Seat::take(stream, game, &token)?);
+    listener.set_nonblocking(true)?;
+    let (greeter, greetings) = mpsc::channel();
+    let mut spectators: Vec<Spectator> = Vec::new();
+    game.set_local_players(1);
+    game.enable_chat();
+
+    let game_screen = play::enter_game_screen()?;
+    game.draw()?;
+
+    let mut vacated = None;  // When player two's connection dropped, while they're away
+    let mut forfeited = false;
+    while !game.is_over() {
+        game.poll_input(Duration::from_millis(20))?;
+
+        // Player two coming back takes their seat again (even before the old connection is
+        // noticed to be gone); everyone else watches
+        while let Ok((stream, _)) = listener.accept() {
+            spawn_greeter(stream, &greeter);
+        }
+        while let Ok((stream, greeting)) = greetings.try_recv() {
+            match greeting {
+                Some(Message::Resume(presented)) if presented == token => {
+                    if let Ok(taken) = Seat::take(stream, game, &token) {
+                        seat = Some(taken);
+                        if vacated.take().is_some() {
+                            game.set_hint(None);
+                            game.start_countdown();
+                        }
+                    }
+                }
+                _ => {
+                    if let Ok(spectator) = Spectator::admit(stream, game) {
+                        spectators.push(spectator);
+                    }
+                }
+            }
+        }
+
+        // Chat from either player goes on the host's screen and out to everyone else's
+        let mut said: Vec<(&str, String)> = game.take_chat_messages().into_iter().map(|text| ("P1", text)).collect();
+        if let Some(taken) = &seat {
+            loop {
+                match taken.inbox.try_recv() {
+                    Ok(Message::Dir(direction)) => game.steer(1, direction),
+                    Ok(Message::Turn(turn)) => game.turn(1, turn),
+                    Ok(Message::Say(text)) => said.push(("P2", chat::clean(&text))),
+                    Ok(Message::Quit) => game.forfeit(1),
+                    Ok(_) => {}
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        seat = None;
+                        vacated = Some(Instant::now());
+                        break;
+                    }
+                }
+            }
+        }
+        for (who, text) in said.into_iter().filter(|(_, text)| !text.is_empty()) {
+            game.add_chat_line(who, &text);
+            let line = || Message::Chat { who: who.to_string(), text: text.clone() };
+            if let Some(taken) = &mut seat {
+                let _ = send(&mut taken.outbox, line());
+            }
+            spectators.retain_mut(|spectator| send(&mut spectator.outbox, line()).is_ok());
+            game.draw()?;
+        }
+
+        // Nothing moves while player two is away, and they forfeit once the grace window is up
+        if let Some(since) = vacated {
+            let left = RECONNECT_GRACE.saturating_sub(since.elapsed());
+            if left.is_zero() {
+                forfeited = true;
+                game.forfeit(1);
+                break;
+            }
+            game.set_hint(Some((lang::text().waiting_for_return)(left.as_secs() + 1)));
+            game.draw()?;
+            continue;
+        }
+
+        if game.tick_if_due()? {
+            game.draw()?;
+            // A failed send means the client is gone; the reader will report it next loop
+            if let Some(taken) = &mut seat {
+                let _ = send_frame(&mut taken.outbox, game, &mut taken.previous);
+            }
+            // Spectators who went away (or stopped reading) are dropped
+            spectators.retain_mut(|spectator| send_frame(&mut spectator.outbox, game, &mut spectator.previous).is_ok());
+        }
+    }
+
+    let message = if forfeited {
+        lang::text().opponent_disconnected.to_string()
+    } else {
+        game.final_message()
+    };
+    if let Some(taken) = &mut seat {
+        let _ = send(&mut taken.outbox, Message::Over(message.clone()));
+    }
+    for spectator in &mut spectators {
+        let _ = send(&mut spectator.outbox, Message::Over(message.clone()));
+    }
+    game_screen.leave()?;
+    println!("\n{message}");
+    Ok(())
+}
+
+// Opens a connection to a host, on the default port unless the address names one
+fn connect(address: &str) -> std::io::Result<TcpStream> {
+    let stream = if address.contains(':') {
+        TcpStream::connect(address)?
+    } else {
+        TcpStream::connect((address, DEFAULT_PORT))?
+    };
+    stream.set_nodelay(true)?;
+    Ok(stream)
+}
+
+// Tries to get back into the game after the connection dropped, until the host's grace
+// window is over or the player gives up with the quit key
+fn reconnect(address: &str, token: &str, config: &Config) -> Option<TcpStream> {
+    let started = Instant::now();
+    while started.elapsed() < RECONNECT_GRACE {
+        if let Ok(mut stream) = connect(address) {
+            if send(&mut stream, Message::Resume(token.to_string())).is_ok() {
+                return Some(stream);
+            }
+        }
+        if let Ok(Some(Event::Key(key_event))) = input::next(Duration::from_secs(1)) {
+            if config.keys.action(key_event.code, 1) == Some(Action::Quit) {
+                return None;
+            }
+        }
+    }
+    None
+}
+
+// Joins a hosted game and plays as player two with player one's keys from the config, or
+// watches when the host already has both players
+pub(crate) fn join(address: &str, config: &Config) -> std::io::Result<()> {
+    let mut outbox = connect(address)?;
+    send(&mut outbox, Message::Hello)?;
+    let mut inbox = spawn_reader(outbox.try_clone()?);
+
+    let game_screen = play::enter_game_screen()?;
+    let mut out = stdout();
+    execute!(out, Clear(ClearType::All), MoveTo(0, 0), Print(lang::text().waiting_for_host))?;
+    out.flush()?;
+
+    let mut frame = Frame::new(0, 0);
+    let mut layout = Layout::default();
+    let mut height = 0;  // Terminal rows the board takes
+    let mut hint = (lang::text().client_hint)(&config.keys.describe(0), &config.keys.key_label(Action::Quit));
+    let mut watching = false;
+    let mut token = None;  // Lets this client take its seat back after losing the connection
+    let mut chat = Chat::new(config.keys.key_label(Action::Chat));
+    let message = loop {
+        if let Ok(Some(Event::Key(key_event))) = input::next(Duration::from_millis(20)) {
+            match config.keys.action(key_event.code, 1) {
+                // While a chat message is being typed, every key goes to it
+                _ if chat.is_typing() => {
+                    if let Some(text) = chat.type_key(key_event.code) {
+                        let _ = send(&mut outbox, Message::Say(text));
+                    }
+                    chat.draw(&mut out, layout, height + 2)?;
+                }
+                // The host isn't listening to spectators, so leaving is just hanging up
+                Some(Action::Quit) if watching => break lang::text().stopped_watching.to_string(),
+                _ if watching => {}
+                Some(Action::Quit) => {
+                    let _ = send(&mut outbox, Message::Quit);
+                    break lang::text().left_game.to_string();
+                }
+                // Whoever joins steers with the keys they'd use as player one at home
+                Some(Action::Steer(0, direction)) => {
+                    let _ = send(&mut outbox, Message::Dir(direction));
+                }
+                // Only the host knows which way the snake is heading, so turns go over as-is
+                Some(Action::Turn(0, turn)) => {
+                    let _ = send(&mut outbox, Message::Turn(turn));
+                }
+                Some(Action::Chat) => {
+                    chat.start_typing();
+                    chat.draw(&mut out, layout, height + 2)?;
+                }
+                _ => {}
+            }
+        }
+
+        let mut outcome = None;
+        loop {
+            match inbox.try_recv() {
+                Ok(Message::Board { width, height: board_height }) => {
+                    frame = Frame::new(width, board_height);
+                    let (columns, rows) = config.theme.renderer.size(width, board_height, &config.theme);
+                    (height, layout) = (rows, Layout::centered(columns, rows + 2 + CHAT_ROWS));
+                    execute!(out, Clear(ClearType::All))?;
+                    chat.draw(&mut out, layout, height + 2)?;
+                }
+                Ok(Message::Cells(cells)) => {
+                    // Kept in a frame, since a renderer may draw several cells as one character
+                    for &(x, y, glyph) in &cells {
+                        frame.set(x, y, glyph, false);
+                    }
+                    let changed = cells.into_iter().map(|(x, y, _)| Position { x, y });
+                    config.theme.renderer.draw_cells(&mut out, layout, &frame, changed, &config.theme)?;
+                }
+                // The host's text is cleaned like chat, so it can't carry escape sequences
+                Ok(Message::Status(text)) => {
+                    execute!(
+                        out,
+                        layout.at(0, height),
+                        Clear(ClearType::UntilNewLine),
+                        Print(chat::clean(&text)),
+                        layout.at(0, height + 1),
+                        Print(&hint)
+                    )?;
+                }
+                Ok(Message::Over(text)) => {
+                    outcome = Some(chat::clean(&text));
+                    break;
+                }
+                Ok(Message::Chat { who, text }) => {
+                    chat.add(&who, &text);
+                    chat.draw(&mut out, layout, height + 2)?;
+                }
+                Ok(Message::Watch) => {
+                    watching = true;
+                    chat.set_read_only();
+                    hint = (lang::text().watching_hint)(&config.keys.key_label(Action::Quit));
+                }
+                Ok(Message::Seat(seat)) => token = Some(seat),
+                Ok(_) => {}
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // A player tries to get their seat back; spectators just leave
+                    let resumed = token.as_deref().and_then(|token| {
+                        execute!(out, layout.at(0, height + 1), Clear(ClearType::UntilNewLine), Print(lang::text().reconnecting)).ok()?;
+                        reconnect(address, token, config)
+                    });
+                    match resumed.map(|stream| stream.try_clone().map(|reader| (stream, reader))) {
+                        Some(Ok((stream, reader))) => (outbox, inbox) = (stream, spawn_reader(reader)),
+                        _ => outcome = Some(lang::text().host_lost.to_string()),
+                    }
+                    break;
+                }
+            }
+        }
+        out.flush()?;
+        if let Some(text) = outcome {
+            break text;
+        }
+    };
+
+    game_screen.leave()?;
+    println!("\n{message}");
+    Ok(())
+}
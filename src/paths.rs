@@ -0,0 +1,40 @@
+// Platform directories for files snekrs keeps between runs
+// Design Decision: Follows the usual per-OS conventions from environment variables, which
+// covers what we need without pulling in a directories crate
+use std::env;
+use std::path::PathBuf;
+
+// Directory for saved data (replays, scores), created on demand by callers
+pub fn data_dir() -> PathBuf {
+    if cfg!(windows) {
+        if let Some(appdata) = env::var_os("APPDATA") {
+            return PathBuf::from(appdata).join("snekrs");
+        }
+    } else if cfg!(target_os = "macos") {
+        if let Some(home) = env::var_os("HOME") {
+            return PathBuf::from(home).join("Library/Application Support/snekrs");
+        }
+    } else if let Some(data_home) = env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(data_home).join("snekrs");
+    } else if let Some(home) = env::var_os("HOME") {
+        return PathBuf::from(home).join(".local/share/snekrs");
+    }
+
+    // Last resort: next to wherever the game was started
+    PathBuf::from(".snekrs")
+}
+
+// Directory for settings and the high score table, created on demand by callers
+pub fn config_dir() -> PathBuf {
+    if cfg!(windows) || cfg!(target_os = "macos") {
+        // Both platforms keep config and data side by side
+        return data_dir();
+    }
+    if let Some(config_home) = env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(config_home).join("snekrs");
+    }
+    if let Some(home) = env::var_os("HOME") {
+        return PathBuf::from(home).join(".config/snekrs");
+    }
+    PathBuf::from(".snekrs")
+}
@@ -0,0 +1,1038 @@
+// A game on screen: the engine's Game with the terminal around it - drawing, keys, the
+// gamepad, chat and recordings
+// Design Decision: Play derefs to the Game it wraps, so the screens and the network code ask
+// the game about itself as before, and only drawing and local input live here
+use crossterm::{
+    cursor::{Hide, Show, MoveTo},  // Terminal cursor control
+    event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event, KeyCode}, // Keyboard, mouse and focus input
+    execute, queue,
+    terminal::{self, Clear, ClearType},
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
+};
+use std::cell::RefCell;
+use std::io::{stdout, Stdout, Write};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+use crate::achievements::Achievements;
+use crate::cast::Cast;
+use crate::chat::{Chat, CHAT_ROWS};
+use crate::config_file::Config;
+use crate::entity::{Entity, SPARKLE_GLYPH};
+use crate::events::GameEvent;
+use crate::frame::Frame;
+use crate::game::{Checkpoint, Direction, Game, Position};
+use crate::ghost::GHOST_GLYPH;
+use crate::input;
+use crate::keymap::{Action, Keymap};
+use crate::lang;
+use crate::log;
+use crate::powerup::PowerUp;
+use crate::replay::Replay;
+use crate::render::{Layout, Renderer};
+use crate::scores::HighScores;
+use crate::skin::Skin;
+use crate::theme::Theme;
+
+// Death animation: crashed snakes flash this many times, then fall apart from the tail in at
+// most this many steps
+const DEATH_FLASHES: usize = 3;
+const COLLAPSE_STEPS: usize = 20;
+
+// Item animations: wall-clock length of one animation frame, and frames in each half of the
+// food's pulse
+const ANIMATION_FRAME: Duration = Duration::from_millis(120);
+const PULSE_FRAMES: u64 = 4;
+
+// How long an achievement toast stays in the HUD
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+// Daily challenge results listed under the high score table
+const DAILY_RESULTS_SHOWN: usize = 7;
+
+// How long the next stage's title stays up between campaign boards
+const STAGE_TRANSITION: Duration = Duration::from_secs(2);
+
+// Replay playback speed limits, as multiples of the recorded pace
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 16.0;
+
+// The parts of the board an ordinary move can change, kept with the last drawn frame
+// Design Decision: A move only touches heads, tails and items, so when nothing bigger happened
+// (a snake shrinking, a power-up dimming it, a ring closing) just those cells are redrawn
+struct Outline {
+    lengths: Vec<usize>,   // Segments per snake
+    phasing: Vec<bool>,    // Whether each snake is drawn dimmed by the ghost power-up
+    ghost: Option<usize>,  // Length of the personal-best ghost while it's shown
+    rings: (u16, u16),     // Wall and zone rings closed in
+    hot: Vec<Position>,    // Heads, tails, food, power-up and the ghost's ends
+    dying: Option<Dying>,  // How far the death animation had got
+}
+
+impl Outline {
+    // Whether the board can have changed only in the hot cells since `earlier` was drawn
+    fn follows(&self, earlier: &Outline) -> bool {
+        let grew = |now: usize, then: usize| now == then || now == then + 1;
+        self.phasing == earlier.phasing
+            && self.rings == earlier.rings
+            && self.dying == earlier.dying
+            && self.lengths.len() == earlier.lengths.len()
+            && self.lengths.iter().zip(&earlier.lengths).all(|(&now, &then)| grew(now, then))
+            && match (self.ghost, earlier.ghost) {
+                (Some(now), Some(then)) => grew(now, then),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+// How much of the crashed snakes one frame of the death animation shows
+#[derive(Clone, Copy, PartialEq)]
+struct Dying {
+    hidden: bool,    // Blinked out while flashing
+    removed: usize,  // Segments fallen off the tail so far
+}
+
+// A game being played in this terminal
+pub struct Play {
+    game: Game,                         // The game itself
+    keys: Keymap,                       // Keys steering the local snakes
+    theme: Theme,                       // Colors used when drawing
+    popup_cells: Vec<Position>,         // Board cells the popups covered when last drawn
+    dying: Option<Dying>,               // Frame of the death animation being shown, if any
+    hint: Option<String>,               // Shown instead of the controls reminder, unless paused
+    toast: Rc<RefCell<Option<(String, Instant)>>>,  // Latest achievement notice and when it appeared, set from the game's events
+    chat: Option<Chat>,                 // Chat under the board (network games only)
+    said: Vec<String>,                  // Chat messages typed here, waiting to be sent
+    animation: (Instant, u64),          // When the item animations started, and the frame last drawn
+    gamepad: Option<Receiver<Direction>>,  // Directions from a connected gamepad, for player one
+    cast: Option<Cast>,                 // Recording of every frame drawn, if one was asked for
+    screen: Option<(Frame, Option<Outline>)>,  // Board as last drawn (None = the terminal shows something else), with its outline unless it has moved on unseen
+    terminal: Option<(u16, u16)>,       // Terminal size when the screen was last drawn
+    layout: Layout,                     // Where the board sits in the terminal
+    #[cfg(feature = "tui")]
+    widgets: Option<crate::tui::Screen>,  // Board and score panel as last drawn with widgets
+}
+
+impl Deref for Play {
+    type Target = Game;
+
+    fn deref(&self) -> &Game {
+        &self.game
+    }
+}
+
+impl DerefMut for Play {
+    fn deref_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+}
+
+impl Play {
+    // Puts a game on screen with the default keys and colors
+    pub fn new(game: Game) -> Self {
+        Play {
+            game,
+            keys: Keymap::default(),
+            theme: Theme::default(),
+            popup_cells: Vec::new(),
+            dying: None,
+            hint: None,
+            toast: Rc::default(),
+            chat: None,
+            said: Vec::new(),
+            animation: (Instant::now(), 0),
+            gamepad: None,
+            cast: None,
+            screen: None,
+            terminal: None,
+            layout: Layout::default(),
+            #[cfg(feature = "tui")]
+            widgets: None,
+        }
+    }
+
+    // Shows a chat under the board, started with the chat key, for network games
+    pub fn enable_chat(&mut self) {
+        self.chat = Some(Chat::new(self.keys.key_label(Action::Chat)));
+        self.screen = None;
+    }
+
+    // Adds a line to the chat, if there is one
+    pub fn add_chat_line(&mut self, who: &str, message: &str) {
+        if let Some(chat) = &mut self.chat {
+            chat.add(who, message);
+        }
+    }
+
+    // Chat messages typed here since the last call, for the network code to send
+    pub fn take_chat_messages(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.said)
+    }
+
+    // Replaces the controls reminder under the score with other text (None = the reminder)
+    pub fn set_hint(&mut self, hint: Option<String>) {
+        self.hint = hint;
+    }
+
+    // Uses the player's key bindings and colors
+    pub fn set_preferences(&mut self, keys: Keymap, theme: Theme) {
+        self.keys = keys;
+        self.theme = theme;
+        self.screen = None;
+    }
+
+    // Lets a gamepad steer player one alongside the keyboard
+    pub fn set_gamepad(&mut self, gamepad: Receiver<Direction>) {
+        self.gamepad = Some(gamepad);
+    }
+
+    // Hands the gamepad back once the game is done with it, for the next game
+    pub fn take_gamepad(&mut self) -> Option<Receiver<Direction>> {
+        self.gamepad.take()
+    }
+
+    // Lets player one earn achievements in this game, each new one shown as a toast
+    // Design Decision: Unlocks are checked against the progress every tick reports, so the
+    // engine never hears about achievements at all
+    pub fn track_achievements(&mut self, mut achievements: Achievements) {
+        let toast = Rc::clone(&self.toast);
+        self.game.subscribe(move |event| {
+            let GameEvent::Ticked { played, eaten, fill, .. } = *event else { return };
+            if let Some(achievement) = achievements.check(eaten, played, fill).last() {
+                let text = (lang::text().achievement)(achievement.name, achievement.description);
+                *toast.borrow_mut() = Some((text, Instant::now()));
+            }
+        });
+    }
+
+    // The latest achievement notice, while it's fresh enough to show
+    fn toast(&self) -> Option<String> {
+        self.toast.borrow().as_ref().filter(|(_, shown)| shown.elapsed() < TOAST_DURATION).map(|(text, _)| text.clone())
+    }
+
+    // Records every frame drawn from now on as an asciinema cast
+    pub fn set_cast(&mut self, cast: Cast) {
+        self.cast = Some(cast);
+    }
+
+    // The cast recorded so far, if the game is being recorded
+    pub fn cast(&self) -> Option<&Cast> {
+        self.cast.as_ref()
+    }
+
+    // Shows the upcoming stage between boards
+    // Design Decision: Blocking pause with input drained so stray keys don't steer the new stage
+    fn draw_stage_transition(&self) -> std::io::Result<()> {
+        let Some(campaign) = self.campaign() else {
+            return Ok(());
+        };
+        let (mut stdout, text) = (stdout(), lang::text());
+        execute!(
+            stdout,
+            Clear(ClearType::All),
+            MoveTo(2, 2),
+            Print((text.stage_cleared)(self.state().score())),
+            MoveTo(2, 4),
+            Print((text.level)(campaign.stage_number(), campaign.stage().name)),
+            MoveTo(2, 6),
+            Print(text.get_ready)
+        )?;
+        stdout.flush()?;
+
+        std::thread::sleep(STAGE_TRANSITION);
+        input::discard()
+    }
+
+    // Control reminder shown under the score
+    fn controls_hint(&self) -> String {
+        let (pause, quit) = (self.keys.key_label(Action::Pause), self.keys.key_label(Action::Quit));
+        let text = lang::text();
+        if let Some(hint) = self.hint.as_ref().filter(|_| !self.is_paused()) {
+            hint.clone()
+        } else if self.crashed() {
+            (text.crashed_hint)(&self.keys.key_label(Action::Rewind), &quit)
+        } else if self.local_players() == 0 {
+            text.demo_hint.to_string()
+        } else if self.is_paused() {
+            (text.resume_hint)(&pause, &quit)
+        } else if self.local_players() > 1 {
+            (text.duo_hint)(&self.keys.describe(0), &self.keys.describe(1), &pause, &quit)
+        } else if self.is_practice() {
+            let rewind = (text.rewind_hint)(&self.keys.key_label(Action::Rewind));
+            (text.solo_hint)(&self.keys.describe(0), &pause, &quit) + &rewind
+        } else {
+            (text.solo_hint)(&self.keys.describe(0), &pause, &quit)
+        }
+    }
+
+    // Text shown over the dimmed board while the game is frozen
+    fn overlay(&self) -> Option<String> {
+        if self.is_paused() {
+            return Some(format!(" {} ", lang::text().paused));
+        }
+        if self.crashed() {
+            return Some(format!(" {} ", lang::text().crashed));
+        }
+        self.countdown().map(|number| format!(" {number} "))
+    }
+
+    // The board as logical glyphs, with the ghost filled in and dimmed cells marked
+    pub fn frame(&self, dimmed: bool) -> Frame {
+        let state = self.state();
+        let mut frame = Frame::new(state.width(), state.height());
+        for y in 0..frame.height() {
+            for x in 0..frame.width() {
+                let (glyph, dim) = self.cell(Position { x, y });
+                frame.set(x, y, glyph, dim || dimmed);
+            }
+        }
+        if self.theme.skin != Skin::Classic {
+            let snake = state.snake(0);
+            for (index, segment) in snake.body.iter().enumerate() {
+                frame.set_tone(segment.x, segment.y, snake.tone(index));
+            }
+        }
+        frame
+    }
+
+    // Skin tone of one cell: set on player one's segments when a skin is worn, 0 elsewhere
+    fn tone(&self, pos: Position) -> u8 {
+        if self.theme.skin == Skin::Classic {
+            return 0;
+        }
+        let snake = self.state().snake(0);
+        snake.body.iter().position(|segment| *segment == pos).map_or(0, |index| snake.tone(index))
+    }
+
+    // Glyph shown in one cell, and whether it's dimmed
+    fn cell(&self, pos: Position) -> (char, bool) {
+        let state = self.state();
+        let glyph = match state.glyph_at(pos) {
+            // A steady theme shows items through the blink that warns they're about to expire
+            ' ' if self.theme.steady => state.entities().iter().find(|entity| entity.pos == pos).map_or(' ', Entity::glyph),
+            glyph => glyph,
+        };
+        let glyph = match glyph {
+            ' ' if self.ghost().is_some_and(|ghost| ghost.occupies(pos, self.stage())) => GHOST_GLYPH,
+            _ if self.is_fallen(pos) => ' ',
+            glyph => glyph,
+        };
+        let (glyph, pulsing) = self.animate(pos, glyph);
+        (glyph, pulsing || self.is_phasing(pos))
+    }
+
+    // Food as it looks in the current animation frame, and whether it's in the dim half of
+    // its pulse: freshly spawned food sparkles, and the rest pulses slowly
+    // Design Decision: Steady themes promise nothing on the board blinks, so they skip both
+    fn animate(&self, pos: Position, glyph: char) -> (char, bool) {
+        if self.theme.steady || matches!(glyph, ' ' | '#') {
+            return (glyph, false);
+        }
+        let frame = self.animation.1;
+        match self.state().entities().iter().find(|entity| entity.pos == pos && entity.food_kind().is_some()) {
+            Some(entity) if entity.glyph() != glyph => (glyph, false),
+            Some(entity) if entity.is_fresh() => (if frame.is_multiple_of(2) { SPARKLE_GLYPH } else { glyph }, false),
+            Some(_) => (glyph, (frame / PULSE_FRAMES) % 2 == 1),
+            None => (glyph, false),
+        }
+    }
+
+    // Animation frame the wall clock is on
+    fn animation_frame(&self) -> u64 {
+        (self.animation.0.elapsed().as_millis() / ANIMATION_FRAME.as_millis()) as u64
+    }
+
+    // Whether the item animations have moved on since the board was last drawn, so it wants
+    // drawing again even without a tick
+    // Design Decision: The animations keep their own wall clock rather than counting ticks, so
+    // they run at the same speed whatever the pace; behind an overlay the board is frozen
+    pub fn animation_due(&self) -> bool {
+        !self.theme.steady
+            && self.overlay().is_none()
+            && self.dying.is_none()
+            && self.animation_frame() != self.animation.1
+            && self.state().entities().iter().any(|entity| entity.food_kind().is_some())
+    }
+
+    // Whether the death animation has taken a crashed snake's segment off this cell
+    fn is_fallen(&self, pos: Position) -> bool {
+        let Some(dying) = self.dying else {
+            return false;
+        };
+        self.state().snakes.iter().filter(|snake| !snake.alive).any(|snake| {
+            let kept = snake.body.len().saturating_sub(dying.removed);
+            snake.body.iter().position(|segment| *segment == pos).is_some_and(|index| dying.hidden || index >= kept)
+        })
+    }
+
+    // Where an ordinary move can change the board, and what would make it more than that
+    fn outline(&self) -> Outline {
+        let (state, stage) = (self.state(), self.stage());
+        let snakes = &state.snakes;
+        let ghost = self.ghost().and_then(|ghost| ghost.ends(stage));
+        let mut hot: Vec<Position> = snakes.iter().flat_map(|snake| [snake.head(), *snake.body.back().unwrap()]).collect();
+        hot.extend(state.item_cells());
+        hot.extend(ghost.iter().flat_map(|&(_, head, tail)| [head, tail]));
+        Outline {
+            lengths: snakes.iter().map(|snake| snake.body.len()).collect(),
+            phasing: (0..snakes.len()).map(|index| self.effects().is_active(index, PowerUp::Phase)).collect(),
+            ghost: ghost.map(|(length, ..)| length),
+            rings: state.rings(),
+            hot,
+            dying: self.dying,
+        }
+    }
+
+    // Terminal size the board needs, when the window is currently smaller than that
+    // (never when the size is unknown, which some terminals report as 0x0)
+    fn too_small(&self) -> Option<(u16, u16)> {
+        let (columns, rows) = terminal::size().ok().filter(|&(columns, rows)| columns > 0 && rows > 0)?;
+        let (width, height) = self.theme.renderer.size(self.state().width(), self.state().height(), &self.theme);
+        let needed = (width, height + 2);  // The status and controls lines go below the board
+        (columns < needed.0 || rows < needed.1).then_some(needed)
+    }
+
+    // Where the board was last drawn in the terminal
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    // Terminal rows the board takes, with the status and controls lines right below
+    pub fn board_rows(&self) -> u16 {
+        self.theme.renderer.size(self.state().width(), self.state().height(), &self.theme).1
+    }
+
+    // Renders the game state to the terminal
+    // Design Decision: Only cells that changed since the last frame are printed, and everything
+    // is flushed in one write, so nothing flickers even over a slow SSH link. The terminal is
+    // only cleared when the board isn't already on screen
+    pub fn draw(&mut self) -> std::io::Result<()> {
+        let mut stdout = stdout();
+
+        // A resized terminal may have reflowed or dropped what was on it, and one that's too
+        // small would wrap the board into garbage, so it gets a notice instead
+        let size = terminal::size().ok();
+        if size != self.terminal {
+            self.terminal = size;
+            self.screen = None;
+        }
+        if let Some((width, height)) = self.too_small() {
+            let (columns, rows) = size.unwrap_or_default();
+            self.screen = None;
+            queue!(
+                stdout,
+                Clear(ClearType::All),
+                MoveTo(0, 0),
+                Print(lang::text().terminal_too_small),
+                MoveTo(0, 1),
+                Print((lang::text().needs_size)(width, height, columns, rows)),
+            )?;
+            return stdout.flush();
+        }
+        #[cfg(feature = "tui")]
+        return self.draw_widgets(&mut stdout);
+        #[cfg(not(feature = "tui"))]
+        self.draw_board(&mut stdout)
+    }
+
+    // Prints the board, popups, overlay and the lines under it cell by cell
+    #[cfg_attr(feature = "tui", allow(dead_code))]
+    fn draw_board(&mut self, stdout: &mut Stdout) -> std::io::Result<()> {
+        // Draw border, game area and entities (dimmed behind the pause overlay), centered
+        // along with the two lines under the board
+        let overlay = self.overlay();
+        let renderer = self.theme.renderer;
+        let (columns, height) = renderer.size(self.state().width(), self.state().height(), &self.theme);
+        if self.screen.is_none() {
+            let chat_rows = if self.chat.is_some() { CHAT_ROWS } else { 0 };
+            self.layout = Layout::centered(columns, height + 2 + chat_rows);
+        }
+        let layout = self.layout;
+        self.animation.1 = self.animation_frame();
+        let outline = self.outline();
+        // Cells last covered by popups are put back whatever else changed
+        let uncovered = std::mem::take(&mut self.popup_cells);
+        let frame = match self.screen.take() {
+            // Fast path: after an ordinary move only the outline's cells can have changed, so
+            // the rest of the board isn't even looked at
+            Some((mut frame, Some(drawn))) if overlay.is_none() && outline.follows(&drawn) => {
+                let changed: Vec<Position> = drawn.hot.iter().chain(&outline.hot)
+                    .filter(|pos| frame.get(pos.x, pos.y) != self.cell(**pos) || frame.tone(pos.x, pos.y) != self.tone(**pos))
+                    .copied()
+                    .collect();
+                for pos in &changed {
+                    let (glyph, dim) = self.cell(*pos);
+                    frame.set(pos.x, pos.y, glyph, dim);
+                    frame.set_tone(pos.x, pos.y, self.tone(*pos));
+                }
+                renderer.draw_cells(stdout, layout, &frame, changed.into_iter().chain(uncovered), &self.theme)?;
+                frame
+            }
+            // An overlay label covers cells the frame knows nothing about, so those frames are
+            // drawn in full
+            Some((previous, _)) if overlay.is_none() => {
+                let frame = self.frame(false);
+                let changed = frame.changed_since(&previous).into_iter().chain(uncovered);
+                renderer.draw_cells(stdout, layout, &frame, changed, &self.theme)?;
+                frame
+            }
+            Some(_) => {
+                let frame = self.frame(overlay.is_some());
+                renderer.draw(stdout, layout, &frame, &self.theme)?;
+                frame
+            }
+            None => {
+                let frame = self.frame(overlay.is_some());
+                queue!(stdout, Clear(ClearType::All))?;
+                renderer.draw(stdout, layout, &frame, &self.theme)?;
+                frame
+            }
+        };
+        // A frame under an overlay is dimmed and partly covered, so the next one is compared in full
+        self.screen = Some((frame, overlay.is_none().then_some(outline)));
+        if overlay.is_none() {
+            self.draw_popups(stdout)?;
+        }
+        if let Some(label) = &overlay {
+            let x = columns.saturating_sub(label.len() as u16) / 2;
+            queue!(
+                stdout,
+                SetAttribute(Attribute::Reset),
+                layout.at(x, height / 2),
+                SetAttribute(Attribute::Reverse),
+                Print(label),
+                SetAttribute(Attribute::Reset),
+            )?;
+        }
+
+        // Draw UI elements (score, campaign stage and controls), clearing what's left of the
+        // previous, possibly longer, lines
+        let status = self.status_line();
+        queue!(
+            stdout,
+            layout.at(0, height),
+            Print(&status),
+            Clear(ClearType::UntilNewLine),
+            layout.at(0, height + 1),
+        )?;
+
+        // A fresh achievement takes the controls line for a moment
+        let hint = match self.toast() {
+            Some(text) => {
+                match self.theme.color {
+                    true => queue!(stdout, SetForegroundColor(self.theme.golden), Print(&text), ResetColor)?,
+                    false => queue!(stdout, Print(&text))?,
+                }
+                text
+            }
+            None => {
+                let hint = self.controls_hint();
+                queue!(stdout, Print(&hint))?;
+                hint
+            }
+        };
+        queue!(stdout, Clear(ClearType::UntilNewLine))?;
+        if self.cast.is_some() {
+            if let Some((frame, _)) = &self.screen {
+                let (rows, width) = cast_rows(frame, overlay.as_deref(), [status, hint]);
+                if let Some(cast) = &mut self.cast {
+                    cast.record(rows, width);
+                }
+            }
+        }
+        if let Some(chat) = &self.chat {
+            chat.draw(stdout, layout, height + 2)?;
+        }
+
+        stdout.flush()
+    }
+
+    // Renders the board, popups, overlay and score panel as ratatui widgets
+    // Design Decision: The widget buffer is diffed against the screen, so the frame is simply
+    // rebuilt in full each time instead of tracking which cells the last move touched
+    #[cfg(feature = "tui")]
+    fn draw_widgets(&mut self, stdout: &mut Stdout) -> std::io::Result<()> {
+        use crate::tui::{self, Board, ScorePanel};
+        use ratatui::style::{Modifier, Style};
+        use ratatui::widgets::Widget;
+
+        let overlay = self.overlay();
+        let (columns, height) = self.theme.renderer.size(self.state().width(), self.state().height(), &self.theme);
+        if self.screen.is_none() {
+            let chat_rows = if self.chat.is_some() { CHAT_ROWS } else { 0 };
+            self.layout = Layout::centered(columns, height + 2 + chat_rows);
+            self.widgets = None;
+            queue!(stdout, Clear(ClearType::All))?;
+        }
+        let layout = self.layout;
+        self.animation.1 = self.animation_frame();
+        let frame = self.frame(overlay.is_some());
+        let status = self.status_line();
+        let (hint, golden) = match self.toast() {
+            Some(text) => (text, self.theme.color.then(|| self.theme.golden.into())),
+            None => (self.controls_hint(), None),
+        };
+        let popups = match (&overlay, self.theme.renderer) {
+            (None, Renderer::Text) => {
+                let cell_width = self.theme.cell_width();
+                self.game.popups().labels(self.state().width(), self.state().height(), cell_width)
+                    .into_iter()
+                    .map(|(pos, text)| (pos.x * cell_width, pos.y, text))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        let popup_style = match self.theme.color {
+            true => Style::default().fg(self.theme.golden.into()).add_modifier(Modifier::BOLD),
+            false => Style::default().add_modifier(Modifier::BOLD),
+        };
+        let (board, panel) = (layout.area(columns, height), layout.area(columns.max(status.len() as u16).max(hint.len() as u16), 2));
+        let panel = ratatui::layout::Rect { y: panel.y + height, ..panel };
+        let theme = &self.theme;
+        let widgets = self.widgets.get_or_insert_with(|| tui::Screen::new(board.union(panel)));
+        widgets.draw(|buf| {
+            Board { frame: &frame, theme }.render(board, buf);
+            for (x, y, text) in &popups {
+                tui::label(buf, board, *x, *y, text, popup_style);
+            }
+            if let Some(label) = &overlay {
+                let x = columns.saturating_sub(label.len() as u16) / 2;
+                tui::label(buf, board, x, height / 2, label, Style::default().add_modifier(Modifier::REVERSED));
+            }
+            ScorePanel { status: &status, hint: &hint, golden }.render(panel, buf);
+        })?;
+
+        if self.cast.is_some() {
+            let (rows, width) = cast_rows(&frame, overlay.as_deref(), [status, hint]);
+            if let Some(cast) = &mut self.cast {
+                cast.record(rows, width);
+            }
+        }
+        self.screen = Some((frame, None));
+        if let Some(chat) = &self.chat {
+            chat.draw(stdout, layout, height + 2)?;
+        }
+        stdout.flush()
+    }
+
+    // Prints the score popups over the board, remembering the cells they cover
+    // Design Decision: Only the text renderer has a terminal character per cell to write them
+    // into; the pixel renderers skip them
+    fn draw_popups(&mut self, stdout: &mut Stdout) -> std::io::Result<()> {
+        if self.theme.renderer != Renderer::Text {
+            return Ok(());
+        }
+        let cell_width = self.theme.cell_width();
+        let (width, height) = (self.state().width(), self.state().height());
+        for (pos, text) in self.game.popups().labels(width, height, cell_width) {
+            queue!(stdout, self.layout.at(pos.x * cell_width, pos.y), SetAttribute(Attribute::Bold))?;
+            match self.theme.color {
+                true => queue!(stdout, SetForegroundColor(self.theme.golden), Print(text), ResetColor)?,
+                false => queue!(stdout, Print(text))?,
+            }
+            queue!(stdout, SetAttribute(Attribute::Reset))?;
+            let cells = (text.len() as u16).div_ceil(cell_width);
+            self.popup_cells.extend((0..cells).map(|offset| Position { x: pos.x + offset, y: pos.y }));
+        }
+        Ok(())
+    }
+
+    // Waits up to `timeout` (but never past the next tick) for local input and applies
+    // everything that arrived
+    // Design Decision: Key presses and gamepad directions both become Actions here, so every
+    // loop that takes local input treats the two sources identically
+    pub fn poll_input(&mut self, timeout: Duration) -> std::io::Result<()> {
+        let mut wait = self.next_tick_in().map_or(timeout, |due| timeout.min(due));
+        while let Some(event) = input::next(wait)? {
+            match event {
+                Event::Key(key_event) => self.handle_key(key_event.code)?,
+                Event::Resize(..) => self.draw()?,
+                // Alt-tabbing away mid-run pauses rather than leaving the snake to crash unseen
+                Event::FocusLost if !self.is_paused() && self.local_players() > 0 && !self.is_over() => {
+                    self.toggle_pause()?;
+                }
+                _ => {}
+            }
+            wait = Duration::ZERO;
+        }
+        let pressed: Vec<Direction> = self.gamepad.iter().flat_map(Receiver::try_iter).collect();
+        for direction in pressed {
+            if self.local_players() > 0 {
+                self.handle_action(Action::Steer(0, direction))?;
+            }
+        }
+        Ok(())
+    }
+
+    // Applies a key press from the local keyboard
+    fn handle_key(&mut self, code: KeyCode) -> std::io::Result<()> {
+        // While a chat message is being typed, every key goes to it and the snake carries on
+        if let Some(chat) = self.chat.as_mut().filter(|chat| chat.is_typing()) {
+            self.said.extend(chat.type_key(code));
+            return self.draw();
+        }
+        // Player one steers with the arrows, player two with WASD, unless rebound in the config
+        match self.keys.action(code, self.local_players()) {
+            Some(action) => self.handle_action(action),
+            None => Ok(()),
+        }
+    }
+
+    // Applies an action from any local input device
+    fn handle_action(&mut self, action: Action) -> std::io::Result<()> {
+        if log::enabled() {
+            log::record("action", &[("action", &format!("{action:?}")), ("tick", &self.ticks()), ("paused", &self.is_paused())]);
+        }
+        // Design Decision: Snake::steer rejects 180° turns for every player
+        let paused = self.is_paused();
+        match action {
+            Action::Steer(player, direction) if !paused => self.steer(player, direction),
+            Action::Turn(player, turn) if !paused => self.turn(player, turn),
+            Action::Steer(..) | Action::Turn(..) => {}  // The board is frozen, so turns would be invisible
+            Action::Pause => return self.toggle_pause(),
+            // Takes effect from the next tick on, and shows in the HUD right away
+            Action::Faster | Action::Slower => {
+                if self.change_pace(action == Action::Faster) {
+                    return self.draw();
+                }
+            }
+            Action::Rewind => return self.step_back(),
+            Action::Chat => {
+                if let Some(chat) = &mut self.chat {
+                    chat.start_typing();
+                    return self.draw();
+                }
+            }
+            Action::Quit => self.quit(),
+        }
+        Ok(())
+    }
+
+    // Freezes or unfreezes the game and redraws it
+    fn toggle_pause(&mut self) -> std::io::Result<()> {
+        if self.is_paused() && self.too_small().is_some() {
+            return Ok(());  // Nothing to play on until the window grows back
+        }
+        self.game.toggle_pause();
+        self.draw()
+    }
+
+    // Advances the simulation once the tick interval has elapsed, returning whether the
+    // screen wants redrawing
+    pub fn tick_if_due(&mut self) -> std::io::Result<bool> {
+        if self.is_paused() || self.crashed() {
+            return Ok(false);
+        }
+        // A window too small for the board holds the game; local players have to unpause
+        // once it's big enough again, so it never restarts under their hands
+        if self.too_small().is_some() {
+            let pausing = self.local_players() > 0 && !self.is_over();
+            if pausing {
+                self.pause();
+            }
+            return Ok(pausing);
+        }
+        let stage = self.stage();
+        let changed = self.game.tick_if_due();
+        self.show_new_stage(stage)?;
+        Ok(changed)
+    }
+
+    // Runs exactly one simulation tick
+    pub fn tick(&mut self) -> std::io::Result<()> {
+        let stage = self.stage();
+        self.game.tick();
+        self.show_new_stage(stage)
+    }
+
+    // Shows the next stage's title when a tick has moved the campaign on from `stage`, then
+    // starts the clock over so the time it was up isn't caught up
+    fn show_new_stage(&mut self, stage: usize) -> std::io::Result<()> {
+        if self.stage() != stage {
+            self.draw_stage_transition()?;
+            self.screen = None;
+            self.restart_clock();
+        }
+        Ok(())
+    }
+
+    // Steps back in practice mode and counts down again from the earlier board
+    fn step_back(&mut self) -> std::io::Result<()> {
+        if !self.game.step_back() {
+            return Ok(());
+        }
+        self.screen = None;
+        self.draw()
+    }
+
+    // Goes back to a checkpoint, dropping every tick and input since
+    // Design Decision: The board on screen is kept, so the next draw only prints the cells
+    // that differ from it rather than clearing the terminal
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.game.rollback(checkpoint);
+        self.forget_outline();
+    }
+
+    // Runs one simulation tick without touching the screen, for games raced in the background
+    pub fn tick_quietly(&mut self) {
+        self.game.tick_quietly();
+        self.forget_outline();
+    }
+
+    // Makes the next draw compare the whole board, since it may have changed anywhere
+    fn forget_outline(&mut self) {
+        if let Some((_, outline)) = &mut self.screen {
+            *outline = None;
+        }
+    }
+
+    // Draws one frame of the death animation after a crash: the crashed snakes flash, then
+    // collapse from the tail. Returns false, drawing nothing, once there are no frames left
+    // (straight away when nobody crashed)
+    pub fn draw_death(&mut self, frame: usize) -> std::io::Result<bool> {
+        let longest = self.state().snakes.iter().filter(|snake| !snake.alive).map(|snake| snake.body.len()).max();
+        let Some(longest) = longest else {
+            return Ok(false);
+        };
+        let flashing = DEATH_FLASHES * 2;
+        let steps = longest.min(COLLAPSE_STEPS);
+        self.dying = match frame {
+            _ if frame < flashing => Some(Dying { hidden: frame.is_multiple_of(2), removed: 0 }),
+            _ if frame < flashing + steps => {
+                let step = frame - flashing + 1;
+                Some(Dying { hidden: false, removed: (longest * step).div_ceil(steps) })
+            }
+            _ => None,
+        };
+        if self.dying.is_none() {
+            return Ok(false);
+        }
+        self.draw()?;
+        Ok(true)
+    }
+
+    // Records the score and draws the results: score, best score, the high score table and any
+    // notes about the saved replay and leaderboard, returning the first free row below them
+    pub fn draw_game_over(&mut self, notes: &[String]) -> std::io::Result<u16> {
+        self.screen = None;
+        let mut scores = HighScores::load();
+        let (rank, daily_best) = self.record_score(&mut scores);
+        if rank.is_some() || daily_best {
+            scores.save()?;
+        }
+
+        let (mut stdout, text) = (stdout(), lang::text());
+        execute!(stdout, Clear(ClearType::All), MoveTo(2, 1), Print(self.final_message()))?;
+        if let Some(date) = self.daily() {
+            let score = self.state().score();
+            let best = scores.daily().iter().find(|entry| entry.date == date).map_or(score, |entry| entry.score);
+            let mut line = (text.daily_best)(date, best);
+            if daily_best {
+                line.push_str(&format!("  {}", text.new_entry));
+            }
+            execute!(stdout, MoveTo(2, 2), Print(line))?;
+        } else if let Some(best) = scores.entries().first() {
+            execute!(stdout, MoveTo(2, 2), Print(format!("{}: {}", text.best_score, best.score)))?;
+        }
+        let mut row = draw_high_scores(&mut stdout, &scores, rank, self.theme.accent(), 4)? + 1;
+        for note in notes {
+            execute!(stdout, MoveTo(2, row), Print(note))?;
+            row += 1;
+        }
+        stdout.flush()?;
+        Ok(row)
+    }
+}
+
+
+// Re-simulates a recorded game on screen; `+`/`-` change speed, `q` stops. With a cast path,
+// what was shown is also saved there as an asciinema recording
+pub fn play_replay(path: &Path, mut speed: f64, config: &Config, cast: Option<&Path>) -> std::io::Result<()> {
+    let replay = Replay::load(path)?;
+    let mut game = Play::new(Game::new(replay.board.clone(), replay.players, replay.seed, replay.settings));
+    game.set_preferences(config.keys.clone(), config.theme.clone());
+    if cast.is_some() {
+        game.set_cast(Cast::new(&format!("snekrs replay: {}", game.board_settings())));
+    }
+    for (player, difficulty) in &replay.bots {
+        game.hand_to_ai(*player, *difficulty);
+    }
+    game.set_local_players(0);
+
+    let game_screen = enter_game_screen()?;
+    draw_replay(&mut game, speed)?;
+
+    let mut inputs = replay.inputs.iter().peekable();
+    let mut last_tick = Instant::now();
+    while !game.is_over() && game.ticks() < replay.ticks {
+        if let Some(Event::Key(key_event)) = input::next(Duration::from_millis(10))? {
+            match key_event.code {
+                KeyCode::Char('+') | KeyCode::Char('=') => speed = (speed * 2.0).min(MAX_SPEED),
+                KeyCode::Char('-') => speed = (speed / 2.0).max(MIN_SPEED),
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                _ => {}
+            }
+        }
+
+        if last_tick.elapsed() >= game.tick_interval().div_f64(speed) {
+            while let Some(input) = inputs.next_if(|input| input.tick <= game.ticks()) {
+                game.apply(input.change);
+            }
+            game.tick()?;
+            draw_replay(&mut game, speed)?;
+            last_tick = Instant::now();
+        }
+    }
+
+    game_screen.leave()?;
+    println!("\n{} {}", lang::text().replay_finished, game.final_message());
+    if let (Some(path), Some(recording)) = (cast, game.cast()) {
+        recording.save(path)?;
+        println!("{}", (lang::text().cast_saved)(&path.display().to_string()));
+    }
+    Ok(())
+}
+
+// Draws the board with a playback banner in place of the controls line
+fn draw_replay(game: &mut Play, speed: f64) -> std::io::Result<()> {
+    game.draw()?;
+    let mut stdout = stdout();
+    execute!(
+        stdout,
+        game.layout().at(0, game.board_rows() + 1),
+        Clear(ClearType::CurrentLine),
+        Print((lang::text().replay_banner)(speed))
+    )?;
+    stdout.flush()
+}
+
+// The screen as a cast frame: the board, with dimmed cells dimmed and the overlay label across
+// its middle row, then the lines under it; returned with the widest row's width
+fn cast_rows(frame: &Frame, overlay: Option<&str>, lines: [String; 2]) -> (Vec<String>, u16) {
+    let label: Vec<char> = overlay.map(|label| label.chars().collect()).unwrap_or_default();
+    let start = usize::from(frame.width()).saturating_sub(label.len()) / 2;
+    let mut rows = Vec::new();
+    for y in 0..frame.height() {
+        let mut row = String::new();
+        let mut style = "";
+        for x in 0..frame.width() {
+            let (glyph, dim) = frame.get(x, y);
+            let labelled = if y == frame.height() / 2 { usize::from(x).checked_sub(start).and_then(|at| label.get(at)) } else { None };
+            let (glyph, wanted) = match labelled {
+                Some(&letter) => (letter, "\u{1b}[7m"),
+                None if dim => (glyph, "\u{1b}[2m"),
+                None => (glyph, ""),
+            };
+            if wanted != style {
+                row.push_str("\u{1b}[0m");
+                row.push_str(wanted);
+                style = wanted;
+            }
+            row.push(glyph);
+        }
+        rows.push(row);
+    }
+    let width = lines.iter().map(|line| line.chars().count() as u16).fold(frame.width(), u16::max);
+    rows.extend(lines);
+    (rows, width)
+}
+
+
+// Draws the high score table from `row` down, highlighting a freshly added entry,
+// and returns the first row below it
+pub fn draw_high_scores(
+    stdout: &mut Stdout,
+    scores: &HighScores,
+    highlight: Option<usize>,
+    color: Option<Color>,
+    mut row: u16,
+) -> std::io::Result<u16> {
+    let text = lang::text();
+    execute!(stdout, MoveTo(2, row), Print(text.high_scores))?;
+    row += 1;
+    if scores.entries().is_empty() {
+        execute!(stdout, MoveTo(2, row), Print(text.no_scores))?;
+        row += 1;
+    }
+    for (index, entry) in scores.entries().iter().enumerate() {
+        let line = format!("{:>2}. {:>4}  {}  {}", index + 1, entry.score, entry.date, entry.board);
+        if highlight == Some(index) {
+            let line = format!("{line}  {}", text.new_entry);
+            match color {
+                Some(color) => execute!(stdout, MoveTo(2, row), SetForegroundColor(color), Print(line), ResetColor)?,
+                None => execute!(stdout, MoveTo(2, row), Print(line))?,
+            }
+        } else {
+            execute!(stdout, MoveTo(2, row), Print(line))?;
+        }
+        row += 1;
+    }
+    Ok(row)
+}
+
+// Draws the most recent daily challenge results from `row` down, if any were played, and
+// returns the first row below them
+pub fn draw_daily_results(stdout: &mut Stdout, scores: &HighScores, mut row: u16) -> std::io::Result<u16> {
+    if scores.daily().is_empty() {
+        return Ok(row);
+    }
+    execute!(stdout, MoveTo(2, row), Print(lang::text().daily_results))?;
+    row += 1;
+    for entry in scores.daily().iter().take(DAILY_RESULTS_SHOWN) {
+        execute!(stdout, MoveTo(2, row), Print(format!("{}  {:>4}  {}", entry.date, entry.score, entry.board)))?;
+        row += 1;
+    }
+    Ok(row)
+}
+
+// Switches the terminal into raw mode with a hidden cursor
+pub fn enter_game_screen() -> std::io::Result<GameScreen> {
+    terminal::enable_raw_mode()?;
+    execute!(stdout(), Hide, EnableMouseCapture, EnableFocusChange)?;
+    Ok(GameScreen { active: true })
+}
+
+// Keeps the terminal in game mode until left or dropped
+// Design Decision: Dropping restores the terminal too, so an early return through `?` still
+// hands the shell back in a usable state
+#[must_use]
+pub struct GameScreen {
+    active: bool,  // Cleared once leave() has restored the terminal
+}
+
+impl GameScreen {
+    // Restores the terminal, reporting any failure
+    pub fn leave(mut self) -> std::io::Result<()> {
+        self.active = false;
+        restore_terminal()
+    }
+}
+
+impl Drop for GameScreen {
+    fn drop(&mut self) {
+        if self.active {
+            let _ = restore_terminal();
+        }
+    }
+}
+
+// Restores the terminal to its normal state
+pub fn restore_terminal() -> std::io::Result<()> {
+    terminal::disable_raw_mode()?;
+    execute!(stdout(), ResetColor, SetAttribute(Attribute::Reset), Show, DisableMouseCapture, DisableFocusChange)
+}
+
+// Makes a panic restore the terminal before its message is printed
+// Design Decision: The default hook prints the message before unwinding reaches any
+// GameScreen, so without this the message would come out garbled by raw mode and the shell
+// would be left with a hidden cursor
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+}
@@ -0,0 +1,52 @@
+// Floating score popups: a short "+N" label that drifts up from where food was eaten
+// Design Decision: Popups live outside the board's logical glyphs (digits and '+' already
+// mean power-ups and snake bodies there) and are printed over the drawn board instead; they
+// age with the ticks, so they stop with the game when it's paused
+use crate::game::Position;
+
+// Ticks a popup stays up, and ticks between each row it drifts upward
+const LIFETIME: u32 = 6;
+const DRIFT_TICKS: u32 = 2;
+
+// One floating label
+struct Popup {
+    origin: Position,  // Cell the food was eaten on
+    text: String,
+    age: u32,          // Ticks since it appeared
+}
+
+// Every popup currently on screen
+#[derive(Default)]
+pub struct Popups {
+    active: Vec<Popup>,
+}
+
+impl Popups {
+    // Shows the points scored on a cell
+    pub fn add(&mut self, origin: Position, points: u32) {
+        self.active.push(Popup { origin, text: format!("+{points}"), age: 0 });
+    }
+
+    // Ages every popup by one tick, dropping the ones that have faded
+    pub fn tick(&mut self) {
+        for popup in &mut self.active {
+            popup.age += 1;
+        }
+        self.active.retain(|popup| popup.age < LIFETIME);
+    }
+
+    // Each label with the cell it starts on: a row above its origin at first, then higher,
+    // kept inside the board's border (`width` and `height` in cells, `cell_width` columns each)
+    pub fn labels(&self, width: u16, height: u16, cell_width: u16) -> Vec<(Position, &str)> {
+        self.active
+            .iter()
+            .filter_map(|popup| {
+                let cells = (popup.text.len() as u16).div_ceil(cell_width);
+                let rise = (1 + popup.age / DRIFT_TICKS) as u16;
+                let y = popup.origin.y.checked_sub(rise).filter(|&y| y > 0 && y < height - 1)?;
+                let x = popup.origin.x.min(width.saturating_sub(1 + cells)).max(1);
+                Some((Position { x, y }, popup.text.as_str()))
+            })
+            .collect()
+    }
+}
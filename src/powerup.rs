@@ -0,0 +1,79 @@
+// Power-up pickups that appear on the board now and then
+// Design Decision: Pickups are board items like food, placed by GameState from the seeded RNG
+// so replays reproduce them; what they do once collected is up to the Effects subsystem
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Chance that eating food brings out a pickup, when none is on the board
+pub const PICKUP_CHANCE: f64 = 0.25;
+
+// Game time a pickup stays on the board before vanishing
+pub const PICKUP_LIFETIME: Duration = Duration::from_secs(8);
+
+// How many tail segments the shrink pickup takes off (the snake never drops below one segment)
+pub const SHRINK_SEGMENTS: usize = 5;
+
+// What a pickup does when collected
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum PowerUp {
+    Boost,  // Doubles the pace for a while
+    Slow,   // Halves the pace for a while
+    Shrink, // Takes segments off the tail at once
+    Phase,  // Passes through its own body and obstacles for a while
+    Double, // Doubles food points for a while
+    Triple, // Triples food points for a while
+    Magnet, // Pulls the food toward the head for a while
+}
+
+// Spawn chances, out of the total weight
+const WEIGHTS: [(PowerUp, u32); 7] = [
+    (PowerUp::Boost, 2),
+    (PowerUp::Slow, 2),
+    (PowerUp::Shrink, 2),
+    (PowerUp::Phase, 2),
+    (PowerUp::Double, 2),
+    (PowerUp::Triple, 1),
+    (PowerUp::Magnet, 2),
+];
+
+impl PowerUp {
+    // Picks a power-up at random according to the spawn weights
+    pub fn roll(rng: &mut impl Rng) -> PowerUp {
+        let total: u32 = WEIGHTS.iter().map(|(_, weight)| weight).sum();
+        let mut pick = rng.gen_range(0..total);
+        for (power, weight) in WEIGHTS {
+            if pick < weight {
+                return power;
+            }
+            pick -= weight;
+        }
+        PowerUp::Boost
+    }
+
+    // Board glyph, also used by network clients to pick its color
+    pub fn glyph(self) -> char {
+        match self {
+            PowerUp::Boost => '>',
+            PowerUp::Slow => '<',
+            PowerUp::Shrink => '-',
+            PowerUp::Phase => '!',
+            PowerUp::Double => '2',
+            PowerUp::Triple => '3',
+            PowerUp::Magnet => '&',
+        }
+    }
+
+    // Name shown in the HUD while its effect lasts
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerUp::Boost => "Boost",
+            PowerUp::Slow => "Slow-mo",
+            PowerUp::Shrink => "Shrink",
+            PowerUp::Phase => "Ghost",
+            PowerUp::Double => "x2",
+            PowerUp::Triple => "x3",
+            PowerUp::Magnet => "Magnet",
+        }
+    }
+}
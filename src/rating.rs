@@ -0,0 +1,130 @@
+// Elo-style ratings from versus matches, one per profile
+// Design Decision: Both sides of a match are profiles with ratings of their own - the player at
+// this keyboard under their name, each bot difficulty, a second player on the same keyboard and
+// a guest over the network - so both ratings move after every match, beating a hard bot is
+// worth more than beating an easy one, and the bots settle at the level they really play
+use crate::lang;
+use crate::paths;
+use std::fs;
+use std::path::PathBuf;
+
+// Rating every profile starts from
+const STARTING_RATING: i32 = 1200;
+
+// Most a single match can move a rating
+const K_FACTOR: f64 = 32.0;
+
+// How a match ended for the first side
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    // Points scored by the first side: 1 for a win, half for a draw
+    fn points(self) -> f64 {
+        match self {
+            Outcome::Win => 1.0,
+            Outcome::Draw => 0.5,
+            Outcome::Loss => 0.0,
+        }
+    }
+}
+
+// One rated profile
+struct Profile {
+    name: String,  // Who it is: a player's name or an opponent kind like "AI (hard)"
+    rating: i32,   // Current rating
+    matches: u32,  // Versus matches rated so far
+}
+
+// Every profile's rating, kept between runs
+#[derive(Default)]
+pub struct Ratings {
+    profiles: Vec<Profile>,
+}
+
+impl Ratings {
+    // Reads the ratings file, starting empty when there is none
+    // Format: one "<rating> <matches> <name>" line per profile, the name last so it may hold spaces
+    pub fn load() -> Ratings {
+        let profiles = fs::read_to_string(ratings_path())
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, ' ');
+                let rating = fields.next()?.parse().ok()?;
+                let matches = fields.next()?.parse().ok()?;
+                Some(Profile { name: fields.next()?.to_string(), rating, matches })
+            })
+            .collect();
+        Ratings { profiles }
+    }
+
+    // A profile's rating, the starting one for a profile not rated yet
+    pub fn rating(&self, name: &str) -> i32 {
+        self.profiles.iter().find(|profile| profile.name == name).map_or(STARTING_RATING, |profile| profile.rating)
+    }
+
+    // Rates a match between two profiles, returning how far the first one's rating moved
+    // Design Decision: The two changes are rounded once and mirrored, so no rating points
+    // appear or vanish over many matches
+    pub fn record(&mut self, player: &str, opponent: &str, outcome: Outcome) -> i32 {
+        let (ours, theirs) = (self.rating(player), self.rating(opponent));
+        let expected = 1.0 / (1.0 + 10f64.powf(f64::from(theirs - ours) / 400.0));
+        let change = (K_FACTOR * (outcome.points() - expected)).round() as i32;
+        self.adjust(player, change);
+        self.adjust(opponent, -change);
+        change
+    }
+
+    // Moves a profile's rating, adding the profile when it's new
+    fn adjust(&mut self, name: &str, change: i32) {
+        let index = match self.profiles.iter().position(|profile| profile.name == name) {
+            Some(index) => index,
+            None => {
+                self.profiles.push(Profile { name: name.to_string(), rating: STARTING_RATING, matches: 0 });
+                self.profiles.len() - 1
+            }
+        };
+        let profile = &mut self.profiles[index];
+        profile.rating += change;
+        profile.matches += 1;
+    }
+
+    // Writes the ratings back to disk
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = ratings_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text: String = self
+            .profiles
+            .iter()
+            .map(|profile| format!("{} {} {}\n", profile.rating, profile.matches, profile.name))
+            .collect();
+        fs::write(path, text)
+    }
+
+    // The ratings table for `snekrs stats`, best first, once a match has been rated
+    pub fn summary(&self) -> Option<String> {
+        if self.profiles.is_empty() {
+            return None;
+        }
+        let mut profiles: Vec<&Profile> = self.profiles.iter().collect();
+        profiles.sort_by_key(|profile| std::cmp::Reverse(profile.rating));
+        let text = lang::text();
+        let mut summary = text.ratings.to_string();
+        for profile in profiles {
+            summary.push_str(&format!("\n{:>5}  {} ({})", profile.rating, profile.name, (text.rated_matches)(profile.matches)));
+        }
+        Some(summary)
+    }
+}
+
+// Where the ratings are kept, next to the stats
+fn ratings_path() -> PathBuf {
+    paths::data_dir().join("ratings.txt")
+}
@@ -0,0 +1,239 @@
+// Turning a board of logical glyphs into terminal output
+// Design Decision: Game code only fills in a Frame of glyphs; the renderer decides how many
+// board cells share one terminal character, so the same board can be drawn as text or as
+// colored pixels without the game logic knowing. Output is only queued; callers flush once
+// per frame
+use crate::frame::Frame;
+use crate::game::{Position, PLAYER_GLYPHS};
+use crate::ghost::GHOST_GLYPH;
+use crate::state::ZONE_GLYPH;
+use crate::theme::Theme;
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+};
+use std::collections::BTreeSet;
+use std::io::Stdout;
+
+// Where the board and the lines under it sit in the terminal
+// Design Decision: Everything drawn relative to the board goes through one offset, so the
+// board can be centered without any drawing code knowing the terminal size
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Layout {
+    left: u16,  // Terminal column of the board's left edge
+    top: u16,   // Terminal row of the board's top edge
+}
+
+impl Layout {
+    // Centers something `columns` wide and `rows` tall in the terminal (top-left corner when
+    // it doesn't fit or the terminal size is unknown)
+    pub fn centered(columns: u16, rows: u16) -> Layout {
+        let (width, height) = crossterm::terminal::size().unwrap_or_default();
+        Layout { left: width.saturating_sub(columns) / 2, top: height.saturating_sub(rows) / 2 }
+    }
+
+    // Cursor move to a column and row counted from the board's top-left corner
+    pub fn at(self, x: u16, y: u16) -> MoveTo {
+        MoveTo(self.left + x, self.top + y)
+    }
+
+    // Terminal area `columns` wide and `rows` tall starting at the board's top-left corner
+    #[cfg(feature = "tui")]
+    pub fn area(self, columns: u16, rows: u16) -> ratatui::layout::Rect {
+        ratatui::layout::Rect::new(self.left, self.top, columns, rows)
+    }
+}
+
+// How board cells map onto terminal characters
+#[derive(Clone, Copy, PartialEq)]
+pub enum Renderer {
+    Text,       // One glyph per cell (two columns per cell with emoji)
+    HalfBlock,  // Two stacked cells per character as colored ▀/▄ halves, so cells look square
+    Braille,    // Experimental: a 2x4 block of cells per character as braille dots
+}
+
+// Dot bit of each cell in a braille character's 2x4 block, indexed [row][column]
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+impl Renderer {
+    // Parses a CLI or config name (text, half-block, braille)
+    pub fn parse(name: &str) -> Option<Renderer> {
+        match name {
+            "text" => Some(Renderer::Text),
+            "half-block" => Some(Renderer::HalfBlock),
+            "braille" => Some(Renderer::Braille),
+            _ => None,
+        }
+    }
+
+    // Terminal columns and rows a board of this size takes
+    pub fn size(self, width: u16, height: u16, theme: &Theme) -> (u16, u16) {
+        match self {
+            Renderer::Text => (width * theme.cell_width(), height),
+            Renderer::HalfBlock => (width, height.div_ceil(2)),
+            Renderer::Braille => (width.div_ceil(2), height.div_ceil(4)),
+        }
+    }
+
+    // Largest board whose drawing fits in the given terminal columns and rows
+    pub fn board_size(self, columns: u16, rows: u16, theme: &Theme) -> (u16, u16) {
+        match self {
+            Renderer::Text => (columns / theme.cell_width(), rows),
+            Renderer::HalfBlock => (columns, rows.saturating_mul(2)),
+            Renderer::Braille => (columns.saturating_mul(2), rows.saturating_mul(4)),
+        }
+    }
+
+    // Draws the whole frame
+    pub fn draw(self, stdout: &mut Stdout, layout: Layout, frame: &Frame, theme: &Theme) -> std::io::Result<()> {
+        let cells = (0..frame.height()).flat_map(|y| (0..frame.width()).map(move |x| Position { x, y }));
+        self.draw_cells(stdout, layout, frame, cells, theme)
+    }
+
+    // Redraws just the terminal characters covering the given board cells
+    pub fn draw_cells(
+        self,
+        stdout: &mut Stdout,
+        layout: Layout,
+        frame: &Frame,
+        cells: impl IntoIterator<Item = Position>,
+        theme: &Theme,
+    ) -> std::io::Result<()> {
+        for painted in self.paint(frame, cells, theme) {
+            painted.print(stdout, layout)?;
+        }
+        Ok(())
+    }
+
+    // The terminal characters covering the given board cells, each one once
+    // Design Decision: Working out what a character looks like is kept apart from printing it,
+    // so every frontend draws the board exactly the same way
+    pub fn paint(self, frame: &Frame, cells: impl IntoIterator<Item = Position>, theme: &Theme) -> Vec<Painted> {
+        match self {
+            Renderer::Text => cells.into_iter().map(|Position { x, y }| paint_cell(frame, x, y, theme)).collect(),
+            Renderer::HalfBlock => {
+                // Both halves of a character come from the frame, so each one is drawn once
+                let characters: BTreeSet<(u16, u16)> = cells.into_iter().map(|Position { x, y }| (y / 2, x)).collect();
+                characters.into_iter().map(|(row, x)| paint_half_blocks(frame, x, row, theme)).collect()
+            }
+            Renderer::Braille => {
+                let characters: BTreeSet<(u16, u16)> = cells.into_iter().map(|Position { x, y }| (y / 4, x / 2)).collect();
+                characters.into_iter().map(|(row, column)| paint_braille(frame, column, row, theme)).collect()
+            }
+        }
+    }
+}
+
+// One terminal character of the board, ready to be drawn
+pub struct Painted {
+    pub column: u16,                // Terminal column counted from the board's left edge
+    pub row: u16,                   // Terminal row counted from the board's top edge
+    pub shown: String,              // What's printed (two columns wide for emoji)
+    pub foreground: Option<Color>,  // None = the terminal's default
+    pub background: Option<Color>,
+    pub dim: bool,
+    pub bold: bool,
+}
+
+impl Painted {
+    // Queues the character at its spot, resetting any attribute it set afterwards
+    fn print(&self, stdout: &mut Stdout, layout: Layout) -> std::io::Result<()> {
+        if self.dim {
+            queue!(stdout, SetAttribute(Attribute::Dim))?;
+        }
+        if self.bold {
+            queue!(stdout, SetAttribute(Attribute::Bold))?;
+        }
+        if let Some(color) = self.foreground {
+            queue!(stdout, SetForegroundColor(color))?;
+        }
+        if let Some(color) = self.background {
+            queue!(stdout, SetBackgroundColor(color))?;
+        }
+        queue!(stdout, layout.at(self.column, self.row), Print(&self.shown))?;
+        match self.dim || self.bold || self.foreground.is_some() || self.background.is_some() {
+            true => queue!(stdout, ResetColor),
+            false => Ok(()),
+        }
+    }
+}
+
+// A single board cell the way the theme draws its logical glyph
+// Design Decision: Every cell is positioned explicitly, so a terminal that disagrees about an
+// emoji's width can only garble that cell, never skew the rest of the row
+fn paint_cell(frame: &Frame, x: u16, y: u16, theme: &Theme) -> Painted {
+    let ((glyph, dim), tone) = (frame.get(x, y), frame.tone(x, y));
+    Painted {
+        column: x * theme.cell_width(),
+        row: y,
+        shown: theme.cell(glyph, tone),
+        foreground: theme.color(glyph, tone),
+        background: theme.background(glyph),
+        dim,
+        bold: theme.bold,
+    }
+}
+
+// One character holding a board cell in each half
+// Design Decision: The upper cell is the foreground of ▀ and the lower one its background;
+// without color, occupied halves are simply filled in
+fn paint_half_blocks(frame: &Frame, x: u16, row: u16, theme: &Theme) -> Painted {
+    let ((upper, upper_dim), (lower, lower_dim)) = (frame.get(x, row * 2), frame.get(x, row * 2 + 1));
+    let (upper, lower) = (theme.pixel(upper, frame.tone(x, row * 2)), theme.pixel(lower, frame.tone(x, row * 2 + 1)));
+    let (shown, foreground, background): (char, Option<Color>, Option<Color>) = match (upper, lower) {
+        (None, None) => (' ', None, None),
+        (Some(color), None) => ('▀', Some(color), None),
+        (None, Some(color)) => ('▄', Some(color), None),
+        (Some(top), Some(bottom)) if theme.color => ('▀', Some(top), Some(bottom)),
+        (Some(_), Some(_)) => ('█', None, None),
+    };
+    Painted {
+        column: x,
+        row,
+        shown: shown.to_string(),
+        foreground: foreground.filter(|_| theme.color),
+        background,
+        dim: upper_dim || lower_dim,
+        bold: false,
+    }
+}
+
+// One braille character covering a 2x4 block of board cells
+// Design Decision: A character has a single color, so it takes the color of the block's most
+// telling cell: anything beats a snake body, which beats the ghost or zone, which beats walls
+fn paint_braille(frame: &Frame, column: u16, row: u16, theme: &Theme) -> Painted {
+    let rank = |glyph: char| match glyph {
+        '#' => 0,
+        GHOST_GLYPH | ZONE_GLYPH => 1,
+        _ if PLAYER_GLYPHS.iter().any(|&(_, body)| body == glyph) => 2,
+        _ => 3,
+    };
+    let (mut dots, mut dim, mut color) = (0, false, None);
+    let mut best = None;
+    for (dy, bits) in BRAILLE_DOTS.iter().enumerate() {
+        for (dx, bit) in bits.iter().enumerate() {
+            let (x, y) = (column * 2 + dx as u16, row * 4 + dy as u16);
+            let (glyph, cell_dim) = frame.get(x, y);
+            let Some(pixel) = theme.pixel(glyph, frame.tone(x, y)) else { continue };
+            dots |= bit;
+            dim |= cell_dim;
+            if best.is_none_or(|best| rank(glyph) > best) {
+                (best, color) = (Some(rank(glyph)), Some(pixel));
+            }
+        }
+    }
+    let shown = match dots {
+        0 => ' ',
+        _ => char::from_u32(0x2800 + dots).unwrap_or(' '),
+    };
+    Painted {
+        column,
+        row,
+        shown: shown.to_string(),
+        foreground: color.filter(|_| theme.color),
+        background: None,
+        dim,
+        bold: false,
+    }
+}
@@ -0,0 +1,241 @@
+// Replay recording and playback
+// Design Decision: A game is fully determined by its board, seed and steering inputs, so a
+// replay stores only those and playback re-simulates instead of storing frames
+//
+// File format (plain text, one entry per line):
+//   snekrs-replay 1
+//   seed <u64>
+//   players <n>
+//   bot <player> <easy|medium|hard>
+//   size <width> <height>               (classic and maze boards, default 40 20)
+//   tick <ms>                            (default 100)
+//   min-tick <ms>                        (default 50)
+//   difficulty <easy|normal|hard|insane> (default normal)
+//   mode <standard|fleeing|time-attack|survival|zen|hardcore|royale> (default standard)
+//   mod <no-poison|double-points|big-meals|hunger>   (one line per mod, in stacking order)
+//   board classic|maze|campaign|level   ("level" is followed by its grid and an "end" line)
+//   daily <YYYY-MM-DD>                   (daily challenge games only)
+//   input <tick> <player> <U|D|L|R>
+//   ticks <n>
+//   score <n>
+use crate::ai::Difficulty;
+use crate::config::{self, GameConfig};
+use crate::difficulty;
+use crate::events::GameEvent;
+use crate::game::{Board, Direction};
+use crate::level::Level;
+use crate::mode::Mode;
+use crate::mods::ModKind;
+use crate::paths;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+const HEADER: &str = "snekrs-replay 1";
+
+// An input applied before the given tick
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Input {
+    pub tick: u64,
+    pub change: Change,
+}
+
+// What an input changes
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Change {
+    Steer(usize, Direction),  // A player turned their snake
+    Pace(f64),                // The pace multiplier was set
+}
+
+// Collects a game's inputs from its events, for the replay file
+// Design Decision: Shared with the game it listens to, which reads the inputs back for replays
+// and checkpoints and drops the ones a rewind undoes
+#[derive(Clone, Default)]
+pub struct Recorder {
+    inputs: Rc<RefCell<Vec<Input>>>,
+}
+
+impl Recorder {
+    // Keeps the input an event carries, if it carries one
+    pub fn hear(&self, event: &GameEvent) {
+        if let GameEvent::InputMade(input) = event {
+            self.inputs.borrow_mut().push(*input);
+        }
+    }
+
+    // Every input recorded so far, oldest first
+    pub fn inputs(&self) -> Vec<Input> {
+        self.inputs.borrow().clone()
+    }
+
+    // Number of inputs recorded so far
+    pub fn count(&self) -> usize {
+        self.inputs.borrow().len()
+    }
+
+    // Forgets every input after the first `count`
+    pub fn truncate(&self, count: usize) {
+        self.inputs.borrow_mut().truncate(count);
+    }
+}
+
+// Everything needed to re-simulate a game
+pub struct Replay {
+    pub seed: u64,
+    pub players: usize,
+    pub bots: Vec<(usize, Difficulty)>,
+    pub board: Board,
+    pub settings: GameConfig,
+    pub inputs: Vec<Input>,
+    pub ticks: u64,
+    pub score: u32,  // Final score, so best runs can be compared without re-simulating
+    pub daily: Option<String>,  // Date of the daily challenge this game was
+}
+
+impl Replay {
+    // Serializes the replay into its text format
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{HEADER}\nseed {}\nplayers {}\n", self.seed, self.players);
+        for (player, difficulty) in &self.bots {
+            text.push_str(&format!("bot {player} {}\n", difficulty.name()));
+        }
+        text.push_str(&format!("size {} {}\n", self.settings.width, self.settings.height));
+        text.push_str(&format!("tick {}\n", self.settings.tick_interval.as_millis()));
+        text.push_str(&format!("min-tick {}\n", self.settings.min_tick_interval.as_millis()));
+        text.push_str(&format!("difficulty {}\n", self.settings.difficulty.name()));
+        text.push_str(&format!("mode {}\n", self.settings.mode.name()));
+        for game_mod in &self.settings.mods {
+            text.push_str(&format!("mod {}\n", game_mod.name()));
+        }
+        match &self.board {
+            Board::Classic => text.push_str("board classic\n"),
+            Board::Maze => text.push_str("board maze\n"),
+            Board::Campaign => text.push_str("board campaign\n"),
+            Board::Custom(level) => {
+                text.push_str("board level\n");
+                text.push_str(&level.to_grid());
+                text.push_str("end\n");
+            }
+        }
+        if let Some(date) = &self.daily {
+            text.push_str(&format!("daily {date}\n"));
+        }
+        for input in &self.inputs {
+            match input.change {
+                Change::Steer(player, direction) => {
+                    text.push_str(&format!("input {} {player} {}\n", input.tick, direction.code()));
+                }
+                Change::Pace(pace) => text.push_str(&format!("pace {} {pace}\n", input.tick)),
+            }
+        }
+        text.push_str(&format!("ticks {}\n", self.ticks));
+        text.push_str(&format!("score {}\n", self.score));
+        text
+    }
+
+    // Parses a replay from its text format
+    pub fn parse(text: &str) -> std::io::Result<Replay> {
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("not a snekrs replay file"));
+        }
+
+        let mut replay = Replay {
+            seed: 0,
+            players: 1,
+            bots: Vec::new(),
+            board: Board::Classic,
+            settings: GameConfig::default(),
+            inputs: Vec::new(),
+            ticks: 0,
+            score: 0,
+            daily: None,
+        };
+        while let Some(line) = lines.next() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["seed", seed] => replay.seed = parse_field(seed)?,
+                ["players", players] => replay.players = parse_field(players)?,
+                ["bot", player, difficulty] => {
+                    let difficulty = Difficulty::parse(difficulty).ok_or_else(|| invalid("unknown bot"))?;
+                    replay.bots.push((parse_field(player)?, difficulty));
+                }
+                // Sizes outside the board limits can't be simulated, so they're as bad as garbage
+                ["size", width, height] => {
+                    replay.settings.width = config::board_size(parse_field(width)?).map_err(|err| invalid(&err))?;
+                    replay.settings.height = config::board_size(parse_field(height)?).map_err(|err| invalid(&err))?;
+                }
+                ["tick", millis] => replay.settings.tick_interval = Duration::from_millis(parse_field(millis)?),
+                ["min-tick", millis] => {
+                    replay.settings.min_tick_interval = Duration::from_millis(parse_field(millis)?);
+                }
+                ["difficulty", name] => {
+                    replay.settings.difficulty = difficulty::Difficulty::parse(name).ok_or_else(|| invalid("unknown difficulty"))?;
+                }
+                ["mode", name] => replay.settings.mode = Mode::parse(name).ok_or_else(|| invalid("unknown mode"))?,
+                ["mod", name] => replay.settings.mods.push(ModKind::parse(name).ok_or_else(|| invalid("unknown mod"))?),
+                ["board", "classic"] => replay.board = Board::Classic,
+                ["board", "maze"] => replay.board = Board::Maze,
+                ["board", "campaign"] => replay.board = Board::Campaign,
+                ["board", "level"] => {
+                    let grid: Vec<&str> = lines.by_ref().take_while(|line| *line != "end").collect();
+                    replay.board = Board::Custom(Level::parse(&grid.join("\n"))?);
+                }
+                ["input", tick, player, direction] => replay.inputs.push(Input {
+                    tick: parse_field(tick)?,
+                    change: Change::Steer(
+                        parse_field(player)?,
+                        Direction::from_code(direction).ok_or_else(|| invalid("bad direction"))?,
+                    ),
+                }),
+                ["pace", tick, pace] => replay.inputs.push(Input {
+                    tick: parse_field(tick)?,
+                    change: Change::Pace(parse_field(pace)?),
+                }),
+                ["daily", date] => replay.daily = Some(date.to_string()),
+                ["ticks", ticks] => replay.ticks = parse_field(ticks)?,
+                ["score", score] => replay.score = parse_field(score)?,
+                [] => {}
+                _ => return Err(invalid(&format!("unexpected line: {line}"))),
+            }
+        }
+        Ok(replay)
+    }
+
+    // Writes the replay to disk, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    // Reads a replay from disk
+    pub fn load(path: &Path) -> std::io::Result<Replay> {
+        Replay::parse(&fs::read_to_string(path)?)
+    }
+}
+
+// Where the most recent game's replay is kept
+pub fn default_path() -> PathBuf {
+    paths::data_dir().join("last.replay")
+}
+
+// Where a game quit mid-round is kept until it's resumed
+pub fn suspended_path() -> PathBuf {
+    paths::data_dir().join("suspended.replay")
+}
+
+// Parses a numeric field, reporting malformed files as InvalidData
+fn parse_field<T: std::str::FromStr>(value: &str) -> std::io::Result<T> {
+    value.parse().map_err(|_| invalid(&format!("bad number: {value}")))
+}
+
+// Builds an InvalidData error for malformed replay files
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
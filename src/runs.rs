@@ -0,0 +1,47 @@
+// History of every hardcore run
+// Design Decision: An append-only tab-separated log (finish time, seed, duration, score,
+// board) opens in any spreadsheet and never has to be rewritten, however many attempts pile up
+use crate::lang;
+use crate::paths;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Appends one finished run to the history, returning how many runs it now holds
+pub fn log(seed: u64, duration: Duration, score: u32, board: &str) -> std::io::Result<usize> {
+    let path = history_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let finished = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{finished}\t{seed}\t{}\t{score}\t{board}", duration.as_millis())?;
+    Ok(runs().len())
+}
+
+// One line for `snekrs stats`, once there is at least one run
+pub fn summary() -> Option<String> {
+    let runs = runs();
+    let best = runs.iter().map(|(score, _)| *score).max()?;
+    let time: Duration = runs.iter().map(|(_, duration)| *duration).sum();
+    Some((lang::text().hardcore_runs)(runs.len(), best, time.as_secs()))
+}
+
+// Score and duration of every logged run, skipping lines that don't parse
+fn runs() -> Vec<(u32, Duration)> {
+    fs::read_to_string(history_path())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let millis = fields.get(2)?.parse().ok()?;
+            Some((fields.get(3)?.parse().ok()?, Duration::from_millis(millis)))
+        })
+        .collect()
+}
+
+// Where the history is kept
+pub fn history_path() -> PathBuf {
+    paths::data_dir().join("hardcore-runs.tsv")
+}
@@ -0,0 +1,141 @@
+// Persistent high score table
+// Design Decision: A small tab-separated file is easy to inspect by hand and needs no
+// extra crates; unreadable lines are skipped rather than losing the whole table
+//
+// File format: one entry per line, best first, then the best result of each daily challenge
+//   <score>\t<YYYY-MM-DD>\t<board settings>
+//   daily\t<score>\t<YYYY-MM-DD of the challenge>\t<board settings>
+use crate::paths;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Entries kept in the table
+const TABLE_SIZE: usize = 10;
+
+// One finished game in the table
+pub struct Entry {
+    pub score: u32,
+    pub date: String,   // Day the game was played (UTC)
+    pub board: String,  // Board settings, e.g. "maze, seed 42"
+}
+
+// The saved top scores, best first
+// Design Decision: Daily challenges get their own list, one entry per day, so a lucky daily
+// board can't crowd out the regular table (older builds skip the daily lines)
+pub struct HighScores {
+    entries: Vec<Entry>,
+    daily: Vec<Entry>,  // Best result of each daily challenge played, newest first
+}
+
+impl HighScores {
+    // Reads the table, starting empty when there is no file yet
+    pub fn load() -> HighScores {
+        let mut scores = HighScores { entries: Vec::new(), daily: Vec::new() };
+        for line in fs::read_to_string(table_path()).unwrap_or_default().lines() {
+            match line.strip_prefix("daily\t") {
+                Some(line) => scores.daily.extend(parse_entry(line)),
+                None => scores.entries.extend(parse_entry(line)),
+            }
+        }
+        scores
+    }
+
+    // Entries, best first
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    // Adds a score dated today if it makes the table, returning its rank (0-based)
+    // Design Decision: Ties go below older entries so the first to reach a score keeps its spot
+    pub fn add(&mut self, score: u32, board: String) -> Option<usize> {
+        if score == 0 {
+            return None;
+        }
+        let rank = self.entries.iter().position(|entry| entry.score < score).unwrap_or(self.entries.len());
+        if rank >= TABLE_SIZE {
+            return None;
+        }
+        self.entries.insert(rank, Entry { score, date: today(), board });
+        self.entries.truncate(TABLE_SIZE);
+        Some(rank)
+    }
+
+    // Best result of each daily challenge played, newest first
+    pub fn daily(&self) -> &[Entry] {
+        &self.daily
+    }
+
+    // Records a daily challenge result, returning whether it beat that day's best
+    pub fn add_daily(&mut self, date: &str, score: u32, board: String) -> bool {
+        match self.daily.iter_mut().find(|entry| entry.date == date) {
+            Some(best) if best.score >= score => false,
+            Some(best) => {
+                (best.score, best.board) = (score, board);
+                true
+            }
+            None => {
+                let at = self.daily.iter().position(|entry| entry.date.as_str() < date).unwrap_or(self.daily.len());
+                self.daily.insert(at, Entry { score, date: date.to_string(), board });
+                true
+            }
+        }
+    }
+
+    // Writes the table back to disk
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = table_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text: String = self
+            .entries
+            .iter()
+            .map(|entry| format!("{}\t{}\t{}\n", entry.score, entry.date, entry.board))
+            .chain(self.daily.iter().map(|entry| format!("daily\t{}\t{}\t{}\n", entry.score, entry.date, entry.board)))
+            .collect();
+        fs::write(path, text)
+    }
+}
+
+// Reads one `<score>\t<date>\t<board>` line
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut fields = line.splitn(3, '\t');
+    Some(Entry {
+        score: fields.next()?.parse().ok()?,
+        date: fields.next()?.to_string(),
+        board: fields.next()?.to_string(),
+    })
+}
+
+// Where the table is kept
+fn table_path() -> PathBuf {
+    paths::config_dir().join("highscores.tsv")
+}
+
+// Today's date as YYYY-MM-DD (UTC)
+fn today() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    date((seconds / 86_400) as i64)
+}
+
+// A day counted from 1970-01-01 as YYYY-MM-DD
+pub fn date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// Converts days since 1970-01-01 to a calendar date
+// Design Decision: Howard Hinnant's civil_from_days, to avoid a date crate for one label
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
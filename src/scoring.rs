@@ -0,0 +1,23 @@
+// Points awarded for eating food
+// Design Decision: Every bonus (difficulty food value, multiplier power-ups) is applied in one
+// place, so GameState::update only asks how much a bite was worth
+use crate::effects::Effects;
+use crate::food::FoodKind;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Scoring {
+    food_value: u32,  // Points per food from the difficulty preset
+}
+
+impl Scoring {
+    // Scoring for a game whose difficulty pays `food_value` per food
+    pub fn new(food_value: u32) -> Self {
+        Scoring { food_value }
+    }
+
+    // Points a player scores for eating food of a kind, with their running multiplier applied
+    pub fn food(&self, kind: FoodKind, player: usize, effects: &Effects) -> u32 {
+        kind.points() * self.food_value * effects.multiplier(player)
+    }
+}
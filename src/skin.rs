@@ -0,0 +1,145 @@
+// Snake skins: how player one's body is patterned, on top of whatever theme is in use
+// Design Decision: A skin only ever sees a segment's tone (how many moves ago that segment was
+// the head), never its place in the body, so the pattern travels with the snake and a move
+// only redraws the cells it touches; the choice is kept in the data directory next to the
+// achievements some of the skins are unlocked by
+use crate::achievements::Achievements;
+use crate::paths;
+use crossterm::style::Color;
+use std::fs;
+use std::path::PathBuf;
+
+// Segments in each band of the striped skin
+const STRIPE: u8 = 3;
+
+// Segments from one end of the gradient to the other
+const RAMP: u8 = 12;
+
+// Ends of the gradient skin's ramp (green to deep blue)
+const RAMP_FROM: (u8, u8, u8) = (0x2e, 0xcc, 0x71);
+const RAMP_TO: (u8, u8, u8) = (0x1a, 0x52, 0x76);
+
+// Colors the rainbow skin cycles through, one per segment
+const RAINBOW: [Color; 6] = [Color::Red, Color::Yellow, Color::Green, Color::Cyan, Color::Blue, Color::Magenta];
+
+// Alternating glyphs of the scales skin
+const SCALES: [char; 2] = ['◆', '◇'];
+
+// Every skin, in the order the Settings screen cycles through them
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Skin {
+    Classic,   // The theme's own body glyph and color
+    Striped,   // Bands of the body color and white
+    Scales,    // Alternating filled and hollow diamonds
+    Gradient,  // Fades from green to deep blue and back along the body
+    Rainbow,   // A different color on every segment
+}
+
+impl Skin {
+    // Parses a saved name (classic, striped, scales, gradient, rainbow)
+    pub fn parse(name: &str) -> Option<Skin> {
+        match name {
+            "classic" => Some(Skin::Classic),
+            "striped" => Some(Skin::Striped),
+            "scales" => Some(Skin::Scales),
+            "gradient" => Some(Skin::Gradient),
+            "rainbow" => Some(Skin::Rainbow),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Skin::Classic => "classic",
+            Skin::Striped => "striped",
+            Skin::Scales => "scales",
+            Skin::Gradient => "gradient",
+            Skin::Rainbow => "rainbow",
+        }
+    }
+
+    // The skin after this one, wrapping around
+    pub fn next(self) -> Skin {
+        match self {
+            Skin::Classic => Skin::Striped,
+            Skin::Striped => Skin::Scales,
+            Skin::Scales => Skin::Gradient,
+            Skin::Gradient => Skin::Rainbow,
+            Skin::Rainbow => Skin::Classic,
+        }
+    }
+
+    // Id of the achievement that unlocks this skin (None = always available)
+    pub fn unlocked_by(self) -> Option<&'static str> {
+        match self {
+            Skin::Classic | Skin::Striped => None,
+            Skin::Scales => Some("hungry"),
+            Skin::Gradient => Some("survivor"),
+            Skin::Rainbow => Some("centurion"),
+        }
+    }
+
+    // The skin an achievement unlocks, if any
+    pub fn reward(achievement: &str) -> Option<Skin> {
+        [Skin::Scales, Skin::Gradient, Skin::Rainbow].into_iter().find(|skin| skin.unlocked_by() == Some(achievement))
+    }
+
+    // Whether the player can pick this skin
+    pub fn is_unlocked(self, achievements: &Achievements) -> bool {
+        self.unlocked_by().is_none_or(|id| achievements.has(id))
+    }
+
+    // The next skin the player has unlocked, wrapping around (Classic always is)
+    pub fn next_unlocked(self, achievements: &Achievements) -> Skin {
+        let mut skin = self.next();
+        while !skin.is_unlocked(achievements) {
+            skin = skin.next();
+        }
+        skin
+    }
+
+    // Glyph for a body segment of this tone, when the skin replaces the theme's
+    pub fn glyph(self, tone: u8) -> Option<char> {
+        match self {
+            Skin::Scales => Some(SCALES[usize::from(tone % 2)]),
+            _ => None,
+        }
+    }
+
+    // Color for a body segment of this tone, when the skin replaces the theme's
+    pub fn color(self, tone: u8) -> Option<Color> {
+        match self {
+            Skin::Striped => ((tone / STRIPE) % 2 == 1).then_some(Color::White),
+            Skin::Gradient => {
+                // Up the ramp and back down, so neighbouring segments never jump in color
+                let step = tone % (RAMP * 2);
+                let step = if step < RAMP { step } else { RAMP * 2 - step };
+                let mix = |from: u8, to: u8| (i32::from(from) + (i32::from(to) - i32::from(from)) * i32::from(step) / i32::from(RAMP)) as u8;
+                Some(Color::Rgb { r: mix(RAMP_FROM.0, RAMP_TO.0), g: mix(RAMP_FROM.1, RAMP_TO.1), b: mix(RAMP_FROM.2, RAMP_TO.2) })
+            }
+            Skin::Rainbow => Some(RAINBOW[usize::from(tone) % RAINBOW.len()]),
+            Skin::Classic | Skin::Scales => None,
+        }
+    }
+
+    // The skin picked last time, if it's still unlocked (Classic otherwise)
+    pub fn load() -> Skin {
+        let saved = fs::read_to_string(skin_path()).ok().and_then(|name| Skin::parse(name.trim()));
+        let achievements = Achievements::load(0);
+        saved.filter(|skin| skin.is_unlocked(&achievements)).unwrap_or(Skin::Classic)
+    }
+
+    // Remembers the skin for the next launch
+    pub fn save(self) -> std::io::Result<()> {
+        let path = skin_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, format!("{}\n", self.name()))
+    }
+}
+
+// Where the chosen skin is kept
+fn skin_path() -> PathBuf {
+    paths::data_dir().join("skin.txt")
+}
@@ -0,0 +1,84 @@
+// Snake entity: body, heading and per-player score
+// Design Decision: Pulled out of Game so several snakes can share one board
+use crate::game::{Direction, Point, Position};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// Most direction changes buffered ahead of the snake's moves
+// Design Decision: Two covers a quick double tap like "up then left" within one tick, while
+// mashing keys can't queue up turns the player has long forgotten about
+const MAX_QUEUED_TURNS: usize = 2;
+
+// Tones repeat after this many moves; it divides evenly by every skin pattern's length, so the
+// wrap never shows
+pub const TONES: u64 = 240;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snake {
+    pub body: VecDeque<Position>,  // Using VecDeque for O(1) push/pop at both ends
+    pub direction: Direction,      // Current movement direction
+    turns: VecDeque<Direction>,           // Buffered turns, one applied per move, oldest first
+    pub score: u32,                // Points scored by this snake
+    pub eaten: u32,                // Food eaten by this snake
+    pub alive: bool,               // Cleared when the snake crashes
+    pub moves: u64,                // Moves made, which dates every segment for skins
+}
+
+impl Snake {
+    // Creates a one-segment snake at the spawn point
+    pub fn new(spawn: Position, direction: Direction) -> Self {
+        let mut body = VecDeque::new();
+        body.push_back(spawn);
+        Snake {
+            body,
+            direction,
+            turns: VecDeque::new(),
+            score: 0,
+            eaten: 0,
+            alive: true,
+            moves: 0,
+        }
+    }
+
+    // Current head position
+    pub fn head(&self) -> Position {
+        *self.body.front().unwrap()
+    }
+
+    // Skin tone of the segment at `index` from the head: when it was laid down, so it keeps
+    // its tone as the snake moves on
+    pub fn tone(&self, index: usize) -> u8 {
+        (self.moves.saturating_sub(index as u64) % TONES) as u8
+    }
+
+    // Whether any segment of this snake covers the position
+    pub fn occupies(&self, pos: &Position) -> bool {
+        self.body.contains(pos)
+    }
+
+    // Direction the snake will be heading once every queued turn is made
+    pub fn heading(&self) -> Direction {
+        self.turns.back().copied().unwrap_or(self.direction)
+    }
+
+    // Queues a direction change, ignoring 180° reversals into the body and turns that wouldn't
+    // change anything; each is judged against the heading the turns before it leave behind.
+    // Returns whether the turn was queued
+    pub fn steer(&mut self, direction: Direction) -> bool {
+        let heading = self.heading();
+        let queued = direction != heading && direction != heading.opposite() && self.turns.len() < MAX_QUEUED_TURNS;
+        if queued {
+            self.turns.push_back(direction);
+        }
+        queued
+    }
+
+    // Applies the oldest queued turn and returns where the head moves next (possibly off the
+    // board, which the game state resolves)
+    pub fn advance_direction(&mut self) -> Point {
+        if let Some(direction) = self.turns.pop_front() {
+            self.direction = direction;
+        }
+        self.head().step(self.direction)
+    }
+}
@@ -0,0 +1,97 @@
+// Sound effects for eating, power-ups and crashes, and the volume they play at
+// Design Decision: Sounds hang off the game's event bus, so the engine never knows about audio.
+// Effects ring the terminal bell, which needs nothing beyond the terminal the game already has
+// (terminals that flash instead of beeping flash). Music needs a real audio backend: rodio
+// itself fetches fine, but on Linux it builds alsa-sys, which links the system ALSA library and
+// fails wherever its development files aren't installed, so it's the optional `rodio` feature
+// (see music.rs) rather than something every build has to link
+use crate::events::GameEvent;
+use crate::game::Game;
+use crate::lang;
+use crossterm::{execute, style::Print};
+use std::io::stdout;
+
+// How loud the game is, as a percentage in steps of a quarter, set in the config file and on
+// the Settings screen
+// Design Decision: A bell can't be made quieter, so it rings at every level but off; the levels
+// in between set the music's loudness
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct Volume(u8);
+
+impl Volume {
+    pub(crate) const OFF: Volume = Volume(0);
+    pub(crate) const FULL: Volume = Volume(100);
+    const STEP: u8 = 25;
+
+    // A volume from a percentage, rounded down to a step
+    pub(crate) fn percent(value: i64) -> Option<Volume> {
+        let value = u8::try_from(value).ok().filter(|&value| value <= 100)?;
+        Some(Volume(value / Volume::STEP * Volume::STEP))
+    }
+
+    // The next step up, wrapping from full back to off
+    pub(crate) fn next(self) -> Volume {
+        if self == Volume::FULL {
+            Volume::OFF
+        } else {
+            Volume(self.0 + Volume::STEP)
+        }
+    }
+
+    pub(crate) fn is_muted(self) -> bool {
+        self == Volume::OFF
+    }
+
+    // What the music's samples are scaled by
+    #[cfg(feature = "rodio")]
+    pub(crate) fn gain(self) -> f32 {
+        f32::from(self.0) / 100.0
+    }
+
+    // How the Settings screen shows it
+    pub(crate) fn label(self) -> String {
+        if self.is_muted() {
+            lang::text().off.to_string()
+        } else {
+            format!("{}%", self.0)
+        }
+    }
+}
+
+// What a sound effect marks
+#[derive(Clone, Copy, PartialEq)]
+enum Sound {
+    Eat,      // A snake ate something, poison included
+    PowerUp,  // A snake collected a power-up
+    Crash,    // A snake died
+}
+
+impl Sound {
+    // The effect for a game event, if it has one
+    fn for_event(event: &GameEvent) -> Option<Sound> {
+        match event {
+            GameEvent::FoodEaten { .. } => Some(Sound::Eat),
+            GameEvent::PowerUpCollected { .. } => Some(Sound::PowerUp),
+            GameEvent::Collision { .. } => Some(Sound::Crash),
+            GameEvent::DirectionChanged { .. }
+            | GameEvent::NearMiss { .. }
+            | GameEvent::InputMade(_)
+            | GameEvent::Ticked { .. }
+            | GameEvent::GameOver => None,
+        }
+    }
+
+    // Plays the effect, ignoring a terminal that can't take it
+    fn play(self) {
+        let _ = execute!(stdout(), Print('\x07'));
+    }
+}
+
+// Plays the game's sound effects as its events happen
+pub(crate) fn attach(game: &mut Game) {
+    game.subscribe(|event| {
+        if let Some(sound) = Sound::for_event(event) {
+            sound.play();
+        }
+    });
+}
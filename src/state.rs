@@ -0,0 +1,836 @@
+// Board-level game state: snakes and the entities around them, plus the rules that move them
+// Design Decision: Kept apart from Game (timing, input, terminal) so bots and other
+// subsystems can inspect the board without touching the runtime
+use crate::effects::Effects;
+use crate::entity::{Entity, Role};
+use crate::events::GameEvent;
+use crate::food::{FoodKind, POISON_SHRINK};
+use crate::game::{Direction, Point, Position, PLAYER_GLYPHS, TICK_INTERVAL};
+use crate::config::GameConfig;
+use crate::level::{Level, PORTAL_GLYPH};
+use crate::mode::Mode;
+use crate::mods::{GameMod, Spawn};
+use crate::powerup::{PowerUp, PICKUP_CHANCE, SHRINK_SEGMENTS};
+use crate::scoring::Scoring;
+use crate::snake::Snake;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Smallest open area, in cells per side, that closing walls or the zone leave
+const MIN_ARENA: u16 = 5;
+
+// Glyph for empty cells inside the closing zone of battle royale mode
+pub const ZONE_GLYPH: char = '·';
+
+// Seeded source for every random choice on a board: the generator rand's StdRng wraps, named
+// directly because StdRng can't be serialized and a saved state has to carry where its RNG is
+pub type BoardRng = ChaCha12Rng;
+
+// Random cells tried for a new item before giving up on a board with no room left
+const SPAWN_ATTEMPTS: usize = 1000;
+
+// Design Decision: Serialize/Deserialize give the whole board one canonical form for saves, the
+// network and outside tools. Mods are code rather than data, so they're left out and whoever
+// restores a state adds them back with add_mod
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameState {
+    pub snakes: Vec<Snake>,    // One snake per player, player one first
+    entities: Vec<Entity>,            // Food, power-ups and obstacles, oldest first
+    portals: Vec<(Position, Position)>, // Linked cells that send a head to the other end
+    food_spots: Vec<Position>,        // Preferred food locations from a level (empty = anywhere)
+    width: u16,                       // Board width including border
+    height: u16,                      // Board height including border
+    inset: u16,                       // Rings of wall closed inside the border so far
+    zone: u16,                        // Rings of damaging zone inside the border so far
+    until_shrink: Option<Duration>,   // Game time until the next ring closes (None = never)
+    scoring: Scoring,                 // Points awarded for each bite
+    mode: Mode,                       // Rules variant for the round
+    game_over: bool,                  // Set once a snake crashes or the round is abandoned
+    rng: BoardRng,                    // Seeded source for every random choice on the board
+    events: Vec<GameEvent>,           // What happened since the game last took them
+    #[serde(skip)]
+    mods: Vec<Box<dyn GameMod>>,      // Rule hooks, called in the order they were added
+}
+
+impl GameState {
+    // Builds the starting state for a level with the given number of snakes
+    // Design Decision: Takes the RNG that built the level so one seed reproduces the whole run
+    pub fn new(level: Level, players: usize, settings: &GameConfig, rng: BoardRng) -> Self {
+        let mut state = GameState {
+            snakes: GameState::spawn_snakes(&level, players),
+            entities: level.walls.into_iter().map(Entity::obstacle).collect(),
+            portals: level.portals,
+            food_spots: level.food,
+            width: level.width,
+            height: level.height,
+            inset: 0,
+            zone: 0,
+            until_shrink: None,
+            scoring: Scoring::new(settings.difficulty.food_value()),
+            mode: settings.mode,
+            game_over: false,
+            rng,
+            events: Vec::new(),
+            mods: settings.mods.iter().map(|kind| kind.build()).collect(),
+        };
+        state.schedule_shrink();
+        state.spawn_food();
+        state
+    }
+
+    // Adds a mod on top of the ones already running, from the next food spawn and tick on
+    pub fn add_mod(&mut self, game_mod: Box<dyn GameMod>) {
+        self.mods.push(game_mod);
+    }
+
+    // Places one snake per player on the level's spawn points
+    fn spawn_snakes(level: &Level, players: usize) -> Vec<Snake> {
+        let mut snakes = vec![Snake::new(level.spawn, Direction::Right)];
+        if players > 1 {
+            snakes.push(Snake::new(level.second_spawn(), Direction::Left));
+        }
+        snakes
+    }
+
+    // Swaps in a new board while keeping the score
+    // Design Decision: Used between campaign stages so progress carries over
+    pub fn load_level(&mut self, level: Level) {
+        let scores: Vec<(u32, u32)> = self.snakes.iter().map(|snake| (snake.score, snake.eaten)).collect();
+        self.snakes = GameState::spawn_snakes(&level, self.snakes.len());
+        for (snake, (score, eaten)) in self.snakes.iter_mut().zip(scores) {
+            snake.score = score;
+            snake.eaten = eaten;
+        }
+        self.width = level.width;
+        self.height = level.height;
+        self.inset = 0;
+        self.zone = 0;
+        self.schedule_shrink();
+        self.entities = level.walls.into_iter().map(Entity::obstacle).collect();
+        self.portals = level.portals;
+        self.food_spots = level.food;
+        self.spawn_food();
+    }
+
+    // Random source shared with board generation between campaign stages
+    pub fn rng(&mut self) -> &mut BoardRng {
+        &mut self.rng
+    }
+
+    // Read-only access to a snake
+    pub fn snake(&self, index: usize) -> &Snake {
+        &self.snakes[index]
+    }
+
+    // Everything on the board besides the snakes
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    // Puts an entity on the board if its cell is free floor, returning whether it went down
+    // (for mods and scripted boards; ordinary games spawn everything themselves)
+    pub fn place(&mut self, entity: Entity) -> bool {
+        let free = self.is_free_for_food(&entity.pos);
+        if free {
+            self.entities.push(entity);
+        }
+        free
+    }
+
+    // Position of the oldest food worth chasing (None while only poison is out)
+    pub fn edible_food(&self) -> Option<Position> {
+        self.entities
+            .iter()
+            .find(|entity| entity.food_kind().is_some_and(|kind| kind != FoodKind::Poison))
+            .map(|entity| entity.pos)
+    }
+
+    // Cells holding food or a power-up
+    pub fn item_cells(&self) -> Vec<Position> {
+        self.entities.iter().filter(|entity| entity.role != Role::Obstacle).map(|entity| entity.pos).collect()
+    }
+
+    // Whether a cell holds an obstacle
+    fn is_obstacle(&self, pos: &Position) -> bool {
+        self.entities.iter().any(|entity| entity.role == Role::Obstacle && entity.pos == *pos)
+    }
+
+    // Rings of wall and of zone closed in so far
+    pub fn rings(&self) -> (u16, u16) {
+        (self.inset, self.zone)
+    }
+
+    // Board width including border
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    // Board height including border
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    // Whether the round has ended
+    pub fn is_over(&self) -> bool {
+        self.game_over
+    }
+
+    // Ends the round early (quit key or lost connection)
+    pub fn end(&mut self) {
+        if !self.game_over {
+            self.game_over = true;
+            self.events.push(GameEvent::GameOver);
+        }
+    }
+
+    // Ends the round after a crash, reporting every snake that died
+    fn end_in_crash(&mut self) {
+        for (player, snake) in self.snakes.iter().enumerate() {
+            if !snake.alive {
+                self.events.push(GameEvent::Collision { player });
+            }
+        }
+        self.end();
+    }
+
+    // Hands over everything that happened since the last call, oldest first
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    // Number of cells a snake can occupy (the open area minus the obstacles left in it)
+    pub fn floor_cells(&self) -> usize {
+        let open = (self.width - 2 - 2 * self.inset) as usize * (self.height - 2 - 2 * self.inset) as usize;
+        open - self.entities
+            .iter()
+            .filter(|entity| entity.role == Role::Obstacle && !self.is_border(&entity.pos))
+            .count()
+    }
+
+    // Game time until the walls or the zone next close in, in modes that shrink the board
+    pub fn next_shrink(&self) -> Option<Duration> {
+        self.until_shrink
+    }
+
+    // Combined score of every player
+    pub fn score(&self) -> u32 {
+        self.snakes.iter().map(|snake| snake.score).sum()
+    }
+
+    // Whether any snake covers the position
+    pub fn is_snake(&self, pos: &Position) -> bool {
+        self.snakes.iter().any(|snake| snake.occupies(pos))
+    }
+
+    // Whether a cell is a portal end
+    fn is_portal(&self, pos: &Position) -> bool {
+        self.portals.iter().any(|(one, two)| pos == one || pos == two)
+    }
+
+    // Where a head stepping onto a cell ends up: the other end for a portal, the cell itself otherwise
+    // (portals swallowed by closing walls stop working)
+    pub fn through_portal(&self, pos: Position) -> Position {
+        self.portals
+            .iter()
+            .filter(|(one, two)| !self.is_border(one) && !self.is_border(two))
+            .find_map(|&(one, two)| match pos {
+                pos if pos == one => Some(two),
+                pos if pos == two => Some(one),
+                _ => None,
+            })
+            .unwrap_or(pos)
+    }
+
+    // Whether food or a power-up may go onto a cell (nothing lethal, no portal and no other entity there)
+    fn is_free_for_food(&self, pos: &Position) -> bool {
+        !self.is_blocked(pos) && !self.is_portal(pos) && !self.entities.iter().any(|entity| entity.pos == *pos)
+    }
+
+    // Whether a cell is part of the border, including any rings that have closed in on it
+    // Design Decision: Every boundary check goes through here rather than the board size, so
+    // the play area can shrink during a round
+    fn is_border(&self, pos: &Position) -> bool {
+        self.within_ring(pos, self.inset)
+    }
+
+    // Whether a cell lies in the damaging zone of battle royale mode
+    fn in_zone(&self, pos: &Position) -> bool {
+        self.zone > 0 && self.within_ring(pos, self.zone)
+    }
+
+    // Whether a cell is no more than `depth` rings in from the board's edge
+    fn within_ring(&self, pos: &Position, depth: u16) -> bool {
+        pos.x <= depth || pos.y <= depth || pos.x >= self.width - 1 - depth || pos.y >= self.height - 1 - depth
+    }
+
+    // Whether a cell is part of the border or an obstacle
+    pub fn is_wall(&self, pos: &Position) -> bool {
+        self.is_border(pos) || self.is_obstacle(pos)
+    }
+
+    // Whether moving onto a cell would be fatal (border, obstacle or any snake)
+    pub fn is_blocked(&self, pos: &Position) -> bool {
+        self.is_wall(pos) || self.is_snake(pos)
+    }
+
+    // Picks a cell for new food, or None when no free cell turns up
+    // Design Decision: Level food spots win when one is free, otherwise fall back to anywhere
+    fn generate_food(&mut self) -> Option<Position> {
+        let free_spots: Vec<Position> = self.food_spots
+            .iter()
+            .filter(|pos| self.is_free_for_food(pos))
+            .copied()
+            .collect();
+        if let Some(spot) = free_spots.choose(&mut self.rng) {
+            return Some(*spot);
+        }
+        self.free_cell()
+    }
+
+    // Random cell inside the border that an item may go onto, giving up after SPAWN_ATTEMPTS
+    // tries so a board with no room left skips the spawn instead of hanging
+    fn free_cell(&mut self) -> Option<Position> {
+        for _ in 0..SPAWN_ATTEMPTS {
+            let pos = self.random_cell();
+            if self.is_free_for_food(&pos) {
+                return Some(pos);
+            }
+        }
+        None
+    }
+
+    // Random cell inside the border
+    fn random_cell(&mut self) -> Position {
+        let inset = self.inset;
+        Position {
+            // Generate position within game bounds (excluding walls)
+            x: self.rng.gen_range(1 + inset..self.width - 1 - inset),
+            y: self.rng.gen_range(1 + inset..self.height - 1 - inset),
+        }
+    }
+
+    // Adds food of a random kind in a valid position (not on a snake, an obstacle or another
+    // entity), or nothing when the board has no room for it
+    fn spawn_food(&mut self) {
+        let Some(pos) = self.generate_food() else {
+            return;
+        };
+        let mut spawn = Spawn { pos, kind: FoodKind::roll(&mut self.rng) };
+        let mut mods = std::mem::take(&mut self.mods);
+        for game_mod in &mut mods {
+            game_mod.modify_spawn(&mut spawn, self);
+        }
+        self.mods = mods;
+        // A mod can't drop food onto a snake, a wall, a portal or the pickup
+        if spawn.pos != pos && !self.is_free_for_food(&spawn.pos) {
+            spawn.pos = pos;
+        }
+        let mut food = Entity::food(spawn.pos, spawn.kind);
+        // Fleeing mode's food runs from the snakes
+        food.flee = (self.mode == Mode::Fleeing).then_some(0);
+        self.entities.push(food);
+    }
+
+    // Brings out a power-up at a random free cell, if one turns up
+    fn spawn_pickup(&mut self) {
+        let Some(pos) = self.free_cell() else {
+            return;
+        };
+        let power = PowerUp::roll(&mut self.rng);
+        self.entities.push(Entity::pickup(pos, power));
+    }
+
+    // Updates game state (snake movement, collisions, food and pickup collection), queueing
+    // an event for each thing that happened; `elapsed` is the game time this tick stands for,
+    // which ages the food and pickup
+    // Design Decision: Single function for all state updates maintains consistency; running
+    // effects come in from Game so the rules can bend without the state owning any timers
+    pub fn update(&mut self, effects: &Effects, elapsed: Duration) {
+        if self.game_over {
+            return;
+        }
+
+        // Calculate every new head first so all snakes move simultaneously, sending heads that
+        // step onto a portal straight out of its other end (and, in zen mode, heads that hit
+        // the border around to the far side)
+        // Where each head would have gone without this move's turn, to spot close calls
+        let straight_on: Vec<Option<Position>> = self.snakes
+            .iter()
+            .map(|snake| snake.head().step(snake.direction).cell())
+            .collect();
+        let steps: Vec<Point> = self.snakes
+            .iter_mut()
+            .map(|snake| snake.advance_direction())
+            .collect();
+        let zen = self.mode == Mode::Zen;
+        let landed: Vec<Option<Position>> = steps
+            .into_iter()
+            .map(|point| self.resolve(point).map(|pos| self.through_portal(pos)))
+            .collect();
+        // A head that left the board stays put for the crash checks below, then crashes anyway
+        let new_heads: Vec<Position> = landed
+            .iter()
+            .zip(&self.snakes)
+            .map(|(pos, snake)| pos.unwrap_or(snake.head()))
+            .collect();
+
+        // Resolve what each head runs into: crashes under the usual rules, bites in zen mode
+        let bites = if zen {
+            self.zen_bites(&new_heads, effects)
+        } else {
+            self.resolve_crashes(&new_heads, effects);
+            for (snake, pos) in self.snakes.iter_mut().zip(&landed) {
+                snake.alive &= pos.is_some();
+            }
+            vec![None; new_heads.len()]
+        };
+
+        // Design Decision: The round ends as soon as any snake crashes
+        if self.snakes.iter().any(|snake| !snake.alive) {
+            self.end_in_crash();
+            return;
+        }
+
+        for (index, ((snake, new_head), bite)) in self.snakes.iter_mut().zip(new_heads).zip(bites).enumerate() {
+            // Move snake by adding new head
+            snake.body.push_front(new_head);
+            snake.moves += 1;
+            // A zen snake that ran into itself loses everything from the bitten segment back
+            // (food never sits under a snake, so there's nothing to eat on this move)
+            if let Some(segment) = bite {
+                snake.body.truncate(segment + 1);
+                continue;
+            }
+
+            // Handle food collection
+            let Some(kind) = self.entities.iter().filter(|entity| entity.pos == new_head).find_map(Entity::food_kind) else {
+                // Remove tail if no food was eaten
+                snake.body.pop_back();
+                continue;
+            };
+            let mut points = match kind {
+                FoodKind::Normal | FoodKind::Golden => {
+                    snake.eaten += 1;
+                    self.scoring.food(kind, index, effects)
+                }
+                FoodKind::Poison => {
+                    let length = snake.body.len().saturating_sub(1 + POISON_SHRINK).max(1);
+                    snake.body.truncate(length);
+                    0
+                }
+            };
+            for game_mod in &mut self.mods {
+                points = game_mod.on_food_eaten(snake, kind, points);
+            }
+            snake.score += points;
+            self.events.push(GameEvent::FoodEaten { player: index, kind, points });
+        }
+
+        // Battle royale: the zone wears down whoever is caught in it
+        if self.apply_zone_damage() {
+            self.end_in_crash();
+            return;
+        }
+
+        // Close calls: a snake that turned away from a wall or body right in front of it just
+        // grazed past it (nothing is lethal in zen mode)
+        if !zen {
+            for (player, (snake, ahead)) in self.snakes.iter().zip(&straight_on).enumerate() {
+                let dodged = ahead.filter(|pos| *pos != snake.head()).is_some_and(|pos| self.is_blocked(&pos));
+                if dodged {
+                    self.events.push(GameEvent::NearMiss { player });
+                }
+            }
+        }
+
+        // Eaten food is replaced, and so is food left out too long
+        let heads: Vec<Position> = self.snakes.iter().map(|snake| snake.head()).collect();
+        let mut spent = Vec::new();
+        self.entities.retain_mut(|entity| {
+            let Some(kind) = entity.food_kind() else {
+                return true;
+            };
+            let eaten = heads.contains(&entity.pos);
+            if eaten || entity.age(elapsed) {
+                spent.push(eaten && kind != FoodKind::Poison);
+                return false;
+            }
+            true
+        });
+        for fed in spent {
+            // Eating sometimes brings out a power-up
+            let pickup_out = self.entities.iter().any(|entity| entity.power().is_some());
+            if fed && !pickup_out && self.rng.gen_bool(PICKUP_CHANCE) {
+                self.spawn_pickup();
+            }
+            self.spawn_food();
+        }
+
+        // Power-ups are collected by whoever reaches them, and vanish when left too long
+        let mut collected = Vec::new();
+        self.entities.retain_mut(|entity| {
+            let Some(power) = entity.power() else {
+                return true;
+            };
+            if let Some(player) = heads.iter().position(|head| *head == entity.pos) {
+                collected.push((player, power));
+                return false;
+            }
+            !entity.age(elapsed)
+        });
+        for (player, power) in collected {
+            // Instant power-ups act here; timed ones are handed back for the effect timers
+            if power == PowerUp::Shrink {
+                let snake = &mut self.snakes[player];
+                let length = snake.body.len().saturating_sub(SHRINK_SEGMENTS).max(1);
+                snake.body.truncate(length);
+            }
+            self.events.push(GameEvent::PowerUpCollected { player, power });
+        }
+
+        // In survival and battle royale modes the walls or the zone close in on a timer
+        if let Some(left) = self.until_shrink {
+            let left = left.saturating_sub(elapsed);
+            self.until_shrink = Some(left);
+            if left.is_zero() {
+                self.close_ring();
+                if self.game_over {
+                    return;
+                }
+            }
+        }
+
+        // Fleeing entities run from whoever is closest, and a magnet drags food a cell toward
+        // the closest head holding one
+        let magnets: Vec<Position> = (0..self.snakes.len())
+            .filter(|&index| effects.is_active(index, PowerUp::Magnet))
+            .map(|index| self.snakes[index].head())
+            .collect();
+        for index in 0..self.entities.len() {
+            let mut entity = self.entities[index];
+            entity.flee(&heads, |pos| self.is_free_for_food(pos));
+            self.entities[index] = entity;
+            if entity.food_kind().is_none() {
+                continue;
+            }
+            let magnet = magnets
+                .iter()
+                .min_by_key(|head| head.x.abs_diff(entity.pos.x) + head.y.abs_diff(entity.pos.y));
+            if let Some(&head) = magnet {
+                entity.pull(head, |pos| self.is_free_for_food(pos));
+                self.entities[index] = entity;
+            }
+        }
+
+        // Mods get the last word on the tick
+        let mut mods = std::mem::take(&mut self.mods);
+        for game_mod in &mut mods {
+            game_mod.on_tick(self);
+        }
+        self.mods = mods;
+    }
+
+    // Kills every snake whose new head runs into the border, an obstacle or a snake, judged
+    // against the board as it was before anyone moved
+    // Design Decision: Deaths are decided up front so turn order never favors a player
+    fn resolve_crashes(&mut self, new_heads: &[Position], effects: &Effects) {
+        let mut tangled = false;
+        for (index, new_head) in new_heads.iter().enumerate() {
+            // A phasing snake slips through obstacles and itself, but the border and other snakes still count
+            let phasing = effects.is_active(index, PowerUp::Phase);
+            let hits_wall = self.is_border(new_head);
+            let hits_obstacle = !phasing && self.is_obstacle(new_head);
+            let hits_snake = self.snakes
+                .iter()
+                .enumerate()
+                .any(|(other, snake)| (other != index || !phasing) && snake.occupies(new_head));
+            let head_on = new_heads
+                .iter()
+                .enumerate()
+                .any(|(other, head)| other != index && head == new_head);
+            if hits_wall || hits_obstacle || hits_snake || head_on {
+                self.snakes[index].alive = false;
+            }
+            let hits_partner = self.snakes.iter().enumerate().any(|(other, snake)| other != index && snake.occupies(new_head));
+            tangled |= hits_partner || head_on;
+        }
+        // Co-op partners who run into each other both go down: the team crashed, not one of them
+        if tangled && self.mode == Mode::Coop {
+            for snake in &mut self.snakes {
+                snake.alive = false;
+            }
+        }
+    }
+
+    // Zen mode's stand-in for crashes: obstacles and other snakes are passed over, and a head
+    // running into its own body bites it, giving the bitten segment's index per snake
+    fn zen_bites(&self, new_heads: &[Position], effects: &Effects) -> Vec<Option<usize>> {
+        new_heads
+            .iter()
+            .enumerate()
+            .map(|(index, new_head)| {
+                let body = &self.snakes[index].body;
+                let bitten = body.iter().position(|pos| pos == new_head);
+                bitten.filter(|_| !effects.is_active(index, PowerUp::Phase))
+            })
+            .collect()
+    }
+
+    // The board cell a head stepping to a point lands on, before portals (None when it has
+    // left the board)
+    // Design Decision: Zen mode brings heads back in on the far side of the open area; in
+    // every other mode the board's edge is the end of the world
+    fn resolve(&self, point: Point) -> Option<Position> {
+        match self.mode {
+            Mode::Zen => Some(self.wrap(point)),
+            _ => point.on_board(self.width, self.height),
+        }
+    }
+
+    // Brings a point on or past the border back in on the far side of the open area
+    fn wrap(&self, point: Point) -> Position {
+        let wrap = |value: i32, size: u16| {
+            let (first, last) = (1 + self.inset, size - 2 - self.inset);
+            match value {
+                _ if value < i32::from(first) => last,
+                _ if value > i32::from(last) => first,
+                _ => value as u16,
+            }
+        };
+        Position { x: wrap(point.x, self.width), y: wrap(point.y, self.height) }
+    }
+
+    // Starts the countdown to the next ring closing, if the mode shrinks the board and the
+    // open area can still give up a ring
+    fn schedule_shrink(&mut self) {
+        let next = self.inset.max(self.zone) + 1;
+        let room = |size: u16| size.saturating_sub(2 + 2 * next) >= MIN_ARENA;
+        self.until_shrink = self.mode.shrink_interval().filter(|_| room(self.width) && room(self.height));
+    }
+
+    // Walls off the outermost ring of the open area: a head caught in it is crushed, bodies
+    // are cut where they meet the wall, and food or a pickup in it moves on
+    // Design Decision: In battle royale the ring becomes zone instead, which hurts rather
+    // than kills, so the snakes get a moment to fight their way back in
+    fn close_ring(&mut self) {
+        if self.mode == Mode::Royale {
+            self.zone += 1;
+            self.schedule_shrink();
+            return;
+        }
+        self.inset += 1;
+        self.schedule_shrink();
+
+        for index in 0..self.snakes.len() {
+            if self.is_border(&self.snakes[index].head()) {
+                self.snakes[index].alive = false;
+            } else if let Some(cut) = self.snakes[index].body.iter().position(|pos| self.is_border(pos)) {
+                self.snakes[index].body.truncate(cut);
+            }
+        }
+        // A head caught under the new wall is crushed, which ends the round like any other crash
+        if self.snakes.iter().any(|snake| !snake.alive) {
+            self.end_in_crash();
+            return;
+        }
+        let mut buried = 0;
+        let entities = std::mem::take(&mut self.entities);
+        self.entities = entities
+            .into_iter()
+            .filter(|entity| {
+                let gone = entity.role != Role::Obstacle && self.is_border(&entity.pos);
+                buried += usize::from(gone && entity.food_kind().is_some());
+                !gone
+            })
+            .collect();
+        for _ in 0..buried {
+            self.spawn_food();
+        }
+    }
+
+    // Takes a tail segment off every snake whose head is in the zone, killing any that have
+    // nothing left but the head; returns whether one died
+    fn apply_zone_damage(&mut self) -> bool {
+        let mut died = false;
+        for index in 0..self.snakes.len() {
+            if !self.in_zone(&self.snakes[index].head()) {
+                continue;
+            }
+            let snake = &mut self.snakes[index];
+            if snake.body.len() > 1 {
+                snake.body.pop_back();
+            } else {
+                snake.alive = false;
+                died = true;
+            }
+        }
+        died
+    }
+
+    // Glyph shown for a board cell, border included
+    // Design Decision: One lookup shared by the local renderer and the network stream
+    pub fn glyph_at(&self, pos: Position) -> char {
+        if self.is_border(&pos) {
+            return '#';  // Border, closed-in rings included
+        }
+        if let Some(index) = self.snakes.iter().position(|snake| snake.occupies(&pos)) {
+            let (head, body) = PLAYER_GLYPHS[index];
+            return if self.snakes[index].head() == pos { head } else { body };
+        }
+        // Items about to expire blink, leaving their cell blank on alternate phases
+        if let Some(entity) = self.entities.iter().find(|entity| entity.pos == pos) {
+            if entity.is_shown() { entity.glyph() } else { ' ' }
+        } else if self.is_portal(&pos) {
+            PORTAL_GLYPH
+        } else if self.in_zone(&pos) {
+            ZONE_GLYPH
+        } else {
+            ' '  // Empty space
+        }
+    }
+}
+
+// The state one move on from `state`, leaving `state` untouched: player one turns to `input`
+// first (if given and allowed), then everything moves as in a game at the default pace with
+// no power-ups running. Every random choice (food, power-ups) comes from `rng`, and the new
+// state's events are just the ones this move caused
+// Design Decision: A side-effect-free wrapper around update() rather than a second copy of the
+// rules, so tests can poke at collisions, growth and spawning on hand-built boards without a
+// Game, a terminal or the game's own RNG, and can't drift from what a real game does
+pub fn step(state: &GameState, input: Option<Direction>, rng: &mut impl Rng) -> GameState {
+    let mut next = state.clone();
+    next.rng = BoardRng::seed_from_u64(rng.gen());
+    next.events.clear();
+    if let Some(direction) = input {
+        next.snakes[0].steer(direction);
+    }
+    next.update(&Effects::default(), TICK_INTERVAL);
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+
+    // A state on a hand-drawn board, with nothing on it but the snake and the level's walls
+    fn board(rows: &str, seed: u64) -> GameState {
+        let level = Level::parse(rows).expect("test board should parse");
+        let mut state = GameState::new(level, 1, &GameConfig::default(), BoardRng::seed_from_u64(seed));
+        state.entities.retain(|entity| entity.role == Role::Obstacle);
+        state
+    }
+
+    // Every food on the board, as (x, y, kind)
+    fn food(state: &GameState) -> Vec<(u16, u16, FoodKind)> {
+        state.entities.iter().filter_map(|entity| Some((entity.pos.x, entity.pos.y, entity.food_kind()?))).collect()
+    }
+
+    const OPEN: &str = "#######\n#     #\n#  S  #\n#     #\n#######\n";
+
+    #[test]
+    fn running_into_a_wall_ends_the_round() {
+        let state = board("#######\n#     #\n#  S# #\n#     #\n#######\n", 1);
+        let next = step(&state, None, &mut StdRng::seed_from_u64(1));
+        assert!(!next.snake(0).alive);
+        assert!(next.is_over());
+        assert_eq!(next.events, vec![GameEvent::Collision { player: 0 }, GameEvent::GameOver]);
+        // The state stepped from is left as it was
+        assert!(state.snake(0).alive && !state.is_over());
+    }
+
+    #[test]
+    fn running_into_the_border_ends_the_round() {
+        let mut state = board(OPEN, 2);
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..2 {
+            state = step(&state, None, &mut rng);
+            assert!(state.snake(0).alive);
+        }
+        state = step(&state, None, &mut rng);
+        assert!(!state.snake(0).alive && state.is_over());
+    }
+
+    #[test]
+    fn turning_into_its_own_body_ends_the_round() {
+        let mut state = board("#########\n#       #\n#       #\n#       #\n#       #\n#       #\n#########\n", 3);
+        // Heading left, with the body curling round below the head
+        let body = [(3, 3), (4, 3), (4, 4), (3, 4), (2, 4)];
+        state.snakes[0].body = body.iter().map(|&(x, y)| Position { x, y }).collect();
+        state.snakes[0].direction = Direction::Left;
+        let next = step(&state, Some(Direction::Down), &mut StdRng::seed_from_u64(3));
+        assert!(!next.snake(0).alive && next.is_over());
+        assert!(next.events.contains(&GameEvent::Collision { player: 0 }));
+    }
+
+    #[test]
+    fn eating_food_grows_the_snake() {
+        let mut state = board(OPEN, 4);
+        assert!(state.place(Entity::food(Position { x: 4, y: 2 }, FoodKind::Normal)));
+        let next = step(&state, None, &mut StdRng::seed_from_u64(4));
+        let snake = next.snake(0);
+        assert!(snake.alive);
+        assert_eq!((snake.head().x, snake.head().y), (4, 2));
+        assert_eq!(snake.body.len(), 2);
+        assert_eq!(snake.eaten, 1);
+        assert!(snake.score > 0);
+        assert!(next.events.iter().any(|event| matches!(event, GameEvent::FoodEaten { player: 0, kind: FoodKind::Normal, .. })));
+        // The food eaten is replaced somewhere else
+        let food = food(&next);
+        assert_eq!(food.len(), 1);
+        assert_ne!((food[0].0, food[0].1), (4, 2));
+    }
+
+    #[test]
+    fn moving_without_food_keeps_the_length() {
+        let state = board(OPEN, 5);
+        let next = step(&state, None, &mut StdRng::seed_from_u64(5));
+        assert_eq!(next.snake(0).body.len(), 1);
+        assert_eq!(next.snake(0).eaten, 0);
+    }
+
+    #[test]
+    fn food_spawns_the_same_for_the_same_seed() {
+        let rows = "############\n#          #\n#          #\n#    S     #\n#          #\n#          #\n############\n";
+        let level = || Level::parse(rows).expect("test board should parse");
+        let start = |seed| GameState::new(level(), 1, &GameConfig::default(), BoardRng::seed_from_u64(seed));
+        assert_eq!(food(&start(6)), food(&start(6)));
+
+        // Eating from the same state with the same seed puts the next food in the same place
+        let mut state = board(rows, 6);
+        assert!(state.place(Entity::food(Position { x: 6, y: 3 }, FoodKind::Normal)));
+        let once = step(&state, None, &mut StdRng::seed_from_u64(42));
+        let again = step(&state, None, &mut StdRng::seed_from_u64(42));
+        assert_eq!(food(&once), food(&again));
+        assert_eq!(food(&once).len(), 1);
+    }
+
+    #[test]
+    fn food_skips_a_spot_taken_by_a_power_up() {
+        // The level's only food spot is under a power-up when the food is eaten
+        let mut state = board("#######\n#*    #\n#  S  #\n#     #\n#######\n", 7);
+        assert!(state.place(Entity::pickup(Position { x: 1, y: 1 }, PowerUp::Boost)));
+        assert!(state.place(Entity::food(Position { x: 4, y: 2 }, FoodKind::Normal)));
+        let next = step(&state, None, &mut StdRng::seed_from_u64(7));
+        let food = food(&next);
+        assert_eq!(food.len(), 1);
+        assert_ne!((food[0].0, food[0].1), (1, 1));
+    }
+
+    #[test]
+    fn a_state_round_trips_through_json() {
+        let mut state = board(OPEN, 8);
+        assert!(state.place(Entity::food(Position { x: 4, y: 2 }, FoodKind::Normal)));
+        let json = serde_json::to_string(&state).expect("state should serialize");
+        let mut restored: GameState = serde_json::from_str(&json).expect("state should deserialize");
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        // The RNG comes back where it was, so the restored state plays on exactly like the original
+        state.update(&Effects::default(), TICK_INTERVAL);
+        restored.update(&Effects::default(), TICK_INTERVAL);
+        assert_eq!(restored.snake(0).eaten, 1);
+        assert_eq!(serde_json::to_string(&restored).unwrap(), serde_json::to_string(&state).unwrap());
+    }
+}
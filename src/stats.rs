@@ -0,0 +1,101 @@
+// Lifetime statistics across every game played
+// Design Decision: Stats follow player one, the person at this keyboard, and are stored as
+// "key value" lines so adding a counter later doesn't break older files
+use crate::events::GameEvent;
+use crate::lang;
+use crate::paths;
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+
+// Running totals kept between runs
+#[derive(Clone, Default)]
+pub struct Stats {
+    games: u64,          // Games finished
+    food: u64,           // Food eaten across all games
+    play_time: Duration, // Game time across all games
+    longest: usize,      // Longest snake ever grown
+}
+
+impl Stats {
+    // Reads the stats file, starting from zero when there is none
+    pub fn load() -> Stats {
+        let mut stats = Stats::default();
+        for line in fs::read_to_string(stats_path()).unwrap_or_default().lines() {
+            let Some((key, value)) = line.split_once(' ') else { continue };
+            match key {
+                "games" => stats.games = value.parse().unwrap_or(0),
+                "food" => stats.food = value.parse().unwrap_or(0),
+                "millis" => stats.play_time = Duration::from_millis(value.parse().unwrap_or(0)),
+                "longest" => stats.longest = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+        stats
+    }
+
+    // Games recorded so far
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    // Food eaten across all recorded games
+    pub fn food(&self) -> u64 {
+        self.food
+    }
+
+    // Adds a finished game to the totals
+    pub fn record(&mut self, food: u32, play_time: Duration, longest: usize) {
+        self.games += 1;
+        self.food += u64::from(food);
+        self.play_time += play_time;
+        self.longest = self.longest.max(longest);
+    }
+
+    // Keeps the totals up to date with a game as it plays out, counting it once it's over
+    // Design Decision: Built from the game's events, so the totals never read the game itself.
+    // A practice run can end more than once (a crash can be rewound), so each ending replaces
+    // the last instead of adding to it
+    pub fn follow(stats: &Rc<RefCell<Stats>>) -> impl FnMut(&GameEvent) + 'static {
+        let (stats, before) = (Rc::clone(stats), stats.borrow().clone());
+        let (mut food, mut play_time, mut longest) = (0, Duration::ZERO, 0);
+        move |event| match *event {
+            GameEvent::Ticked { played, eaten, longest: length, .. } => (food, play_time, longest) = (eaten, played, length),
+            GameEvent::GameOver => {
+                let mut totals = before.clone();
+                totals.record(food, play_time, longest);
+                *stats.borrow_mut() = totals;
+            }
+            _ => {}
+        }
+    }
+
+    // Writes the totals back to disk
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = stats_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = format!(
+            "games {}\nfood {}\nmillis {}\nlongest {}\n",
+            self.games,
+            self.food,
+            self.play_time.as_millis(),
+            self.longest
+        );
+        fs::write(path, text)
+    }
+
+    // Formatted summary for `snekrs stats`
+    pub fn summary(&self) -> String {
+        let average = if self.games == 0 { 0.0 } else { self.food as f64 / self.games as f64 };
+        (lang::text().lifetime_stats)(self.games, self.food, self.play_time.as_secs(), self.longest, average)
+    }
+}
+
+// Where the stats are kept
+fn stats_path() -> PathBuf {
+    paths::data_dir().join("stats.txt")
+}
@@ -0,0 +1,86 @@
+// Per-tick JSON snapshots of a game for outside tools
+// Design Decision: One self-contained JSON object per line (the whole board, every snake, the
+// food and the score), written and flushed after every tick, so a visualizer or training
+// script can pick up the stream at any line without keeping state of its own. The handful of
+// fields is formatted by hand, like the config is parsed by hand, rather than pulling in a crate
+use crate::entity::Role;
+use crate::game::Direction;
+use crate::state::GameState;
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+
+// Where a game's snapshots go
+pub struct StateStream {
+    out: Box<dyn Write>,
+}
+
+impl StateStream {
+    // Streams to any writer, one line per tick
+    pub fn new(out: impl Write + 'static) -> Self {
+        StateStream { out: Box::new(LineWriter::new(out)) }
+    }
+
+    // Streams to a file, replacing what was in it; a named pipe works too, and opening one
+    // waits until something reads from it
+    pub fn create(path: &Path) -> io::Result<StateStream> {
+        Ok(StateStream::new(File::create(path)?))
+    }
+
+    // Writes the state as it stands after `tick` ticks
+    pub fn write(&mut self, tick: u64, state: &GameState) -> io::Result<()> {
+        writeln!(self.out, "{}", snapshot(tick, state))
+    }
+}
+
+// The state as one line of JSON:
+//   {"tick":12,"width":40,"height":20,"score":3,"over":false,
+//    "snakes":[{"alive":true,"direction":"right","score":3,"body":[[5,4],[4,4]]}],
+//    "food":[{"x":9,"y":2,"kind":"normal"}],"powerups":[{"x":1,"y":7,"power":"Magnet"}],
+//    "obstacles":[[3,3]]}
+pub fn snapshot(tick: u64, state: &GameState) -> String {
+    let snakes: Vec<String> = state
+        .snakes
+        .iter()
+        .map(|snake| {
+            let body: Vec<String> = snake.body.iter().map(|pos| format!("[{},{}]", pos.x, pos.y)).collect();
+            format!(
+                "{{\"alive\":{},\"direction\":\"{}\",\"score\":{},\"body\":[{}]}}",
+                snake.alive,
+                direction_name(snake.direction),
+                snake.score,
+                body.join(",")
+            )
+        })
+        .collect();
+    let (mut food, mut powerups, mut obstacles) = (Vec::new(), Vec::new(), Vec::new());
+    for entity in state.entities() {
+        let (x, y) = (entity.pos.x, entity.pos.y);
+        match entity.role {
+            Role::Food(kind) => food.push(format!("{{\"x\":{x},\"y\":{y},\"kind\":\"{}\"}}", kind.name())),
+            Role::PowerUp(power) => powerups.push(format!("{{\"x\":{x},\"y\":{y},\"power\":\"{}\"}}", power.name())),
+            Role::Obstacle => obstacles.push(format!("[{x},{y}]")),
+        }
+    }
+    format!(
+        "{{\"tick\":{tick},\"width\":{},\"height\":{},\"score\":{},\"over\":{},\"snakes\":[{}],\"food\":[{}],\"powerups\":[{}],\"obstacles\":[{}]}}",
+        state.width(),
+        state.height(),
+        state.score(),
+        state.is_over(),
+        snakes.join(","),
+        food.join(","),
+        powerups.join(","),
+        obstacles.join(",")
+    )
+}
+
+// Direction as it appears in a snapshot
+fn direction_name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Up => "up",
+        Direction::Down => "down",
+        Direction::Left => "left",
+        Direction::Right => "right",
+    }
+}
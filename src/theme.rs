@@ -0,0 +1,369 @@
+// Glyphs and colors for everything drawn on the board
+// Design Decision: The game state and the network protocol only deal in fixed logical glyphs;
+// a theme decides how each one looks on this terminal, so two players can pick different
+// themes for the same game
+use crate::entity::SPARKLE_GLYPH;
+use crate::game::PLAYER_GLYPHS;
+use crate::ghost::GHOST_GLYPH;
+use crate::level::PORTAL_GLYPH;
+use crate::render::Renderer;
+use crate::skin::Skin;
+use crate::state::ZONE_GLYPH;
+use crossterm::style::Color;
+
+// Built-in themes a config file or --theme can start from
+#[derive(Clone, Copy, PartialEq)]
+pub enum Scheme {
+    Classic,       // Green and cyan sneks on the terminal's own colors
+    Neon,          // Bright, saturated colors with filled walls
+    Solarized,     // Ethan Schoonover's Solarized accent palette
+    Mono,          // No color at all, with plain ASCII glyphs for any terminal
+    HighContrast,  // Bright bold glyphs on two-column cells with nothing blinking, for low vision
+}
+
+impl Scheme {
+    // Parses a CLI or config name (classic, neon, solarized, mono, high-contrast)
+    pub fn parse(name: &str) -> Option<Scheme> {
+        match name {
+            "classic" => Some(Scheme::Classic),
+            "neon" => Some(Scheme::Neon),
+            "solarized" => Some(Scheme::Solarized),
+            "mono" => Some(Scheme::Mono),
+            "high-contrast" => Some(Scheme::HighContrast),
+            _ => None,
+        }
+    }
+
+    // The full theme this scheme stands for
+    pub fn theme(self) -> Theme {
+        let classic = Theme::default();
+        match self {
+            Scheme::Classic => classic,
+            Scheme::Neon => Theme {
+                heads: [Color::Magenta, Color::Cyan],
+                snakes: [Color::DarkMagenta, Color::DarkCyan],
+                food: Some(Color::Green),
+                golden: Color::Yellow,
+                poison: Color::Red,
+                powerup: Color::Blue,
+                walls: Some(Color::Blue),
+                walls_bg: Some(Color::DarkBlue),
+                ghost: Color::Grey,
+                portal: Color::Yellow,
+                zone: Color::Red,
+                ..classic
+            },
+            Scheme::Solarized => Theme {
+                heads: [solarized(0xb58900), solarized(0x2aa198)],  // Yellow, cyan
+                snakes: [solarized(0x859900), solarized(0x268bd2)], // Green, blue
+                food: Some(solarized(0xdc322f)),                    // Red
+                golden: solarized(0xcb4b16),                        // Orange
+                poison: solarized(0xd33682),                        // Magenta
+                powerup: solarized(0x6c71c4),                       // Violet
+                walls: Some(solarized(0x93a1a1)),                   // Base1
+                walls_bg: Some(solarized(0x073642)),                // Base02
+                ghost: solarized(0x586e75),                         // Base01
+                portal: solarized(0x6c71c4),
+                zone: solarized(0xdc322f),
+                ..classic
+            },
+            Scheme::Mono => Theme {
+                glyphs: Glyphs { portal: '=', ghost: ':', zone: '.', sparkle: '\'', ..classic.glyphs },
+                color: false,
+                ..classic
+            },
+            // Design Decision: Bright colors only (never the dark variants), solid blocks for
+            // bodies and walls, and each player keeps a distinct glyph so colors aren't the only
+            // way to tell them apart
+            Scheme::HighContrast => Theme {
+                glyphs: Glyphs { wide: true, bodies: ['█', '▓'], wall: '█', ..classic.glyphs },
+                heads: [Color::White, Color::Yellow],
+                snakes: [Color::Green, Color::Cyan],
+                food: Some(Color::White),
+                golden: Color::Yellow,
+                poison: Color::Magenta,
+                powerup: Color::White,
+                walls: Some(Color::White),
+                ghost: Color::Grey,
+                portal: Color::Magenta,
+                zone: Color::Red,
+                bold: true,
+                steady: true,
+                ..classic
+            },
+        }
+    }
+}
+
+// Converts a 0xRRGGBB palette entry
+fn solarized(hex: u32) -> Color {
+    Color::Rgb { r: (hex >> 16) as u8, g: (hex >> 8) as u8, b: hex as u8 }
+}
+
+// What each logical glyph is drawn as
+// Design Decision: Power-up symbols aren't themed, since the HUD and README refer to them by
+// their glyph
+#[derive(Clone, Copy)]
+pub struct Glyphs {
+    pub wide: bool,  // Every board cell takes two terminal columns (for emoji)
+    pub heads: [char; 2],
+    pub bodies: [char; 2],
+    pub food: char,
+    pub golden: char,
+    pub poison: char,
+    pub wall: char,
+    pub portal: char,
+    pub ghost: char,
+    pub zone: char,
+    pub sparkle: char,  // Freshly spawned food, flashing
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Glyphs {
+            wide: false,
+            heads: [PLAYER_GLYPHS[0].0, PLAYER_GLYPHS[1].0],
+            bodies: [PLAYER_GLYPHS[0].1, PLAYER_GLYPHS[1].1],
+            food: '*',
+            golden: '$',
+            poison: '%',
+            wall: '#',
+            portal: PORTAL_GLYPH,
+            ghost: GHOST_GLYPH,
+            zone: ZONE_GLYPH,
+            sparkle: SPARKLE_GLYPH,
+        }
+    }
+}
+
+impl Glyphs {
+    // Emoji snakes and food with solid block walls, two columns per cell
+    // Design Decision: Emoji are double-width on the terminals that can show them, so the whole
+    // board is drawn two columns per cell rather than mixing widths within a row
+    pub fn emoji() -> Glyphs {
+        Glyphs {
+            wide: true,
+            heads: ['🟢', '🔵'],
+            bodies: ['🟩', '🟦'],
+            food: '🍎',
+            golden: '🌟',
+            poison: '🍄',
+            wall: '█',
+            portal: '🌀',
+            ghost: '👻',
+            zone: '░',
+            sparkle: '💫',
+        }
+    }
+}
+
+// Glyphs and colors for every drawable element
+#[derive(Clone)]
+pub struct Theme {
+    pub renderer: Renderer,
+    pub glyphs: Glyphs,
+    pub heads: [Color; 2],       // Head of player one and two
+    pub snakes: [Color; 2],      // Body of player one and two
+    pub food: Option<Color>,     // None = terminal default
+    pub golden: Color,           // Golden food
+    pub poison: Color,           // Poisoned food
+    pub powerup: Color,          // Power-up pickups
+    pub walls: Option<Color>,
+    pub walls_bg: Option<Color>, // Fill behind wall glyphs (None = terminal default)
+    pub ghost: Color,
+    pub portal: Color,
+    pub zone: Color,             // Battle royale's closing zone
+    pub color: bool,             // False = plain glyphs, for terminals without color
+    pub bold: bool,              // Board glyphs drawn bold (text renderer only)
+    pub steady: bool,            // Expiring items stay in view instead of blinking
+    pub skin: Skin,              // Pattern on player one's body
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            renderer: Renderer::Text,
+            glyphs: Glyphs::default(),
+            heads: [Color::Green, Color::Cyan],
+            snakes: [Color::DarkGreen, Color::DarkCyan],
+            food: None,
+            golden: Color::Yellow,
+            poison: Color::Magenta,
+            powerup: Color::Blue,
+            walls: None,
+            walls_bg: None,
+            ghost: Color::DarkGrey,
+            portal: Color::DarkMagenta,
+            zone: Color::DarkRed,
+            color: color_supported(),
+            bold: false,
+            steady: false,
+            skin: Skin::Classic,
+        }
+    }
+}
+
+impl Theme {
+    // Color for highlighted text outside the board (None when colors are off)
+    pub fn accent(&self) -> Option<Color> {
+        self.color.then_some(self.heads[0])
+    }
+
+    // What a logical board glyph is drawn as, for a snake segment of this skin tone
+    // Design Decision: A theme with body glyphs of its own (emoji, solid blocks) keeps them, so
+    // a skin's glyph set never mixes cell widths; its colors still apply
+    pub fn glyph(&self, glyph: char, tone: u8) -> char {
+        let glyphs = &self.glyphs;
+        match glyph {
+            '*' => glyphs.food,
+            '$' => glyphs.golden,
+            '%' => glyphs.poison,
+            '#' => glyphs.wall,
+            PORTAL_GLYPH => glyphs.portal,
+            GHOST_GLYPH => glyphs.ghost,
+            ZONE_GLYPH => glyphs.zone,
+            SPARKLE_GLYPH => glyphs.sparkle,
+            _ => match player_part(glyph) {
+                Some((0, false)) if glyphs.bodies[0] == PLAYER_GLYPHS[0].1 => self.skin.glyph(tone).unwrap_or(glyphs.bodies[0]),
+                Some((index, true)) => glyphs.heads[index],
+                Some((index, false)) => glyphs.bodies[index],
+                None => glyph,
+            },
+        }
+    }
+
+    // Terminal columns one board cell takes
+    pub fn cell_width(&self) -> u16 {
+        if self.glyphs.wide { 2 } else { 1 }
+    }
+
+    // Exactly what to print for a logical board glyph, filling the cell width
+    // Design Decision: On wide boards, emoji already take both columns, block shades are
+    // doubled so walls stay solid, and anything else is padded with a space
+    pub fn cell(&self, glyph: char, tone: u8) -> String {
+        let shown = self.glyph(glyph, tone);
+        match self.glyphs.wide {
+            false => shown.to_string(),
+            true if is_double_width(shown) => shown.to_string(),
+            true if ('\u{2580}'..='\u{259f}').contains(&shown) => format!("{shown}{shown}"),
+            true => format!("{shown} "),
+        }
+    }
+
+    // Foreground color of a logical board glyph, for a snake segment of this skin tone, if any
+    // (never when colors are off)
+    pub fn color(&self, glyph: char, tone: u8) -> Option<Color> {
+        if !self.color {
+            return None;
+        }
+        match glyph {
+            '*' => self.food,
+            '$' | SPARKLE_GLYPH => Some(self.golden),
+            '%' => Some(self.poison),
+            '>' | '<' | '-' | '!' | '2' | '3' | '&' => Some(self.powerup),
+            PORTAL_GLYPH => Some(self.portal),
+            ZONE_GLYPH => Some(self.zone),
+            '#' => self.walls,
+            GHOST_GLYPH => Some(self.ghost),
+            _ => match player_part(glyph)? {
+                (0, false) => Some(self.skin.color(tone).unwrap_or(self.snakes[0])),
+                (index, true) => Some(self.heads[index]),
+                (index, false) => Some(self.snakes[index]),
+            },
+        }
+    }
+
+    // Color of a board cell drawn as a solid pixel (None = empty floor)
+    // Design Decision: Elements that are normally left in the terminal's own color still need
+    // one as a pixel, so walls fall back to grey, food to red and the rest to white
+    pub fn pixel(&self, glyph: char, tone: u8) -> Option<Color> {
+        match glyph {
+            ' ' => None,
+            GHOST_GLYPH | ZONE_GLYPH if !self.color => None,
+            '#' => Some(self.walls.unwrap_or(Color::Grey)),
+            '*' => Some(self.food.unwrap_or(Color::Red)),
+            _ => Some(self.color(glyph, tone).unwrap_or(Color::White)),
+        }
+    }
+
+    // Background color of a logical board glyph, if any
+    pub fn background(&self, glyph: char) -> Option<Color> {
+        self.walls_bg.filter(|_| glyph == '#' && self.color)
+    }
+}
+
+// Which player a snake glyph belongs to, and whether it's their head
+fn player_part(glyph: char) -> Option<(usize, bool)> {
+    PLAYER_GLYPHS.iter().enumerate().find_map(|(index, &(head, body))| match glyph {
+        _ if glyph == head => Some((index, true)),
+        _ if glyph == body => Some((index, false)),
+        _ => None,
+    })
+}
+
+// Whether a glyph is an emoji that terminals draw two columns wide
+fn is_double_width(glyph: char) -> bool {
+    ('\u{1f300}'..='\u{1faff}').contains(&glyph)
+}
+
+// Whether the terminal should get color at all
+// Design Decision: Honors the NO_COLOR convention and TERM=dumb, so colors stay on by default
+// everywhere else without probing the terminal
+fn color_supported() -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let dumb = std::env::var("TERM").is_ok_and(|term| term == "dumb");
+    !no_color && !dumb
+}
+
+// A terminal color as RGB, with the named colors in xterm's default shades, for frontends
+// that draw pixels
+#[cfg(any(feature = "gif", feature = "window"))]
+pub fn rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Rgb { r, g, b } => [r, g, b],
+        Color::AnsiValue(value @ 16..=231) => {
+            let level = |step: u8| if step == 0 { 0 } else { 55 + step * 40 };
+            let cube = value - 16;
+            [level(cube / 36), level(cube / 6 % 6), level(cube % 6)]
+        }
+        Color::AnsiValue(value @ 232..=255) => [8 + (value - 232) * 10; 3],
+        Color::AnsiValue(value) => rgb(ANSI_COLORS[usize::from(value)]),
+        Color::Black => [0, 0, 0],
+        Color::DarkRed => [128, 0, 0],
+        Color::DarkGreen => [0, 128, 0],
+        Color::DarkYellow => [128, 128, 0],
+        Color::DarkBlue => [0, 0, 128],
+        Color::DarkMagenta => [128, 0, 128],
+        Color::DarkCyan => [0, 128, 128],
+        Color::Grey => [192, 192, 192],
+        Color::DarkGrey => [128, 128, 128],
+        Color::Red => [255, 0, 0],
+        Color::Green => [0, 255, 0],
+        Color::Yellow => [255, 255, 0],
+        Color::Blue => [0, 0, 255],
+        Color::Magenta => [255, 0, 255],
+        Color::Cyan => [0, 255, 255],
+        Color::White | Color::Reset => [255, 255, 255],
+    }
+}
+
+// The sixteen colors the first ANSI values stand for
+#[cfg(any(feature = "gif", feature = "window"))]
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::DarkRed,
+    Color::DarkGreen,
+    Color::DarkYellow,
+    Color::DarkBlue,
+    Color::DarkMagenta,
+    Color::DarkCyan,
+    Color::Grey,
+    Color::DarkGrey,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
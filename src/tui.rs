@@ -0,0 +1,114 @@
+// Drawing the game with ratatui widgets (built with `--features tui`)
+// Design Decision: Widgets are rendered into an off-screen buffer that's diffed against the one
+// on screen, rather than through ratatui's Terminal, which would take over the cursor and the
+// alternate screen that play::enter_game_screen already manages. Only changed characters are
+// written, so the widget frontend is as cheap over SSH as the hand-placed one
+use crate::frame::Frame;
+use crate::game::Position;
+use crate::theme::Theme;
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListState, Paragraph, StatefulWidget, Widget};
+use std::io::stdout;
+
+// Part of the terminal drawn with widgets, as it was last written
+pub(crate) struct Screen {
+    shown: Buffer,
+}
+
+impl Screen {
+    // An area of the terminal whose contents are unknown, so the first draw writes all of it
+    pub(crate) fn new(area: Rect) -> Self {
+        let mut shown = Buffer::empty(area);
+        for cell in &mut shown.content {
+            cell.set_symbol("\0");
+        }
+        Screen { shown }
+    }
+
+    // Renders widgets into a fresh buffer and writes whatever differs from the screen
+    pub(crate) fn draw(&mut self, paint: impl FnOnce(&mut Buffer)) -> std::io::Result<()> {
+        let mut next = Buffer::empty(self.shown.area);
+        paint(&mut next);
+        let mut backend = CrosstermBackend::new(stdout());
+        backend.draw(self.shown.diff(&next).into_iter())?;
+        Backend::flush(&mut backend)?;
+        self.shown = next;
+        Ok(())
+    }
+}
+
+// The board, drawn by the theme's renderer one terminal character at a time
+pub(crate) struct Board<'a> {
+    pub frame: &'a Frame,
+    pub theme: &'a Theme,
+}
+
+impl Widget for Board<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (width, height) = (self.frame.width(), self.frame.height());
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| Position { x, y }));
+        for painted in self.theme.renderer.paint(self.frame, cells, self.theme) {
+            if painted.column >= area.width || painted.row >= area.height {
+                continue;
+            }
+            let Some(cell) = buf.cell_mut((area.x + painted.column, area.y + painted.row)) else { continue };
+            let mut style = Style::default();
+            if let Some(color) = painted.foreground {
+                style = style.fg(Color::from(color));
+            }
+            if let Some(color) = painted.background {
+                style = style.bg(Color::from(color));
+            }
+            if painted.dim {
+                style = style.add_modifier(Modifier::DIM);
+            }
+            if painted.bold {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            cell.set_symbol(&painted.shown).set_style(style);
+        }
+    }
+}
+
+// Label drawn over the board: score popups in bold gold, the pause overlay reversed
+pub(crate) fn label(buf: &mut Buffer, area: Rect, x: u16, y: u16, text: &str, style: Style) {
+    if x < area.width && y < area.height {
+        buf.set_stringn(area.x + x, area.y + y, text, usize::from(area.width - x), style);
+    }
+}
+
+// The two lines under the board: score and stage, then the controls reminder or a fresh
+// achievement in gold
+pub(crate) struct ScorePanel<'a> {
+    pub status: &'a str,
+    pub hint: &'a str,
+    pub golden: Option<Color>,  // Color of the hint line when it's an achievement
+}
+
+impl Widget for ScorePanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let hint = match self.golden {
+            Some(color) => Span::styled(self.hint, Style::default().fg(color)),
+            None => Span::raw(self.hint),
+        };
+        Paragraph::new(vec![Line::raw(self.status), Line::from(hint)]).render(area, buf);
+    }
+}
+
+// A menu's entries as a list with the selected one marked and reversed
+pub(crate) fn draw_menu(items: &[&str], selected: usize, x: u16, y: u16) -> std::io::Result<()> {
+    let (columns, rows) = crossterm::terminal::size().unwrap_or((u16::MAX, u16::MAX));
+    let width = items.iter().map(|item| item.chars().count() as u16 + 2).max().unwrap_or(0);
+    let area = Rect::new(x, y, width, items.len() as u16).intersection(Rect::new(0, 0, columns, rows));
+    let lines = items.iter().enumerate().map(|(index, item)| match index == selected {
+        true => Line::from(Span::styled(*item, Style::default().add_modifier(Modifier::REVERSED))),
+        false => Line::raw(*item),
+    });
+    let list = List::new(lines).highlight_symbol("> ");
+    let mut state = ListState::default().with_selected(Some(selected));
+    Screen::new(area).draw(|buf| StatefulWidget::render(list, area, buf, &mut state))
+}
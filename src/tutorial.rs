@@ -0,0 +1,221 @@
+// Guided first-run tutorial: a few small scripted boards, each with one thing to learn
+// Design Decision: Every lesson is an ordinary Game on a hand-drawn level with a fixed seed,
+// so the tutorial plays exactly like the real thing; the prompt takes the controls line and
+// the lesson checks its goal between ticks. The forgiving lessons run in zen mode so only the
+// one about walls can end in a crash, and poison is left out of all of them
+use crate::config::GameConfig;
+use crate::config_file::Config;
+use crate::entity::Entity;
+use crate::events::GameEvent;
+use crate::game::{Board, Game, Position};
+use crate::level::Level;
+use crate::mode::Mode;
+use crate::mods::{GameMod, ModKind};
+use crate::play::Play;
+use crate::powerup::PowerUp;
+use crate::state::GameState;
+use crate::stats::Stats;
+use crate::{input, lang, menu, paths};
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::Print,
+    terminal::{Clear, ClearType},
+};
+use std::cell::Cell;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+
+// Seed every lesson is played with, so it's the same board and food every time
+const SEED: u64 = 1;
+
+// How long a message between lessons stays up
+const BREAK: Duration = Duration::from_millis(1500);
+
+// Turns to make in the movement lesson
+const TURNS: u32 = 4;
+
+// Food to eat in the food lesson
+const MEALS: u32 = 3;
+
+// Seconds to stay alive in the walls lesson
+const SURVIVE_SECONDS: u64 = 15;
+
+const OPEN_BOARD: &str = "\
+##############################
+#                            #
+#                            #
+#                            #
+#                            #
+#                            #
+#             S              #
+#                            #
+#                            #
+#                            #
+#                            #
+##############################";
+
+const FOOD_BOARD: &str = "\
+##############################
+#                            #
+#      *                     #
+#                      *     #
+#                            #
+#                            #
+#             S              #
+#                            #
+#    *                       #
+#                    *       #
+#                            #
+##############################";
+
+const WALL_BOARD: &str = "\
+##############################
+#                            #
+#    ######        ######    #
+#                            #
+#         #        #         #
+#         #        #         #
+#         #   S    #         #
+#         #        #         #
+#                            #
+#    ######        ######    #
+#                            #
+##############################";
+
+// Power-ups the power-up lesson keeps on the board, and where
+const SUPPLIES: [(u16, u16, PowerUp); 3] = [(22, 3, PowerUp::Boost), (6, 8, PowerUp::Magnet), (20, 9, PowerUp::Phase)];
+
+// What a lesson asks of the player
+#[derive(Clone, Copy)]
+enum Goal {
+    Turns,     // Steer the snake around
+    Eat,       // Eat a few pieces of food
+    Survive,   // Stay alive among walls for a while
+    PowerUp,   // Collect a power-up
+}
+
+// How a lesson attempt ended
+enum Outcome {
+    Passed,
+    Crashed,
+    Quit,
+}
+
+// Whether the tutorial has been shown before, so it only starts on its own once; anyone with
+// games on record already knows the ropes, so it doesn't start for them either
+pub(crate) fn seen() -> bool {
+    marker_path().exists() || Stats::load().games() > 0
+}
+
+// Plays every lesson in order on the game screen (which the caller has entered), retrying
+// a lesson after a crash; quitting leaves the tutorial early. Either way it won't start on
+// its own again
+pub(crate) fn run(config: &Config) -> std::io::Result<()> {
+    let _ = fs::create_dir_all(paths::data_dir()).and_then(|_| fs::write(marker_path(), ""));
+    let text = lang::text();
+    for goal in [Goal::Turns, Goal::Eat, Goal::Survive, Goal::PowerUp] {
+        loop {
+            match play(goal, config)? {
+                Outcome::Passed => break,
+                Outcome::Crashed => show(text.tutorial_crashed)?,
+                Outcome::Quit => return Ok(()),
+            }
+        }
+        show(text.tutorial_passed)?;
+    }
+    show(text.tutorial_done)?;
+    while !menu::is_dismissal(&input::read()?) {}
+    Ok(())
+}
+
+// Plays one attempt at a lesson
+fn play(goal: Goal, config: &Config) -> std::io::Result<Outcome> {
+    let text = lang::text();
+    let (board, mode, prompt) = match goal {
+        Goal::Turns => (OPEN_BOARD, Mode::Zen, (text.tutorial_move)(&config.keys.describe(0))),
+        Goal::Eat => (FOOD_BOARD, Mode::Zen, text.tutorial_food.to_string()),
+        Goal::Survive => (WALL_BOARD, Mode::Standard, (text.tutorial_walls)(SURVIVE_SECONDS)),
+        Goal::PowerUp => (OPEN_BOARD, Mode::Zen, text.tutorial_power.to_string()),
+    };
+    let level = Level::parse(board)?;
+    let mut game = Play::new(Game::new(Board::Custom(level), 1, SEED, GameConfig { mode, mods: vec![ModKind::NoPoison], ..GameConfig::default() }));
+    game.set_preferences(config.keys.clone(), config.theme.clone());
+    if let Goal::PowerUp = goal {
+        game.add_mod(Box::new(Supply));
+    }
+
+    // Turns and power-ups are only seen as events
+    let (turns, powered) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(false)));
+    let (turned, collected) = (Rc::clone(&turns), Rc::clone(&powered));
+    game.subscribe(move |event| match event {
+        GameEvent::DirectionChanged { player: 0, .. } => turned.set(turned.get() + 1),
+        GameEvent::PowerUpCollected { player: 0, .. } => collected.set(true),
+        _ => {}
+    });
+
+    let mut shown = String::new();
+    game.start_countdown();
+    game.draw()?;
+    loop {
+        let (progress, passed) = match goal {
+            Goal::Turns => (format!(" ({}/{TURNS})", turns.get().min(TURNS)), turns.get() >= TURNS),
+            Goal::Eat => {
+                let eaten = game.state().snake(0).eaten;
+                (format!(" ({}/{MEALS})", eaten.min(MEALS)), eaten >= MEALS)
+            }
+            Goal::Survive => {
+                let left = Duration::from_secs(SURVIVE_SECONDS).saturating_sub(game.play_time());
+                (format!(" ({}s)", left.as_millis().div_ceil(1000)), left.is_zero())
+            }
+            Goal::PowerUp => (String::new(), powered.get()),
+        };
+        if passed {
+            return Ok(Outcome::Passed);
+        }
+        if game.is_over() {
+            return Ok(if game.was_quit() { Outcome::Quit } else { Outcome::Crashed });
+        }
+        let hint = format!("{prompt}{progress}");
+        let changed = hint != shown;
+        if changed {
+            game.set_hint(Some(hint.clone()));
+            shown = hint;
+        }
+        game.poll_input(Duration::from_millis(50))?;
+        if game.tick_if_due()? || game.animation_due() || changed {
+            game.draw()?;
+        }
+    }
+}
+
+// Shows a message on its own for a moment, dropping keys pressed meanwhile
+fn show(message: &str) -> std::io::Result<()> {
+    execute!(std::io::stdout(), Clear(ClearType::All), MoveTo(2, 2), Print(message))?;
+    std::thread::sleep(BREAK);
+    input::discard()
+}
+
+// Where the tutorial leaves a mark once it has been shown
+fn marker_path() -> PathBuf {
+    paths::data_dir().join("tutorial-seen")
+}
+
+// Keeps a power-up on the board for the power-up lesson, at the first free one of a few spots
+#[derive(Clone)]
+struct Supply;
+
+impl GameMod for Supply {
+    fn on_tick(&mut self, state: &mut GameState) {
+        if state.entities().iter().any(|entity| entity.power().is_some()) {
+            return;
+        }
+        for (x, y, power) in SUPPLIES {
+            if state.place(Entity::pickup(Position { x, y }, power)) {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,422 @@
+// Networked two-player mode over UDP, for links too slow to play on over TCP
+// Design Decision: Only inputs cross the network. Both ends run the same deterministic game,
+// rebuilt on the client from the replay header the host sends first, and the host's copy
+// stays the authority. The client runs about one round trip ahead of the host and applies
+// its own turns straight away; whenever the host confirms a tick, the client rolls back to
+// the last tick it had confirmed, replays the confirmed inputs and puts its unconfirmed turns
+// back on top. Nothing is retransmitted as such: every client datagram repeats the turns
+// the host hasn't acknowledged yet, and every host datagram repeats the inputs since the
+// client's last confirmed tick, so a lost one is made up for by the next
+//
+// Protocol: one text message per datagram
+//   client -> host: HELLO | INPUTS <confirmed tick> <stamp> <seq>:<tick>:<U|D|L|R>,... | QUIT
+//   host -> client: START <replay text> | OVER <text> |
+//                   TICK <tick> <acked seq> <stamp> <tick>:<player>:<U|D|L|R>|<tick>:pace:<x>,...
+use crate::chat;
+use crate::config;
+use crate::config_file::Config;
+use crate::game::{Direction, Game};
+use crate::input;
+use crate::keymap::Action;
+use crate::lang;
+use crate::lobby::Beacon;
+use crate::net::DEFAULT_PORT;
+use crate::play::{self, Play};
+use crate::replay::{Change, Input, Replay};
+use crossterm::event::Event;
+use std::collections::VecDeque;
+use std::io::{Error, ErrorKind};
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+// Largest message that fits in one datagram
+const MAX_DATAGRAM: usize = 65_507;
+
+// How often the handshake's HELLO and START are sent again until they're answered
+const HANDSHAKE_INTERVAL: Duration = Duration::from_millis(250);
+
+// Silence from the other end for this long means it's gone
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// Ticks the client may run past its target lead before it waits for the host to catch up
+const SLACK: u64 = 2;
+
+// Times the final OVER is sent, since any one datagram may be lost
+const OVER_REPEATS: usize = 5;
+
+// A turn made on the client, kept until the host acknowledges it
+#[derive(Clone, Copy)]
+struct Move {
+    seq: u64,    // Numbered from 1 in the order they were made
+    tick: u64,   // Tick the client made it on, and the host applies it on if it arrives in time
+    direction: Direction,
+}
+
+// Messages exchanged between host and client
+enum Message {
+    Hello,
+    Start(String),  // The game to play, as replay text with no inputs yet
+    Inputs { confirmed: u64, stamp: u64, moves: Vec<Move> },
+    Tick { tick: u64, acked: u64, stamp: u64, inputs: Vec<Input> },
+    Over(String),
+    Quit,
+}
+
+impl Message {
+    // Serializes the message as the text of one datagram
+    fn encode(&self) -> String {
+        match self {
+            Message::Hello => "HELLO".to_string(),
+            Message::Start(replay) => format!("START {replay}"),
+            Message::Inputs { confirmed, stamp, moves } => {
+                let moves: Vec<String> = moves.iter().map(|m| format!("{}:{}:{}", m.seq, m.tick, m.direction.code())).collect();
+                format!("INPUTS {confirmed} {stamp} {}", moves.join(","))
+            }
+            Message::Tick { tick, acked, stamp, inputs } => {
+                let inputs: Vec<String> = inputs
+                    .iter()
+                    .map(|input| match input.change {
+                        Change::Steer(player, direction) => format!("{}:{player}:{}", input.tick, direction.code()),
+                        Change::Pace(pace) => format!("{}:pace:{pace}", input.tick),
+                    })
+                    .collect();
+                format!("TICK {tick} {acked} {stamp} {}", inputs.join(","))
+            }
+            Message::Over(text) => format!("OVER {text}"),
+            Message::Quit => "QUIT".to_string(),
+        }
+    }
+
+    // Parses a datagram's text, returning None for anything malformed
+    fn parse(text: &str) -> Option<Message> {
+        let (kind, rest) = text.split_once(' ').unwrap_or((text, ""));
+        match kind {
+            "HELLO" => Some(Message::Hello),
+            "START" => Some(Message::Start(rest.to_string())),
+            "INPUTS" => {
+                let mut fields = rest.splitn(3, ' ');
+                let confirmed = fields.next()?.parse().ok()?;
+                let stamp = fields.next()?.parse().ok()?;
+                let moves = entries(fields.next().unwrap_or(""))
+                    .map(|[seq, tick, code]| {
+                        Some(Move { seq: seq.parse().ok()?, tick: tick.parse().ok()?, direction: Direction::from_code(code)? })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Message::Inputs { confirmed, stamp, moves })
+            }
+            "TICK" => {
+                let mut fields = rest.splitn(4, ' ');
+                let tick = fields.next()?.parse().ok()?;
+                let acked = fields.next()?.parse().ok()?;
+                let stamp = fields.next()?.parse().ok()?;
+                let inputs = entries(fields.next().unwrap_or(""))
+                    .map(|[at, player, value]| {
+                        let change = match player {
+                            "pace" => Change::Pace(value.parse().ok()?),
+                            _ => Change::Steer(player.parse().ok()?, Direction::from_code(value)?),
+                        };
+                        Some(Input { tick: at.parse().ok()?, change })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Message::Tick { tick, acked, stamp, inputs })
+            }
+            "OVER" => Some(Message::Over(rest.to_string())),
+            "QUIT" => Some(Message::Quit),
+            _ => None,
+        }
+    }
+}
+
+// Splits a comma-separated list of three-part colon-separated entries; a malformed entry
+// comes out as empty fields, which then fail to parse
+fn entries(list: &str) -> impl Iterator<Item = [&str; 3]> {
+    list.split(',').filter(|entry| !entry.is_empty()).map(|entry| {
+        let mut parts = entry.splitn(3, ':');
+        [parts.next().unwrap_or(""), parts.next().unwrap_or(""), parts.next().unwrap_or("")]
+    })
+}
+
+// Sends one message; a lost or refused datagram is made up for by the next, so failures are
+// only noticed as the other end going quiet
+fn send(socket: &UdpSocket, message: &Message) {
+    let _ = socket.send(message.encode().as_bytes());
+}
+
+// Takes the next datagram from the peer, if one is waiting (or arrives before the socket's
+// read timeout)
+fn receive(socket: &UdpSocket, buffer: &mut [u8]) -> Option<Message> {
+    let size = socket.recv(buffer).ok()?;
+    Message::parse(std::str::from_utf8(&buffer[..size]).ok()?)
+}
+
+// What the host tells the client after a loop: the tick it's on, the newest of the client's
+// turns it has played, and every input from the client's last confirmed tick up to this one
+// Design Decision: Inputs made on the current tick are left out, since more can still come
+// before it runs; the client only ever rolls forward to ticks whose inputs are settled
+fn tick_message(game: &Game, acked: u64, stamp: u64, confirmed: u64) -> Message {
+    let inputs = game.inputs();
+    let from = inputs.partition_point(|input| input.tick < confirmed);
+    let to = inputs.partition_point(|input| input.tick < game.ticks());
+    Message::Tick { tick: game.ticks(), acked, stamp, inputs: inputs[from..to.max(from)].to_vec() }
+}
+
+// Hosts a game over UDP and waits for one player to join as player two
+// Design Decision: The client's turns are played on the tick it made them on whenever they
+// arrive in time, so its prediction holds; a late one is played straight away, and the
+// client corrects itself from the next confirmation
+pub(crate) fn host(game: &mut Play, port: u16) -> std::io::Result<()> {
+    let start = Message::Start(game.replay().to_text());
+    if start.encode().len() > MAX_DATAGRAM {
+        return Err(Error::new(ErrorKind::InvalidInput, "this board is too big to send over UDP; host it without --udp"));
+    }
+    let socket = UdpSocket::bind(("0.0.0.0", port))?;
+    let beacon = Beacon::start(port, true, game);
+    println!("{}", (lang::text().hosting_udp)(port));
+    let mut buffer = vec![0; MAX_DATAGRAM];
+    let peer = loop {
+        let (size, address) = socket.recv_from(&mut buffer)?;
+        if matches!(std::str::from_utf8(&buffer[..size]).ok().and_then(Message::parse), Some(Message::Hello)) {
+            break address;
+        }
+    };
+    println!("{}", (lang::text().joined)(&peer.to_string()));
+    // Nobody else can join a UDP game, so it stops being listed
+    drop(beacon);
+    socket.connect(peer)?;
+
+    // The game is handed over until the client's first INPUTS shows it arrived
+    socket.set_read_timeout(Some(HANDSHAKE_INTERVAL))?;
+    let asked = Instant::now();
+    let mut stamp = loop {
+        send(&socket, &start);
+        match receive(&socket, &mut buffer) {
+            Some(Message::Inputs { stamp, .. }) => break stamp,
+            Some(Message::Quit) => return Ok(()),
+            _ if asked.elapsed() > TIMEOUT => return Err(Error::new(ErrorKind::TimedOut, "the player who joined never started")),
+            _ => {}
+        }
+    };
+    socket.set_nonblocking(true)?;
+    game.set_local_players(1);
+
+    let game_screen = play::enter_game_screen()?;
+    game.start_countdown();
+    game.draw()?;
+
+    let mut waiting: VecDeque<Move> = VecDeque::new();  // Client turns made for ticks not reached yet
+    let mut received = 0;   // Newest of the client's turns seen
+    let mut applied = 0;    // Newest of the client's turns played
+    let mut acked = 0;      // Newest of the client's turns played before the last tick ran
+    let mut confirmed = 0;  // Newest tick the client has confirmed
+    let mut heard = Instant::now();
+    let mut forfeited = false;
+    while !game.is_over() {
+        game.poll_input(Duration::from_millis(20))?;
+
+        while let Some(message) = receive(&socket, &mut buffer) {
+            heard = Instant::now();
+            match message {
+                Message::Inputs { confirmed: tick, stamp: sent, moves } => {
+                    confirmed = confirmed.max(tick);
+                    stamp = stamp.max(sent);
+                    let fresh: Vec<Move> = moves.into_iter().filter(|m| m.seq > received).collect();
+                    received = fresh.last().map_or(received, |m| m.seq);
+                    waiting.extend(fresh);
+                }
+                Message::Quit => game.forfeit(1),
+                _ => {}
+            }
+        }
+        if heard.elapsed() > TIMEOUT {
+            forfeited = true;
+            game.forfeit(1);
+            break;
+        }
+
+        while let Some(m) = waiting.front().copied().filter(|m| m.tick <= game.ticks()) {
+            game.steer(1, m.direction);
+            applied = m.seq;
+            waiting.pop_front();
+        }
+        let before = game.ticks();
+        if game.tick_if_due()? {
+            game.draw()?;
+        }
+        if game.ticks() > before {
+            acked = applied;
+        }
+        send(&socket, &tick_message(game, acked, stamp, confirmed));
+    }
+
+    let message = if forfeited {
+        lang::text().opponent_disconnected.to_string()
+    } else {
+        game.final_message()
+    };
+    for _ in 0..OVER_REPEATS {
+        send(&socket, &Message::Over(message.clone()));
+    }
+    game_screen.leave()?;
+    println!("\n{message}");
+    Ok(())
+}
+
+// Accepts the game a host sends only if it's one a UDP host can have started: two snakes,
+// player two left to the client, on a board within the size limits
+// Design Decision: The client builds its whole game from this, so a host that sends anything
+// else is turned away before it can get the simulation into a state it can't run
+fn check_start(replay: Replay) -> std::io::Result<Replay> {
+    let invalid = |message: &str| Error::new(ErrorKind::InvalidData, format!("the host sent an unplayable game: {message}"));
+    if replay.players != 2 || !replay.bots.is_empty() {
+        return Err(invalid("a UDP game has exactly two players and no bots"));
+    }
+    config::board_size(replay.settings.width.into()).map_err(|err| invalid(&err))?;
+    config::board_size(replay.settings.height.into()).map_err(|err| invalid(&err))?;
+    Ok(replay)
+}
+
+// Joins a game hosted over UDP and plays as player two with player one's keys from the config
+pub(crate) fn join(address: &str, config: &Config) -> std::io::Result<()> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+    if address.contains(':') {
+        socket.connect(address)?;
+    } else {
+        socket.connect((address, DEFAULT_PORT))?;
+    }
+
+    // Asks for the game until the host answers, timing the first round trip on the way
+    println!("{}", (lang::text().waiting_at)(address));
+    socket.set_read_timeout(Some(HANDSHAKE_INTERVAL))?;
+    let clock = Instant::now();  // Stamps are milliseconds on this clock
+    let mut buffer = vec![0; MAX_DATAGRAM];
+    let (replay, mut rtt) = loop {
+        let asked = Instant::now();
+        send(&socket, &Message::Hello);
+        if let Some(Message::Start(text)) = receive(&socket, &mut buffer) {
+            break (check_start(Replay::parse(&text)?)?, asked.elapsed());
+        }
+        if clock.elapsed() > TIMEOUT {
+            return Err(Error::new(ErrorKind::TimedOut, format!("no answer from {address}")));
+        }
+    };
+    socket.set_nonblocking(true)?;
+
+    // Steering goes through this loop rather than the game's own keys, so it can be numbered
+    let mut game = Play::new(Game::resume(replay));
+    game.set_preferences(config.keys.clone(), config.theme.clone());
+    game.set_local_players(0);
+    let game_screen = play::enter_game_screen()?;
+    game.start_countdown();
+
+    let mut checkpoint = game.checkpoint();  // The game as of the newest confirmed tick
+    let mut latest = 0;   // Newest confirmed tick
+    let mut acked = 0;    // Newest of this client's turns the host has played
+    let mut pending: Vec<Move> = Vec::new();  // Turns made here that the host hasn't acknowledged
+    let mut seq = 0;
+    let mut heard = Instant::now();
+    let mut changed = true;  // Whether the board needs drawing
+    let message = loop {
+        let direction = match input::next(Duration::from_millis(10)) {
+            Ok(Some(Event::Key(key_event))) => match config.keys.action(key_event.code, 1) {
+                Some(Action::Quit) => {
+                    send(&socket, &Message::Quit);
+                    break lang::text().left_game.to_string();
+                }
+                // Whoever joins steers with the keys they'd use as player one at home
+                Some(Action::Steer(0, direction)) => Some(direction),
+                Some(Action::Turn(0, turn)) => Some(game.state().snake(1).heading().turned(turn)),
+                _ => None,
+            },
+            Ok(Some(Event::Resize(..))) => {
+                changed = true;
+                None
+            }
+            _ => None,
+        };
+        if let Some(direction) = direction {
+            seq += 1;
+            pending.push(Move { seq, tick: game.ticks(), direction });
+            game.steer(1, direction);
+        }
+
+        let mut outcome = None;
+        let mut confirmation = None;
+        while let Some(message) = receive(&socket, &mut buffer) {
+            heard = Instant::now();
+            match message {
+                Message::Tick { tick, acked: played, stamp, inputs } => {
+                    let sample = clock.elapsed().saturating_sub(Duration::from_millis(stamp));
+                    rtt = (rtt * 7 + sample) / 8;
+                    acked = acked.max(played);
+                    if tick > latest && confirmation.as_ref().is_none_or(|(newest, _)| tick > *newest) {
+                        confirmation = Some((tick, inputs));
+                    }
+                }
+                Message::Over(text) => outcome = Some(chat::clean(&text)),
+                _ => {}
+            }
+        }
+        if let Some(text) = outcome {
+            break text;
+        }
+        if heard.elapsed() > TIMEOUT {
+            break lang::text().host_lost.to_string();
+        }
+
+        // Reconciles: back to the last confirmed tick, forward through the confirmed inputs to
+        // the new one, then forward again to where this client was with its own turns replayed
+        if let Some((tick, inputs)) = confirmation {
+            let local = game.ticks().max(tick);
+            game.rollback(checkpoint);
+            let mut inputs = inputs.into_iter().filter(|input| input.tick >= latest).peekable();
+            while game.ticks() < tick {
+                while let Some(input) = inputs.next_if(|input| input.tick <= game.ticks()) {
+                    game.apply(input.change);
+                }
+                game.tick_quietly();
+            }
+            (checkpoint, latest) = (game.checkpoint(), tick);
+
+            pending.retain(|m| m.seq > acked);
+            let mut moves = pending.iter().peekable();
+            while game.ticks() < local {
+                while let Some(m) = moves.next_if(|m| m.tick <= game.ticks()) {
+                    game.steer(1, m.direction);
+                }
+                game.tick_quietly();
+            }
+            for m in moves {
+                game.steer(1, m.direction);
+            }
+            changed = true;
+        }
+
+        // Stays about a round trip ahead of the host, so turns made here reach it in time for
+        // the tick they were made on
+        let lead = (rtt.as_secs_f64() / game.tick_interval().as_secs_f64()).ceil() as u64 + 1;
+        if latest > 0 {
+            while game.ticks() < latest + lead && !game.is_over() {
+                game.tick_quietly();
+                changed = true;
+            }
+        }
+        if game.ticks() < latest + lead + SLACK && game.tick_if_due()? {
+            changed = true;
+        }
+
+        send(&socket, &Message::Inputs { confirmed: latest, stamp: clock.elapsed().as_millis() as u64, moves: pending.clone() });
+        if changed {
+            game.set_hint(Some((lang::text().udp_client_hint)(
+                rtt.as_millis() as u64,
+                &config.keys.describe(0),
+                &config.keys.key_label(Action::Quit),
+            )));
+            game.draw()?;
+            changed = false;
+        }
+    };
+
+    game_screen.leave()?;
+    println!("\n{message}");
+    Ok(())
+}
@@ -0,0 +1,186 @@
+// The game in a window of its own, in builds with `--features window` (`snekrs window`)
+// Design Decision: The window draws the same Frame as the terminal renderers and the GIF
+// export, every cell a filled square in the color Theme::pixel gives it, and reads keys through
+// the configured Keymap, so only the pixels differ from a game in the terminal. minifb loads
+// the X11 or Wayland libraries when the window opens rather than linking them, so the feature
+// builds without any system development packages
+use crate::cli::Cli;
+use crate::config::GameConfig;
+use crate::config_file::Config;
+use crate::frame::Frame;
+use crate::game::{Board, Game};
+use crate::keymap::Action;
+use crate::lang;
+use crate::play::Play;
+use crate::theme::{self, Theme};
+use crossterm::event::KeyCode;
+use minifb::{Key, KeyRepeat, Window, WindowOptions};
+use std::io;
+
+// Longest side of the board, in pixels; cells shrink to fit bigger boards
+const MAX_BOARD_PIXELS: usize = 960;
+
+// Size of a cell, in pixels, however small or large the board
+const MIN_CELL: usize = 3;
+const MAX_CELL: usize = 24;
+
+// Empty floor
+const BACKGROUND: u32 = 0x10_10_10;
+
+// How often the window is redrawn and checked for keys
+const FPS: usize = 60;
+
+// Letter and digit keys, which bindings name by the character they type
+const CHARACTER_KEYS: [(Key, char); 36] = [
+    (Key::A, 'a'), (Key::B, 'b'), (Key::C, 'c'), (Key::D, 'd'), (Key::E, 'e'), (Key::F, 'f'),
+    (Key::G, 'g'), (Key::H, 'h'), (Key::I, 'i'), (Key::J, 'j'), (Key::K, 'k'), (Key::L, 'l'),
+    (Key::M, 'm'), (Key::N, 'n'), (Key::O, 'o'), (Key::P, 'p'), (Key::Q, 'q'), (Key::R, 'r'),
+    (Key::S, 's'), (Key::T, 't'), (Key::U, 'u'), (Key::V, 'v'), (Key::W, 'w'), (Key::X, 'x'),
+    (Key::Y, 'y'), (Key::Z, 'z'), (Key::Key0, '0'), (Key::Key1, '1'), (Key::Key2, '2'),
+    (Key::Key3, '3'), (Key::Key4, '4'), (Key::Key5, '5'), (Key::Key6, '6'), (Key::Key7, '7'),
+    (Key::Key8, '8'), (Key::Key9, '9'),
+];
+
+// Plays games in a window until it's closed or the quit key is pressed; Enter starts a new
+// game once one is over
+pub(crate) fn run(cli: &Cli, config: &Config, settings: GameConfig, board: Board) -> io::Result<()> {
+    let new_game = || {
+        let players = settings.mode.players().unwrap_or(cli.players);
+        let mut game = Play::new(Game::new(board.clone(), players, cli.seed.unwrap_or_else(rand::random), settings.clone()));
+        if cli.ai_opponent {
+            game.hand_to_ai(1, cli.difficulty);
+        }
+        game.set_preferences(config.keys.clone(), config.theme.clone());
+        game
+    };
+    let mut game = new_game();
+    let (columns, rows) = (usize::from(game.state().width()), usize::from(game.state().height()));
+    let cell = (MAX_BOARD_PIXELS / columns.max(rows)).clamp(MIN_CELL, MAX_CELL);
+    let mut canvas = Canvas { width: columns * cell, cell, pixels: vec![BACKGROUND; columns * cell * rows * cell] };
+    let mut window = Window::new("snekrs", canvas.width, rows * cell, WindowOptions::default()).map_err(io::Error::other)?;
+    window.set_target_fps(FPS);
+    let mut shown_title = String::new();
+
+    while window.is_open() {
+        for key in window.get_keys_pressed(KeyRepeat::Yes) {
+            if key == Key::Enter && game.is_over() {
+                game = new_game();
+                continue;
+            }
+            let Some(action) = key_code(key).and_then(|code| config.keys.action(code, game.local_players())) else {
+                continue;
+            };
+            match action {
+                Action::Quit => return Ok(()),
+                _ if game.is_over() => {}
+                Action::Steer(player, direction) if !game.is_paused() => game.steer(player, direction),
+                Action::Turn(player, turn) if !game.is_paused() => game.turn(player, turn),
+                Action::Pause => game.toggle_pause(),
+                Action::Faster | Action::Slower => {
+                    game.change_pace(action == Action::Faster);
+                }
+                Action::Rewind => {
+                    game.step_back();
+                }
+                Action::Steer(..) | Action::Turn(..) | Action::Chat => {}
+            }
+        }
+        // Play's own tick_if_due holds the game while the terminal is too small, which
+        // doesn't apply here
+        Game::tick_if_due(&mut game);
+        canvas.paint(&game.frame(game.is_paused()), &config.theme);
+        let status = title(&game);
+        if status != shown_title {
+            window.set_title(&status);
+            shown_title = status;
+        }
+        window.update_with_buffer(&canvas.pixels, canvas.width, canvas.pixels.len() / canvas.width).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+// The window's contents, one 0RGB value per pixel, row by row
+struct Canvas {
+    width: usize,  // In pixels
+    cell: usize,   // Width and height of a board cell, in pixels
+    pixels: Vec<u32>,
+}
+
+impl Canvas {
+    // Draws every cell of a board as a square, with a one-pixel gap to the next so sneks read
+    // as segments (except on boards so big the cells are tiny)
+    fn paint(&mut self, frame: &Frame, theme: &Theme) {
+        let gap = usize::from(self.cell > 6);
+        for y in 0..frame.height() {
+            for x in 0..frame.width() {
+                let (glyph, dim) = frame.get(x, y);
+                let color = theme.pixel(glyph, frame.tone(x, y)).map_or(BACKGROUND, |color| pixel(theme::rgb(color), dim));
+                let (left, top) = (usize::from(x) * self.cell, usize::from(y) * self.cell);
+                for row in top..top + self.cell {
+                    let line = &mut self.pixels[row * self.width + left..][..self.cell];
+                    let filled = if row < top + self.cell - gap { self.cell - gap } else { 0 };
+                    line[..filled].fill(color);
+                    line[filled..].fill(BACKGROUND);
+                }
+            }
+        }
+    }
+}
+
+// An RGB color as a pixel, at half brightness when dimmed
+fn pixel([r, g, b]: [u8; 3], dim: bool) -> u32 {
+    let shift = u32::from(dim);
+    (u32::from(r) >> shift) << 16 | (u32::from(g) >> shift) << 8 | u32::from(b) >> shift
+}
+
+// The window title: the score of every snake, and what the game is waiting for
+fn title(game: &Play) -> String {
+    let text = lang::text();
+    let scores: Vec<String> = game.state().snakes.iter().map(|snake| snake.score.to_string()).collect();
+    let status = match () {
+        _ if game.is_over() => format!(" - {} {}", text.game_over, text.play_again_hint),
+        _ if game.is_paused() => format!(" - {}", text.paused),
+        _ => String::new(),
+    };
+    format!("snekrs - {}: {}{status}", text.score, scores.join(" / "))
+}
+
+// The terminal key a window key stands for, so the configured bindings apply unchanged
+fn key_code(key: Key) -> Option<KeyCode> {
+    let code = match key {
+        Key::Up => KeyCode::Up,
+        Key::Down => KeyCode::Down,
+        Key::Left => KeyCode::Left,
+        Key::Right => KeyCode::Right,
+        Key::Escape => KeyCode::Esc,
+        Key::Enter => KeyCode::Enter,
+        Key::Tab => KeyCode::Tab,
+        Key::Backspace => KeyCode::Backspace,
+        Key::Space => KeyCode::Char(' '),
+        Key::LeftBracket => KeyCode::Char('['),
+        Key::RightBracket => KeyCode::Char(']'),
+        Key::Minus => KeyCode::Char('-'),
+        Key::Equal => KeyCode::Char('='),
+        Key::Comma => KeyCode::Char(','),
+        Key::Period => KeyCode::Char('.'),
+        Key::Slash => KeyCode::Char('/'),
+        Key::Semicolon => KeyCode::Char(';'),
+        Key::Apostrophe => KeyCode::Char('\''),
+        Key::Backslash => KeyCode::Char('\\'),
+        Key::Backquote => KeyCode::Char('`'),
+        Key::F1 => KeyCode::F(1),
+        Key::F2 => KeyCode::F(2),
+        Key::F3 => KeyCode::F(3),
+        Key::F4 => KeyCode::F(4),
+        Key::F5 => KeyCode::F(5),
+        Key::F6 => KeyCode::F(6),
+        Key::F7 => KeyCode::F(7),
+        Key::F8 => KeyCode::F(8),
+        Key::F9 => KeyCode::F(9),
+        Key::F10 => KeyCode::F(10),
+        Key::F11 => KeyCode::F(11),
+        Key::F12 => KeyCode::F(12),
+        _ => KeyCode::Char(CHARACTER_KEYS.iter().find(|&&(character_key, _)| character_key == key)?.1),
+    };
+    Some(code)
+}